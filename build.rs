@@ -0,0 +1,88 @@
+//! Embeds the versions of the toolchain and the Sway crates the plugin interface depends on,
+//! so plugins built against different versions can be rejected before their detectors are used.
+
+use sha2::{Digest, Sha256};
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// The names of the crates whose types are passed across the plugin boundary.
+const SWAY_CRATE_NAMES: &[&str] = &["sway-ast", "sway-types"];
+
+/// The value embedded when a version can not be determined, which `load_plugin` always rejects.
+const UNKNOWN_VERSION: &str = "unknown";
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // Get the version of the Rust compiler building the crate
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+
+    let rustc_version = Command::new(rustc)
+        .arg("-V")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty())
+        .unwrap_or_else(|| UNKNOWN_VERSION.into());
+
+    println!("cargo:rustc-env=SWAY_ANALYZER_RUSTC_VERSION={rustc_version}");
+
+    // Hash the versions and sources of the Sway crates resolved by the lock file of the workspace building the crate
+    let sway_versions = find_lock_file().and_then(|path| {
+        println!("cargo:rerun-if-changed={}", path.display());
+        get_locked_versions(&path)
+    });
+
+    let hash = match sway_versions {
+        Some(sway_versions) => {
+            let hash = Sha256::digest(sway_versions.join("\n").as_bytes());
+            hash.iter().map(|x| format!("{x:02x}")).collect::<String>()
+        }
+
+        None => UNKNOWN_VERSION.into(),
+    };
+
+    println!("cargo:rustc-env=SWAY_ANALYZER_SWAY_VERSIONS_HASH={hash}");
+}
+
+/// Finds the lock file of the workspace building the crate. The output directory is searched first,
+/// since it is located in the workspace of the plugin when the crate is built as one of its dependencies.
+fn find_lock_file() -> Option<PathBuf> {
+    let out_dir = PathBuf::from(env::var("OUT_DIR").ok()?);
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").ok()?);
+
+    out_dir.ancestors()
+        .chain(manifest_dir.ancestors())
+        .map(|path| path.join("Cargo.lock"))
+        .find(|path| path.is_file())
+}
+
+/// Gets the sorted `name version source` triples of the Sway crates locked by the lock file at the supplied `path`,
+/// or `None` if the lock file can not be read or does not lock all of the Sway crates.
+///
+/// The source tells apart crates with the same version from different registries or git revisions, and is empty for local crates.
+fn get_locked_versions(path: &Path) -> Option<Vec<String>> {
+    let source = std::fs::read_to_string(path).ok()?;
+    let lock = source.parse::<toml::Table>().ok()?;
+    let packages = lock.get("package")?.as_array()?;
+
+    let mut versions = packages.iter()
+        .filter_map(|package| {
+            let name = package.get("name")?.as_str()?;
+            let version = package.get("version")?.as_str()?;
+            let source = package.get("source").and_then(|x| x.as_str()).unwrap_or_default();
+            SWAY_CRATE_NAMES.contains(&name).then(|| format!("{name} {version} {source}"))
+        })
+        .collect::<Vec<_>>();
+
+    if !SWAY_CRATE_NAMES.iter().all(|name| versions.iter().any(|x| x.split(' ').next() == Some(*name))) {
+        return None;
+    }
+
+    versions.sort();
+    Some(versions)
+}
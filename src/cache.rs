@@ -0,0 +1,210 @@
+use crate::{config::DetectorOptions, error::Error, report::Entry, rules::CustomRule};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+/// The name of the cache file inside of the cache directory.
+const CACHE_FILE_NAME: &str = "analysis.json";
+
+/// A persisted cache of the findings of a previous analysis, keyed by the content hashes of the analyzed files.
+///
+/// The parsed modules themselves are not cached, since the `sway_ast` types are not serializable. Instead:
+/// * If none of the files changed, the cached findings are used as-is and no files are parsed.
+/// * Otherwise only the changed files and the unchanged files of linked packages are parsed, and the cross-module detectors
+///   are the only ones that visit the unchanged files. The cached findings of the other unchanged files are used as-is.
+#[derive(Default, Deserialize, Serialize)]
+pub struct AnalysisCache {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(skip)]
+    cross_module_detectors: Vec<String>,
+    key: String,
+    files: BTreeMap<PathBuf, CachedFile>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct CachedFile {
+    hash: String,
+    entries: Vec<Entry>,
+}
+
+/// Computes the hex-encoded SHA-256 hash of the supplied `data`.
+fn hash<T: AsRef<[u8]>>(data: T) -> String {
+    Sha256::digest(data.as_ref()).iter().map(|x| format!("{x:02x}")).collect()
+}
+
+impl AnalysisCache {
+    /// Loads the cache from the supplied `directory`, discarding it if it was created by a different tool version, detector set,
+    /// detector options, custom rules or contents of the supplied `key_files`, e.g. the Forc manifests and plugins.
+    ///
+    /// The findings of the supplied `cross_module_detectors` are discarded when a file of a linked package changes.
+    pub fn load<P: AsRef<Path>>(
+        directory: P,
+        detector_names: &[&str],
+        cross_module_detectors: &[&str],
+        detector_options: &DetectorOptions,
+        custom_rules: &[CustomRule],
+        key_files: &[PathBuf],
+    ) -> Self {
+        let path = directory.as_ref().join(CACHE_FILE_NAME);
+
+        // Missing files are hashed as empty, so adding or removing one also invalidates the cache
+        let key_file_hashes = key_files.iter()
+            .map(|x| format!("{}={}", x.to_string_lossy(), hash(std::fs::read(x).unwrap_or_default())))
+            .collect::<Vec<_>>();
+
+        let key = hash(format!(
+            "{}:{}:{detector_options}:{custom_rules:?}:{}",
+            env!("CARGO_PKG_VERSION"),
+            detector_names.join(","),
+            key_file_hashes.join(","),
+        ));
+
+        let cross_module_detectors = cross_module_detectors.iter().map(|x| x.to_string()).collect();
+
+        let cache = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str::<AnalysisCache>(&text).ok())
+            .filter(|cache| cache.key == key);
+
+        match cache {
+            Some(cache) => Self { path, cross_module_detectors, ..cache },
+            None => Self { path, key, cross_module_detectors, ..Default::default() },
+        }
+    }
+
+    /// Saves the cache to its directory.
+    pub fn save(&self) -> Result<(), Error> {
+        if let Some(directory) = self.path.parent() {
+            std::fs::create_dir_all(directory).map_err(|e| Error::Wrapped(Box::new(e)))?;
+        }
+
+        let text = serde_json::to_string(self).map_err(|e| Error::Wrapped(Box::new(e)))?;
+        std::fs::write(&self.path, text).map_err(|e| Error::Wrapped(Box::new(e)))
+    }
+
+    /// Checks if the findings of the supplied `entry` only depend on the file they were found in.
+    fn is_module_local_entry(&self, entry: &Entry) -> bool {
+        entry.detector.as_ref().map(|x| !self.cross_module_detectors.contains(x)).unwrap_or(false)
+    }
+
+    /// Checks if the cached file at the supplied `path` has the same content as the supplied `source`.
+    fn is_unchanged(&self, path: &Path, source: &str) -> bool {
+        self.files.get(path).map(|x| x.hash == hash(source)).unwrap_or(false)
+    }
+
+    /// Gets all of the cached findings if the supplied `sources` are exactly the files that were cached, with unchanged content.
+    pub fn get_all_entries(&self, sources: &HashMap<PathBuf, String>) -> Option<Vec<(PathBuf, Entry)>> {
+        if sources.len() != self.files.len() || !sources.iter().all(|(path, source)| self.is_unchanged(path, source)) {
+            return None;
+        }
+
+        Some(
+            self.files.iter()
+                .flat_map(|(path, file)| file.entries.iter().map(|entry| (path.clone(), entry.clone())))
+                .collect()
+        )
+    }
+
+    /// Gets the paths of the supplied `sources` whose content is unchanged since they were cached.
+    pub fn get_unchanged_paths(&self, sources: &HashMap<PathBuf, String>) -> HashSet<PathBuf> {
+        sources.iter()
+            .filter(|(path, source)| self.is_unchanged(path, source))
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    /// Gets the paths of the cached files which are no longer part of the supplied `sources`.
+    pub fn get_removed_paths(&self, sources: &HashMap<PathBuf, String>) -> Vec<PathBuf> {
+        self.files.keys()
+            .filter(|path| !sources.contains_key(*path))
+            .cloned()
+            .collect()
+    }
+
+    /// Gets all of the cached findings of the file at the supplied `path`.
+    pub fn get_entries(&self, path: &Path) -> Vec<Entry> {
+        self.files.get(path)
+            .map(|file| file.entries.clone())
+            .unwrap_or_default()
+    }
+
+    /// Gets the cached findings of the file at the supplied `path` which do not depend on any other file.
+    pub fn get_module_local_entries(&self, path: &Path) -> Vec<Entry> {
+        self.files.get(path)
+            .map(|file| file.entries.iter().filter(|x| self.is_module_local_entry(x)).cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Replaces the cached files with the supplied `sources` and the findings of their analysis.
+    pub fn update(&mut self, sources: &HashMap<PathBuf, String>, entries: &[(PathBuf, Vec<Entry>)]) {
+        self.files = sources.iter()
+            .map(|(path, source)| {
+                let entries = entries.iter()
+                    .find(|(entry_path, _)| entry_path == path)
+                    .map(|(_, entries)| entries.clone())
+                    .unwrap_or_default();
+
+                (path.clone(), CachedFile { hash: hash(source), entries })
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AnalysisCache;
+    use crate::{project::Project, Options};
+    use std::{collections::HashMap, path::PathBuf};
+
+    #[test]
+    fn test_analysis_cache() {
+        let cache_dir = std::env::temp_dir().join(format!("sway-analyzer-cache-{}", std::process::id()));
+
+        let options = Options {
+            directory: Some(PathBuf::from("test/missing_logs/")),
+            cache_dir: Some(cache_dir.clone()),
+            ..Default::default()
+        };
+
+        let count_entries = |project: &Project| project.report.borrow().entries.iter().map(|(_, x)| x.len()).sum::<usize>();
+
+        // The first run analyzes the modules and populates the cache
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        let entry_count = count_entries(&project);
+        assert!(cache_dir.join(super::CACHE_FILE_NAME).is_file());
+
+        // The second run loads the findings from the cache without parsing any modules
+        let mut project = Project::try_from(&options).unwrap();
+        assert!(project.modules().borrow().is_empty());
+
+        project.analyze_modules().unwrap();
+        assert_eq!(count_entries(&project), entry_count);
+
+        std::fs::remove_dir_all(cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_analysis_cache_key_files() {
+        let cache_dir = std::env::temp_dir().join(format!("sway-analyzer-cache-key-{}", std::process::id()));
+        let load = |key_files: &[PathBuf]| AnalysisCache::load(&cache_dir, &["missing_logs"], &[], &Default::default(), &[], key_files);
+
+        let sources = HashMap::from([(PathBuf::from("test/missing_logs/src/main.sw"), String::new())]);
+
+        let mut cache = load(&[]);
+        cache.update(&sources, &[]);
+        cache.save().unwrap();
+
+        assert_eq!(load(&[]).files.len(), 1);
+
+        // A different set or content of the Forc files and plugins discards the cached findings
+        assert!(load(&[PathBuf::from("test/missing_logs/Forc.toml")]).files.is_empty());
+
+        std::fs::remove_dir_all(cache_dir).unwrap();
+    }
+}
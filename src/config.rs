@@ -0,0 +1,282 @@
+use crate::{
+    detectors::{DETECTOR_OPTIONS, DETECTOR_TYPES},
+    error::Error,
+    rules::{self, CustomRule},
+    Options,
+};
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+/// The name of the config file which is loaded from the project directory when no config file is supplied.
+pub const CONFIG_FILE_NAME: &str = "sway-analyzer.toml";
+
+/// The default value of a detector option, which also determines the type of values the option accepts.
+pub enum OptionDefault {
+    Integer(u64),
+    IntegerList(&'static [u64]),
+    StringList(&'static [&'static str]),
+}
+
+/// A configurable parameter declared by a detector.
+pub struct DetectorOption {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub default: OptionDefault,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OptionValue {
+    Integer(u64),
+    IntegerList(Vec<u64>),
+    StringList(Vec<String>),
+}
+
+impl From<&OptionDefault> for OptionValue {
+    fn from(value: &OptionDefault) -> Self {
+        match value {
+            OptionDefault::Integer(x) => Self::Integer(*x),
+            OptionDefault::IntegerList(x) => Self::IntegerList(x.to_vec()),
+            OptionDefault::StringList(x) => Self::StringList(x.iter().map(|x| x.to_string()).collect()),
+        }
+    }
+}
+
+impl Display for OptionValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(x) => write!(f, "{x}"),
+            Self::IntegerList(x) => write!(f, "{}", x.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(",")),
+            Self::StringList(x) => write!(f, "{}", x.join(",")),
+        }
+    }
+}
+
+impl OptionDefault {
+    /// Gets the name of the type of values accepted by the option.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Integer(_) => "integer",
+            Self::IntegerList(_) => "integer list",
+            Self::StringList(_) => "string list",
+        }
+    }
+
+    /// Parses the supplied comma-separated `text` into a value of the same type as the default value.
+    fn parse(&self, text: &str) -> Option<OptionValue> {
+        let items = text.split(',').map(str::trim).filter(|x| !x.is_empty());
+
+        match self {
+            Self::Integer(_) => text.trim().parse().ok().map(OptionValue::Integer),
+            Self::IntegerList(_) => items.map(|x| x.parse().ok()).collect::<Option<_>>().map(OptionValue::IntegerList),
+            Self::StringList(_) => Some(OptionValue::StringList(items.map(str::to_string).collect())),
+        }
+    }
+
+    /// Converts the supplied TOML `value` into a value of the same type as the default value.
+    fn toml_to_value(&self, value: &toml::Value) -> Option<OptionValue> {
+        match (self, value) {
+            (Self::Integer(_), toml::Value::Integer(x)) => u64::try_from(*x).ok().map(OptionValue::Integer),
+
+            (Self::IntegerList(_), toml::Value::Array(x)) => x.iter()
+                .map(|x| x.as_integer().and_then(|x| u64::try_from(x).ok()))
+                .collect::<Option<_>>()
+                .map(OptionValue::IntegerList),
+
+            (Self::StringList(_), toml::Value::Array(x)) => x.iter()
+                .map(|x| x.as_str().map(str::to_string))
+                .collect::<Option<_>>()
+                .map(OptionValue::StringList),
+
+            (_, toml::Value::String(x)) => self.parse(x),
+
+            _ => None,
+        }
+    }
+}
+
+/// Gets the declaration of the `option_name` option of the `detector_name` detector.
+fn find_option(detector_name: &str, option_name: &str) -> Result<&'static DetectorOption, Error> {
+    let Some((_, options)) = DETECTOR_OPTIONS.iter().find(|(name, _)| *name == detector_name) else {
+        return Err(Error::InvalidDetectorOption(format!("Detector `{detector_name}` does not have any options")));
+    };
+
+    options.iter()
+        .find(|x| x.name == option_name)
+        .ok_or_else(|| Error::InvalidDetectorOption(format!("Detector `{detector_name}` does not have an option named `{option_name}`")))
+}
+
+/// The values of the detector options which were overridden by the config file or the command line.
+#[derive(Clone, Debug, Default)]
+pub struct DetectorOptions {
+    values: BTreeMap<(String, String), OptionValue>,
+}
+
+impl Display for DetectorOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, ((detector_name, option_name), value)) in self.values.iter().enumerate() {
+            if i > 0 {
+                write!(f, ";")?;
+            }
+
+            write!(f, "{detector_name}.{option_name}={value}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The contents of the config file, combined with the detector options supplied on the command line.
+#[derive(Default)]
+pub struct Config {
+    pub detector_options: DetectorOptions,
+    pub rules: Vec<CustomRule>,
+}
+
+impl TryFrom<&Options> for Config {
+    type Error = Error;
+
+    fn try_from(options: &Options) -> Result<Self, Self::Error> {
+        let mut result = Self::default();
+
+        // Load the config file, falling back to the one in the project directory if it exists
+        let config_path = options.config.clone().or_else(|| {
+            options.directory.as_ref()
+                .map(|x| x.join(CONFIG_FILE_NAME))
+                .filter(|x| x.is_file())
+        });
+
+        if let Some(config_path) = config_path {
+            result.load_file(config_path)?;
+        }
+
+        // Options supplied on the command line take precedence over the config file
+        for arg in options.detector_options.iter() {
+            result.detector_options.set_from_arg(arg)?;
+        }
+
+        Ok(result)
+    }
+}
+
+impl Config {
+    /// Loads the `[detectors.<name>]` option tables and the `[[rules]]` custom rules of the config file at the supplied `path`.
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| Error::Wrapped(Box::new(e)))?;
+        let config: toml::Table = toml::from_str(&text).map_err(|_| Error::ConfigParseFailed(PathBuf::from(path)))?;
+
+        if let Some(detectors) = config.get("detectors") {
+            self.detector_options.load_toml(path, detectors)?;
+        }
+
+        if let Some(rules) = config.get("rules") {
+            let rules: Vec<CustomRule> = rules.clone().try_into().map_err(|e| Error::InvalidCustomRule(format!("{e}")))?;
+            self.rules.extend(rules);
+
+            let detector_names = DETECTOR_TYPES.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+            rules::validate_rules(&self.rules, &detector_names)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl DetectorOptions {
+    /// Loads the option values from the `[detectors.<name>]` tables of the config file at the supplied `path`.
+    fn load_toml(&mut self, path: &Path, detectors: &toml::Value) -> Result<(), Error> {
+        let Some(detectors) = detectors.as_table() else { return Err(Error::ConfigParseFailed(PathBuf::from(path))) };
+
+        for (detector_name, options) in detectors {
+            let Some(options) = options.as_table() else { return Err(Error::ConfigParseFailed(PathBuf::from(path))) };
+
+            for (option_name, value) in options {
+                let option = find_option(detector_name, option_name)?;
+
+                let value = option.default.toml_to_value(value).ok_or_else(|| Error::InvalidDetectorOption(
+                    format!("Expected {} value for `{detector_name}.{option_name}` in \"{}\"", option.default.type_name(), path.to_string_lossy())
+                ))?;
+
+                self.values.insert((detector_name.clone(), option_name.clone()), value);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets an option value from the supplied `arg` in the form of `<detector>.<option>=<value>`.
+    pub fn set_from_arg(&mut self, arg: &str) -> Result<(), Error> {
+        let invalid = || Error::InvalidDetectorOption(format!("Expected `<detector>.<option>=<value>`, found `{arg}`"));
+
+        let (key, text) = arg.split_once('=').ok_or_else(invalid)?;
+        let (detector_name, option_name) = key.trim().split_once('.').ok_or_else(invalid)?;
+        let option = find_option(detector_name, option_name)?;
+
+        let value = option.default.parse(text).ok_or_else(|| Error::InvalidDetectorOption(
+            format!("Expected {} value for `{detector_name}.{option_name}`, found `{text}`", option.default.type_name())
+        ))?;
+
+        self.values.insert((detector_name.to_string(), option_name.to_string()), value);
+
+        Ok(())
+    }
+
+    /// Gets the value of the `option_name` option of the `detector_name` detector, or its default value if it was not overridden.
+    ///
+    /// Panics if the detector does not declare the option.
+    pub fn get(&self, detector_name: &str, option_name: &str) -> OptionValue {
+        if let Some(value) = self.values.get(&(detector_name.to_string(), option_name.to_string())) {
+            return value.clone();
+        }
+
+        let option = find_option(detector_name, option_name).unwrap_or_else(|e| panic!("{e}"));
+        OptionValue::from(&option.default)
+    }
+
+    /// Gets the value of the `option_name` integer option of the `detector_name` detector.
+    pub fn integer(&self, detector_name: &str, option_name: &str) -> u64 {
+        match self.get(detector_name, option_name) {
+            OptionValue::Integer(x) => x,
+            _ => panic!("Option `{detector_name}.{option_name}` is not an integer"),
+        }
+    }
+
+    /// Gets the value of the `option_name` integer list option of the `detector_name` detector.
+    pub fn integer_list(&self, detector_name: &str, option_name: &str) -> Vec<u64> {
+        match self.get(detector_name, option_name) {
+            OptionValue::IntegerList(x) => x,
+            _ => panic!("Option `{detector_name}.{option_name}` is not an integer list"),
+        }
+    }
+
+    /// Gets the value of the `option_name` string list option of the `detector_name` detector.
+    pub fn string_list(&self, detector_name: &str, option_name: &str) -> Vec<String> {
+        match self.get(detector_name, option_name) {
+            OptionValue::StringList(x) => x,
+            _ => panic!("Option `{detector_name}.{option_name}` is not a string list"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DetectorOptions, OptionValue};
+
+    #[test]
+    fn test_detector_options() {
+        let mut options = DetectorOptions::default();
+
+        // Options which were not overridden use their default values
+        assert_eq!(options.integer_list("magic_number", "allowed"), vec![0, 1]);
+
+        options.set_from_arg("magic_number.allowed=0,1,10").unwrap();
+        assert_eq!(options.get("magic_number", "allowed"), OptionValue::IntegerList(vec![0, 1, 10]));
+
+        // Unknown options and values of the wrong type are rejected
+        assert!(options.set_from_arg("magic_number.unknown=1").is_err());
+        assert!(options.set_from_arg("magic_number.allowed=one").is_err());
+        assert!(options.set_from_arg("magic_number").is_err());
+    }
+}
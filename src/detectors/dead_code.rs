@@ -0,0 +1,423 @@
+use crate::{
+    error::Error,
+    project::Project,
+    report::Severity,
+    scope::AstScope,
+    utils,
+    visitor::{
+        AbiContext, AstVisitor, BlockContext, ConfigurableFieldContext, ConstContext, EnumContext,
+        EnumFieldContext, ExprContext, FnContext, IfExprContext, ImplContext, MatchBranchContext,
+        ModuleContext, StatementLetContext, StorageFieldContext, StructContext, StructFieldContext,
+        TraitContext, TypeAliasContext,
+    },
+};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+use sway_ast::{
+    CodeBlockContents, Expr, FnArgs, FnSignature, IfCondition, ItemImplItem, ItemKind,
+    ItemTraitItem, Statement,
+};
+use sway_types::{Span, Spanned};
+
+#[derive(Default)]
+pub struct DeadCodeVisitor {
+    trait_names: Option<HashSet<String>>,
+    module_states: HashMap<PathBuf, ModuleState>,
+    trait_impl_declarations: Vec<Declaration>,
+}
+
+#[derive(Default)]
+struct ModuleState {
+    used_names: HashSet<String>,
+    declarations: Vec<Declaration>,
+}
+
+struct Declaration {
+    path: PathBuf,
+    line: Option<usize>,
+    description: String,
+    names: Vec<String>,
+}
+
+impl DeadCodeVisitor {
+    fn use_span(&mut self, path: &Path, span: &Span) {
+        let module_state = self.module_states.get_mut(path).unwrap();
+        module_state.used_names.insert(span.as_str().to_string());
+    }
+
+    fn use_fn_signature(&mut self, path: &Path, fn_signature: &FnSignature) {
+        let args = match &fn_signature.arguments.inner {
+            FnArgs::Static(args) => Some(args),
+            FnArgs::NonStatic { args_opt, .. } => args_opt.as_ref().map(|(_, args)| args),
+        };
+
+        for arg in args.into_iter().flatten() {
+            utils::map_pattern_usage(&arg.pattern, &mut |span| self.use_span(path, span));
+            utils::map_ty_usage(&arg.ty, &mut |span| self.use_span(path, span));
+        }
+
+        if let Some((_, ty)) = fn_signature.return_type_opt.as_ref() {
+            utils::map_ty_usage(ty, &mut |span| self.use_span(path, span));
+        }
+    }
+
+    /// Stores the declaration of a private item, which can only be used in the module at the supplied `path`.
+    fn add_declaration(&mut self, path: &Path, line: Option<usize>, description: String, names: Vec<String>) {
+        let module_state = self.module_states.get_mut(path).unwrap();
+
+        module_state.declarations.push(Declaration {
+            path: path.into(),
+            line,
+            description,
+            names,
+        });
+    }
+}
+
+/// Reports the supplied `declaration` as unused.
+fn report_declaration(project: &mut Project, declaration: &Declaration) {
+    project.report.borrow_mut().add_entry(
+        declaration.path.as_path(),
+        declaration.line,
+        Severity::Low,
+        format!(
+            "{} is never used. Consider removing it to reduce the bytecode size.",
+            declaration.description,
+        ),
+    );
+}
+
+/// Gets the statement or expression which follows an unconditional `return`, `revert`, `panic`, `break` or `continue` in the supplied `block`.
+fn find_unreachable_code(block: &CodeBlockContents) -> Option<(&Expr, Span)> {
+    let is_terminator = |expr: &Expr| match expr {
+        Expr::Return { .. } | Expr::Break { .. } | Expr::Continue { .. } | Expr::Panic { .. } => true,
+        expr => utils::get_fn_call_name(expr).map(|x| x == "revert").unwrap_or(false),
+    };
+
+    for (index, statement) in block.statements.iter().enumerate() {
+        let Statement::Expr { expr, .. } = statement else { continue };
+
+        if !is_terminator(expr) {
+            continue;
+        }
+
+        if let Some(next_statement) = block.statements.get(index + 1) {
+            return Some((expr, next_statement.span()));
+        }
+
+        if let Some(final_expr) = block.final_expr_opt.as_ref() {
+            return Some((expr, final_expr.span()));
+        }
+    }
+
+    None
+}
+
+impl AstVisitor for DeadCodeVisitor {
+    fn is_cross_module(&self) -> bool {
+        true
+    }
+
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Collect the traits declared in each module of the project ahead of time
+        if self.trait_names.is_none() {
+            let mut trait_names = HashSet::new();
+
+            for module in project.modules().borrow().values() {
+                for item in module.items.iter() {
+                    if let ItemKind::Trait(item_trait) = &item.value {
+                        trait_names.insert(item_trait.name.as_str().to_string());
+                    }
+                }
+            }
+
+            self.trait_names = Some(trait_names);
+        }
+
+        // Create the module state
+        self.module_states.entry(context.path.into()).or_default();
+
+        Ok(())
+    }
+
+    fn leave_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get(context.path).unwrap();
+
+        // Check the private items of the module, which can only be used by the module itself
+        for declaration in module_state.declarations.iter() {
+            if !declaration.names.iter().any(|x| module_state.used_names.contains(x)) {
+                report_declaration(project, declaration);
+            }
+        }
+
+        // Wait until every module of the project has been visited, since trait implementations can be used from other modules
+        if self.module_states.len() < project.modules().borrow().len() {
+            return Ok(());
+        }
+
+        for declaration in self.trait_impl_declarations.iter() {
+            if !self.module_states.values().any(|x| declaration.names.iter().any(|name| x.used_names.contains(name))) {
+                report_declaration(project, declaration);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_struct(&mut self, context: &StructContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Store private structs
+        if context.item_struct.visibility.is_some() {
+            return Ok(());
+        }
+
+        let name = context.item_struct.name.as_str().to_string();
+
+        self.add_declaration(
+            context.path,
+            project.span_to_line(context.path, &context.item_struct.name.span())?,
+            format!("The `{name}` struct"),
+            vec![name],
+        );
+
+        Ok(())
+    }
+
+    fn visit_struct_field(&mut self, context: &StructFieldContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        utils::map_ty_usage(&context.field.ty, &mut |span| self.use_span(context.path, span));
+
+        Ok(())
+    }
+
+    fn visit_enum(&mut self, context: &EnumContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Store private enums
+        if context.item_enum.visibility.is_some() {
+            return Ok(());
+        }
+
+        let name = context.item_enum.name.as_str().to_string();
+
+        self.add_declaration(
+            context.path,
+            project.span_to_line(context.path, &context.item_enum.name.span())?,
+            format!("The `{name}` enum"),
+            vec![name],
+        );
+
+        Ok(())
+    }
+
+    fn visit_enum_field(&mut self, context: &EnumFieldContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        utils::map_ty_usage(&context.field.ty, &mut |span| self.use_span(context.path, span));
+
+        Ok(())
+    }
+
+    fn visit_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        self.use_fn_signature(context.path, &context.item_fn.fn_signature);
+
+        // Store private functions, except for entry points, tests and trait implementations
+        let fn_signature = &context.item_fn.fn_signature;
+        let name = fn_signature.name.as_str();
+
+        if fn_signature.visibility.is_some() || utils::check_attribute_decls(context.fn_attributes, "test", &[]) {
+            return Ok(());
+        }
+
+        if context.item_impl.is_none() && name == "main" {
+            return Ok(());
+        }
+
+        if context.item_impl.map(|x| x.trait_opt.is_some()).unwrap_or(false) {
+            return Ok(());
+        }
+
+        self.add_declaration(
+            context.path,
+            project.span_to_line(context.path, &fn_signature.span())?,
+            utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
+            vec![name.to_string()],
+        );
+
+        Ok(())
+    }
+
+    fn visit_block(&mut self, context: &BlockContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Check for code which follows an unconditional `return`, `revert`, `panic`, `break` or `continue`
+        let Some((terminator, span)) = find_unreachable_code(&context.block.inner) else { return Ok(()) };
+
+        project.report.borrow_mut().add_entry(
+            context.path,
+            project.span_to_line(context.path, &span)?,
+            Severity::Low,
+            format!(
+                "{} contains unreachable code after `{}`: `{}`. Consider removing the unreachable code.",
+                utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
+                terminator.span().as_str(),
+                span.as_str(),
+            ),
+        );
+
+        Ok(())
+    }
+
+    fn visit_statement_let(&mut self, context: &StatementLetContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        utils::map_pattern_usage(&context.statement_let.pattern, &mut |span| self.use_span(context.path, span));
+
+        if let Some((_, ty)) = context.statement_let.ty_opt.as_ref() {
+            utils::map_ty_usage(ty, &mut |span| self.use_span(context.path, span));
+        }
+
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        match context.expr {
+            Expr::Path(path_expr) => {
+                utils::map_path_expr_usage(path_expr, &mut |span| self.use_span(context.path, span));
+            }
+
+            Expr::Struct { path, .. } => {
+                utils::map_path_expr_usage(path, &mut |span| self.use_span(context.path, span));
+            }
+
+            Expr::MethodCall { path_seg, .. } => {
+                self.use_span(context.path, &path_seg.name.span());
+            }
+
+            Expr::AbiCast { args, .. } => {
+                utils::map_path_type_usage(&args.inner.name, &mut |span| self.use_span(context.path, span));
+            }
+
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn visit_if_expr(&mut self, context: &IfExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        if let IfCondition::Let { lhs, .. } = &context.if_expr.condition {
+            utils::map_pattern_usage(lhs, &mut |span| self.use_span(context.path, span));
+        }
+
+        Ok(())
+    }
+
+    fn visit_match_branch(&mut self, context: &MatchBranchContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        utils::map_pattern_usage(&context.branch.pattern, &mut |span| self.use_span(context.path, span));
+
+        Ok(())
+    }
+
+    fn visit_trait(&mut self, context: &TraitContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        for item in context.item_trait.trait_items.inner.iter() {
+            if let ItemTraitItem::Fn(fn_signature, _) = &item.value {
+                self.use_fn_signature(context.path, fn_signature);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_impl(&mut self, context: &ImplContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Store implementations of the traits declared in the project
+        let Some((path_type, _)) = context.item_impl.trait_opt.as_ref() else { return Ok(()) };
+        let segment = path_type.suffix.last().map(|(_, x)| x).unwrap_or(&path_type.prefix);
+        let trait_name = segment.name.as_str();
+
+        if !self.trait_names.as_ref().unwrap().contains(trait_name) {
+            return Ok(());
+        }
+
+        let fn_names = context.item_impl.contents.inner.iter()
+            .filter_map(|item| match &item.value {
+                ItemImplItem::Fn(item_fn) => Some(item_fn.fn_signature.name.as_str().to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if fn_names.is_empty() {
+            return Ok(());
+        }
+
+        self.trait_impl_declarations.push(Declaration {
+            path: context.path.into(),
+            line: project.span_to_line(context.path, &path_type.span())?,
+            description: format!(
+                "The `{trait_name}` implementation for `{}`",
+                context.item_impl.ty.span().as_str(),
+            ),
+            names: fn_names,
+        });
+
+        Ok(())
+    }
+
+    fn visit_abi(&mut self, context: &AbiContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        for item in context.item_abi.abi_items.inner.iter() {
+            if let ItemTraitItem::Fn(fn_signature, _) = &item.value {
+                self.use_fn_signature(context.path, fn_signature);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_const(&mut self, context: &ConstContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        if let Some((_, ty)) = context.item_const.ty_opt.as_ref() {
+            utils::map_ty_usage(ty, &mut |span| self.use_span(context.path, span));
+        }
+
+        if let Some(expr) = context.item_const.expr_opt.as_ref() {
+            utils::map_expr_usage(expr, &mut |span| self.use_span(context.path, span));
+        }
+
+        // Store private module-level constants
+        if context.item_const.pub_token.is_some() || context.item_impl.is_some() || context.item_fn.is_some() {
+            return Ok(());
+        }
+
+        let name = context.item_const.name.as_str().to_string();
+
+        self.add_declaration(
+            context.path,
+            project.span_to_line(context.path, &context.item_const.name.span())?,
+            format!("The `{name}` constant"),
+            vec![name],
+        );
+
+        Ok(())
+    }
+
+    fn visit_storage_field(&mut self, context: &StorageFieldContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        utils::map_ty_usage(&context.field.ty, &mut |span| self.use_span(context.path, span));
+        utils::map_expr_usage(&context.field.initializer, &mut |span| self.use_span(context.path, span));
+
+        Ok(())
+    }
+
+    fn visit_configurable_field(&mut self, context: &ConfigurableFieldContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        utils::map_ty_usage(&context.field.ty, &mut |span| self.use_span(context.path, span));
+        utils::map_expr_usage(&context.field.initializer, &mut |span| self.use_span(context.path, span));
+
+        Ok(())
+    }
+
+    fn visit_type_alias(&mut self, context: &TypeAliasContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        utils::map_ty_usage(&context.item_type_alias.ty, &mut |span| self.use_span(context.path, span));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_dead_code() {
+        crate::tests::test_detector("dead_code", 8);
+    }
+}
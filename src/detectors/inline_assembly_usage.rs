@@ -4,25 +4,196 @@ use crate::{
     report::Severity,
     scope::AstScope,
     utils,
-    visitor::{AsmBlockContext, AstVisitor},
+    visitor::{AsmBlockContext, AsmInstructionContext, AstVisitor, ModuleContext},
 };
-use std::{cell::RefCell, rc::Rc};
-use sway_types::Spanned;
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
+use sway_ast::FnArgs;
+use sway_types::{Span, Spanned};
 
 #[derive(Default)]
-pub struct InlineAssemblyUsageVisitor;
+pub struct InlineAssemblyUsageVisitor {
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+#[derive(Default)]
+struct ModuleState {
+    asm_block_states: HashMap<Span, AsmBlockState>,
+}
+
+#[derive(Default)]
+struct AsmBlockState {
+    declared_registers: Vec<String>,
+    arg_registers: Vec<String>,
+    instructions: Vec<(AsmCategory, String, Span)>, // (category, op code, instruction span)
+}
+
+/// The categories of FuelVM instructions, based on the side effects they can have.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AsmCategory {
+    ReadOnly,
+    MemoryWriting,
+    ControlFlow,
+    AssetMoving,
+    Unknown,
+}
+
+impl AsmCategory {
+    /// The dangerous categories, ordered by decreasing severity.
+    const DANGEROUS: &'static [AsmCategory] = &[
+        AsmCategory::AssetMoving,
+        AsmCategory::ControlFlow,
+        AsmCategory::Unknown,
+        AsmCategory::MemoryWriting,
+    ];
+
+    fn from_op_code(op_code: &str) -> Self {
+        match op_code {
+            "tr" | "tro" | "mint" | "burn" | "smo" => Self::AssetMoving,
+
+            "call" | "ldc" | "ret" | "retd" | "jmp" | "ji" | "jmpf" | "jmpb" | "jne" | "jnei" | "jnzi"
+            | "jnef" | "jneb" | "jnzf" | "jnzb" | "ecal" => Self::ControlFlow,
+
+            "sb" | "sw" | "mcl" | "mcli" | "mcp" | "mcpi" | "sww" | "swwq" | "scwq" | "srwq"
+            | "s256" | "k256" | "eck1" | "ecr1" | "ecop" | "ccp" | "bhsh" | "croo" | "cb" | "bldd"
+            | "aloc" | "cfe" | "cfei" | "cfs" | "cfsi" | "pshh" | "pshl" | "poph" | "popl"
+            | "wdop" | "wqop" | "wdml" | "wqml" | "wddv" | "wqdv" | "wdmd" | "wqmd" | "wdam" | "wqam" | "wdmm" | "wqmm" => Self::MemoryWriting,
+
+            "add" | "addi" | "and" | "andi" | "div" | "divi" | "eq" | "exp" | "expi" | "gt" | "lt" | "mlog" | "mod" | "modi"
+            | "move" | "movi" | "mroo" | "mul" | "muli" | "mldv" | "not" | "or" | "ori" | "sll" | "slli" | "srl" | "srli"
+            | "sub" | "subi" | "xor" | "xori" | "wdcm" | "wqcm" | "lb" | "lw" | "meq" | "bal" | "bhei" | "bsiz" | "csiz"
+            | "gm" | "gtf" | "srw" | "time" | "log" | "logd" | "ed19" | "epar" | "rvrt" | "noop" => Self::ReadOnly,
+
+            // Instructions without a known classification could have any side effect
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Checks if the memory-writing `op_code` writes to the memory pointed to by its first register argument.
+    fn writes_to_destination_register(op_code: &str) -> bool {
+        !matches!(
+            op_code,
+            "sww" | "swwq" | "scwq" | "aloc" | "cfe" | "cfei" | "cfs" | "cfsi" | "pshh" | "pshl" | "poph" | "popl"
+        )
+    }
+
+    fn severity(&self) -> Severity {
+        match self {
+            Self::ReadOnly => Severity::Low,
+            Self::MemoryWriting => Severity::Low,
+            Self::ControlFlow => Severity::Medium,
+            Self::AssetMoving => Severity::High,
+            Self::Unknown => Severity::Medium,
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Self::ReadOnly => "only reads state",
+            Self::MemoryWriting => "writes to caller-provided memory, the stack, the heap or storage",
+            Self::ControlFlow => "alters the control flow",
+            Self::AssetMoving => "moves assets",
+            Self::Unknown => "has unclassified side effects",
+        }
+    }
+}
 
 impl AstVisitor for InlineAssemblyUsageVisitor {
-    fn visit_asm_block(&mut self, context: &AsmBlockContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        project.report.borrow_mut().add_entry(
-            context.path,
-            project.span_to_line(context.path, &context.asm.span())?,
-            Severity::Medium,
-            format!(
-                "{} contains inline assembly usage.",
-                utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
-            ),
-        );
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Create the module state
+        if !self.module_states.contains_key(context.path) {
+            self.module_states.insert(context.path.into(), ModuleState::default());
+        }
+
+        Ok(())
+    }
+
+    fn visit_asm_block(&mut self, context: &AsmBlockContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Create the `asm` block state
+        let asm_block_state = module_state.asm_block_states.entry(context.asm.span()).or_default();
+
+        // Get the names of the function's arguments
+        let arg_names = match &context.item_fn.fn_signature.arguments.inner {
+            FnArgs::Static(args) => Some(args),
+            FnArgs::NonStatic { args_opt, .. } => args_opt.as_ref().map(|(_, args)| args),
+        }
+        .into_iter()
+        .flatten()
+        .flat_map(|arg| utils::fold_pattern_idents(&arg.pattern))
+        .map(|ident| ident.as_str().to_string())
+        .collect::<Vec<_>>();
+
+        // Track the declared registers, and the registers which are initialized from function arguments
+        for register in &context.asm.registers.inner {
+            let register_name = register.register.as_str().to_string();
+
+            if let Some((_, value)) = register.value_opt.as_ref() {
+                if arg_names.iter().any(|x| x == value.span().as_str()) {
+                    asm_block_state.arg_registers.push(register_name.clone());
+                }
+            }
+
+            asm_block_state.declared_registers.push(register_name);
+        }
+
+        Ok(())
+    }
+
+    fn visit_asm_instruction(&mut self, context: &AsmInstructionContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Get the `asm` block state
+        let asm_block_state = module_state.asm_block_states.get_mut(&context.asm.span()).unwrap();
+
+        // Classify the instruction
+        let op_code = context.instruction.op_code_ident().as_str().to_string();
+        let mut category = AsmCategory::from_op_code(op_code.as_str());
+
+        // Memory writes through a destination register are only dangerous when it is caller-provided or a reserved register
+        if category == AsmCategory::MemoryWriting && AsmCategory::writes_to_destination_register(op_code.as_str()) {
+            let register_arg_idents = context.instruction.register_arg_idents();
+
+            if let Some(destination) = register_arg_idents.first() {
+                let destination = destination.as_str().to_string();
+
+                if asm_block_state.declared_registers.contains(&destination) && !asm_block_state.arg_registers.contains(&destination) {
+                    category = AsmCategory::ReadOnly;
+                }
+            }
+        }
+
+        asm_block_state.instructions.push((category, op_code, context.instruction.span()));
+
+        Ok(())
+    }
+
+    fn leave_asm_block(&mut self, context: &AsmBlockContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get(context.path).unwrap();
+
+        // Get the `asm` block state
+        let asm_block_state = module_state.asm_block_states.get(&context.asm.span()).unwrap();
+
+        // Report the first instruction of each dangerous category
+        for category in AsmCategory::DANGEROUS {
+            let Some((_, op_code, instruction_span)) = asm_block_state.instructions.iter().find(|(x, _, _)| x == category) else { continue };
+
+            project.report.borrow_mut().add_entry(
+                context.path,
+                project.span_to_line(context.path, instruction_span)?,
+                category.severity(),
+                format!(
+                    "{} contains inline assembly which {} with the `{}` instruction: `{}`.",
+                    utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
+                    category.description(),
+                    op_code.to_uppercase(),
+                    instruction_span.as_str(),
+                ),
+            );
+        }
 
         Ok(())
     }
@@ -32,6 +203,6 @@ impl AstVisitor for InlineAssemblyUsageVisitor {
 mod tests {
     #[test]
     fn test_inline_assembly_usage() {
-        crate::tests::test_detector("inline_assembly_usage", 2);
+        crate::tests::test_detector("inline_assembly_usage", 7);
     }
 }
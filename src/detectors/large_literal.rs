@@ -1,4 +1,5 @@
 use crate::{
+    config::{DetectorOption, OptionDefault},
     error::Error,
     project::Project,
     report::Severity,
@@ -13,13 +14,25 @@ use sway_types::Spanned;
 #[derive(Default)]
 pub struct LargeLiteralVisitor;
 
+impl LargeLiteralVisitor {
+    pub const OPTIONS: &'static [DetectorOption] = &[
+        DetectorOption {
+            name: "min_digits",
+            description: "The minimum number of digits of an integer literal to be considered large.",
+            default: OptionDefault::Integer(7),
+        },
+    ];
+}
+
 impl AstVisitor for LargeLiteralVisitor {
     fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
         let Expr::Literal(Literal::Int(i)) = context.expr else { return Ok(()) };
 
         let value = i.span.as_str();
 
-        if value.starts_with("0x") || value.contains('_') || value.len() <= 6 {
+        let min_digits = project.detector_options().integer("large_literal", "min_digits");
+
+        if value.starts_with("0x") || value.contains('_') || (value.len() as u64) < min_digits {
             return Ok(());
         }
 
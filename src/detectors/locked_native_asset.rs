@@ -1,4 +1,5 @@
 use crate::{
+    config::{DetectorOption, OptionDefault},
     error::Error,
     project::Project,
     report::Severity,
@@ -14,6 +15,22 @@ pub struct LockedNativeAssetVisitor {
     module_states: HashMap<PathBuf, ModuleState>,
 }
 
+impl LockedNativeAssetVisitor {
+    pub const OPTIONS: &'static [DetectorOption] = &[
+        DetectorOption {
+            name: "transfer_functions",
+            description: "The paths of the functions which transfer native assets out of the contract.",
+            default: OptionDefault::StringList(&[
+                "std::asset::transfer",
+                "std::asset::transfer_to_address",
+                "std::asset::force_transfer_to_contract",
+                "std::low_level_call::call_with_function_selector",
+            ]),
+        },
+    ];
+}
+
+#[derive(Default)]
 struct ModuleState {
     fn_calls_to_check: Vec<String>,
     locking_functions: Vec<(Span, String)>,
@@ -21,27 +38,14 @@ struct ModuleState {
     has_withdraw: bool,
 }
 
-impl Default for ModuleState {
-    fn default() -> Self {
-        Self {
-            fn_calls_to_check: vec![
-                "std::asset::transfer".into(),
-                "std::asset::transfer_to_address".into(),
-                "std::asset::force_transfer_to_contract".into(),
-                "std::low_level_call::call_with_function_selector".into(),
-            ],
-            locking_functions: vec![],
-            can_receive: false,
-            has_withdraw: false,
-        }
-    }
-}
-
 impl AstVisitor for LockedNativeAssetVisitor {
-    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
         // Create the module state
         if !self.module_states.contains_key(context.path) {
-            self.module_states.insert(context.path.into(), ModuleState::default());
+            self.module_states.insert(context.path.into(), ModuleState {
+                fn_calls_to_check: project.detector_options().string_list("locked_native_asset", "transfer_functions"),
+                ..Default::default()
+            });
         }
 
         Ok(())
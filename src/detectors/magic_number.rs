@@ -1,4 +1,5 @@
 use crate::{
+    config::{DetectorOption, OptionDefault},
     error::Error,
     project::Project,
     scope::AstScope,
@@ -19,6 +20,16 @@ pub struct MagicNumberVisitor {
     module_states: HashMap<PathBuf, ModuleState>,
 }
 
+impl MagicNumberVisitor {
+    pub const OPTIONS: &'static [DetectorOption] = &[
+        DetectorOption {
+            name: "allowed",
+            description: "The integer values which are not considered magic numbers.",
+            default: OptionDefault::IntegerList(&[0, 1]),
+        },
+    ];
+}
+
 #[derive(Default)]
 struct ModuleState {
     visited_statements: HashSet<Span>,
@@ -49,9 +60,11 @@ impl AstVisitor for MagicNumberVisitor {
         }
 
         // Skip commonly-used values
+        let allowed = project.detector_options().integer_list("magic_number", "allowed");
+
         for x in [lhs, rhs] {
             if let Expr::Literal(Literal::Int(value)) = x {
-                if value.parsed == 0u8.into() || value.parsed == 1u8.into() {
+                if allowed.iter().any(|x| value.parsed == (*x).into()) {
                     return Ok(());
                 }
             }
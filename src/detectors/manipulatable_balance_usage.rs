@@ -1,4 +1,5 @@
 use crate::{
+    config::{DetectorOption, OptionDefault},
     error::Error,
     project::Project,
     report::Severity,
@@ -15,32 +16,40 @@ pub struct ManipulatableBalanceUsageVisitor {
     module_states: HashMap<PathBuf, ModuleState>,
 }
 
+impl ManipulatableBalanceUsageVisitor {
+    pub const OPTIONS: &'static [DetectorOption] = &[
+        DetectorOption {
+            name: "transfer_functions",
+            description: "The paths of the functions which transfer native assets out of the contract.",
+            default: OptionDefault::StringList(&[
+                "std::asset::transfer",
+                "std::asset::transfer_to_address",
+                "std::asset::force_transfer_to_contract",
+                "std::low_level_call::call_with_function_selector",
+            ]),
+        },
+    ];
+}
+
+#[derive(Default)]
 struct ModuleState {
     balances: HashMap<Span, String>,
     balances_used: HashMap<Span, String>,
+    transfer_fns: Vec<String>,
     fn_calls_to_check: Vec<String>,
 }
 
-impl Default for ModuleState {
-    fn default() -> Self {
-        Self {
-            balances: HashMap::new(),
-            balances_used: HashMap::new(),
-            fn_calls_to_check: vec![
-                "std::asset::transfer".into(),
-                "std::asset::transfer_to_address".into(),
-                "std::asset::force_transfer_to_contract".into(),
-                "std::low_level_call::call_with_function_selector".into(),
-            ],
-        }
-    }
-}
-
 impl AstVisitor for ManipulatableBalanceUsageVisitor {
-    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
         // Create the module state
         if !self.module_states.contains_key(context.path) {
-            self.module_states.insert(context.path.into(), ModuleState::default());
+            let transfer_fns = project.detector_options().string_list("manipulatable_balance_usage", "transfer_functions");
+
+            self.module_states.insert(context.path.into(), ModuleState {
+                fn_calls_to_check: transfer_fns.clone(),
+                transfer_fns,
+                ..Default::default()
+            });
         }
 
         Ok(())
@@ -50,24 +59,11 @@ impl AstVisitor for ManipulatableBalanceUsageVisitor {
         // Get the module state
         let module_state = self.module_states.get_mut(context.path).unwrap();
 
-        // Check the use tree for `std::asset::transfer`
-        if let Some(name) = utils::use_tree_to_name(&context.item_use.tree, "std::asset::transfer") {
-            module_state.fn_calls_to_check.push(name);
-        }
-
-        // Check the use tree for `std::asset::transfer_to_address`
-        if let Some(name) = utils::use_tree_to_name(&context.item_use.tree, "std::asset::transfer_to_address") {
-            module_state.fn_calls_to_check.push(name);
-        }
-
-        // Check the use tree for `std::asset::force_transfer_to_contract`
-        if let Some(name) = utils::use_tree_to_name(&context.item_use.tree, "std::asset::force_transfer_to_contract") {
-            module_state.fn_calls_to_check.push(name);
-        }
-
-        // Check the use tree for `std::low_level_call::call_with_function_selector`
-        if let Some(name) = utils::use_tree_to_name(&context.item_use.tree, "std::low_level_call::call_with_function_selector") {
-            module_state.fn_calls_to_check.push(name);
+        // Check the use tree for imports of the transfer functions
+        for transfer_fn in module_state.transfer_fns.iter() {
+            if let Some(name) = utils::use_tree_to_name(&context.item_use.tree, transfer_fn) {
+                module_state.fn_calls_to_check.push(name);
+            }
         }
 
         Ok(())
@@ -78,7 +74,7 @@ impl AstVisitor for ManipulatableBalanceUsageVisitor {
         let module_state = self.module_states.get_mut(context.path).unwrap();
 
         for field in &context.item_storage.entries.inner {
-            if field.value.span().as_str().contains("balance") {
+            if utils::is_balance_storage_field_name(field.value.name.as_str()) {
                 module_state.balances.insert(field.value.span(), field.value.name.span().as_str().to_string());
             }
         }
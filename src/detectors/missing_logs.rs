@@ -1,4 +1,5 @@
 use crate::{
+    config::{DetectorOption, OptionDefault},
     error::Error,
     project::Project,
     report::Severity,
@@ -10,45 +11,235 @@ use crate::{
     },
 };
 use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
-use sway_ast::Expr;
+use sway_ast::{Expr, ItemKind, PathExpr};
 use sway_types::{Span, Spanned};
 
 #[derive(Default)]
 pub struct MissingLogsVisitor {
+    package_type_definitions: HashMap<Option<PathBuf>, HashMap<String, Vec<PathBuf>>>,
     module_states: HashMap<PathBuf, ModuleState>,
 }
 
+impl MissingLogsVisitor {
+    pub const OPTIONS: &'static [DetectorOption] = &[
+        DetectorOption {
+            name: "ownership_fields",
+            description: "The names of the storage fields whose writes are considered ownership changes.",
+            default: OptionDefault::StringList(&["owner", "pending_owner", "proxy_owner"]),
+        },
+    ];
+}
+
+/// The paths of the `std` functions which change state, along with the state change they perform.
+const STATE_CHANGE_FN_PATHS: &[(&str, &str)] = &[
+    ("std::asset::mint", "mints assets"),
+    ("std::asset::mint_to", "mints assets"),
+    ("std::asset::burn", "burns assets"),
+    ("std::asset::transfer", "transfers assets"),
+    ("std::asset::transfer_to_address", "transfers assets"),
+    ("std::asset::force_transfer_to_contract", "transfers assets"),
+];
+
+/// The paths of the `std` functions which revert.
+const REVERT_FN_PATHS: &[&str] = &["std::revert::revert", "std::assert::assert", "std::assert::assert_eq", "std::assert::assert_ne"];
+
 struct ModuleState {
+    package_root: Option<PathBuf>,
     log_names: Vec<String>,
+    std_fns: utils::FnCallMatcher,
     fn_states: HashMap<Span, FnState>,
+    reported_type_names: Vec<String>,
 }
 
 impl Default for ModuleState {
     fn default() -> Self {
         Self {
+            package_root: None,
             // Since `std::logging::log` is part of the prelude, include it here
             log_names: vec!["log".into()],
+            std_fns: {
+                let paths = STATE_CHANGE_FN_PATHS.iter().map(|(path, _)| *path).chain(REVERT_FN_PATHS.iter().copied()).collect::<Vec<_>>();
+                let mut std_fns = utils::FnCallMatcher::new(paths.as_slice());
+
+                // Since `std::asset::transfer` and the `std` revert functions are part of the prelude, include them here
+                std_fns.add_name("transfer", "std::asset::transfer");
+
+                for path in REVERT_FN_PATHS {
+                    std_fns.add_name(path.rsplit("::").next().unwrap(), path);
+                }
+
+                std_fns
+            },
             fn_states: Default::default(),
+            reported_type_names: vec![],
         }
     }
 }
 
-#[derive(Default)]
+impl ModuleState {
+    /// Checks if the supplied `path` refers to the `std::logging::log` function.
+    fn path_is_log_fn(&self, path: &PathExpr) -> bool {
+        // Check for calls to the imported `log` function
+        if path.suffix.is_empty() {
+            return self.log_names.iter().any(|x| path.prefix.name.as_str() == x);
+        }
+
+        // Check for calls to the `std::logging::log` function
+        path.suffix.len() == 2
+            && path.prefix.name.as_str() == "std"
+            && path.suffix[0].1.name.as_str() == "logging"
+            && path.suffix[1].1.name.as_str() == "log"
+    }
+
+    /// Checks if the function with the supplied `name` logs anything, either directly or through one of the functions it calls.
+    fn fn_logs(&self, name: &str, visited: &mut Vec<String>) -> bool {
+        if visited.iter().any(|x| x == name) {
+            return false;
+        }
+
+        visited.push(name.to_string());
+
+        self.fn_states.values().filter(|x| x.name == name).any(|fn_state| {
+            !fn_state.logs.is_empty() || fn_state.function_calls.iter().any(|x| self.fn_logs(x, visited))
+        })
+    }
+}
+
 struct FnState {
+    name: String,
+    location: String,
+    span: Span,
+    is_contract_fn: bool,
+    state_changes: Vec<&'static str>,
+    logs: Vec<(Span, Vec<Span>)>,
+    reverts: Vec<(Span, Vec<Span>)>,
+    function_calls: Vec<String>,
     block_states: HashMap<Span, BlockState>,
 }
 
+impl FnState {
+    /// Checks if the supplied `revert` can be reached after the supplied `log`, each given as its span and the spans of its enclosing blocks.
+    ///
+    /// A revert is not reachable from a log in a different branch of the same `if` or `match` expression.
+    fn is_revert_reachable_after_log(&self, log: &(Span, Vec<Span>), revert: &(Span, Vec<Span>)) -> bool {
+        let ((log_span, log_blocks), (revert_span, revert_blocks)) = (log, revert);
+
+        if revert_span.start() <= log_span.end() {
+            return false;
+        }
+
+        // Find the innermost block containing both the log and the revert
+        let common_depth = log_blocks.iter().zip(revert_blocks.iter()).take_while(|(a, b)| a == b).count();
+
+        if common_depth == 0 || common_depth == log_blocks.len() || common_depth == revert_blocks.len() {
+            return true;
+        }
+
+        // The log and the revert are in different nested blocks, which are branches of the same expression if they are in the same statement
+        let statements = self.block_states.get(&log_blocks[common_depth - 1]).map(|x| x.statements.as_slice()).unwrap_or_default();
+        let find_statement = |block: &Span| statements.iter().position(|x| x.start() <= block.start() && block.end() <= x.end());
+
+        find_statement(&log_blocks[common_depth]) != find_statement(&revert_blocks[common_depth])
+    }
+}
+
 #[derive(Default)]
 struct BlockState {
+    statements: Vec<Span>,
     written: Vec<(Span, Span)>,
-    logged: Vec<Span>,
+    logged: Vec<Expr>,
+}
+
+/// Checks if the supplied `logged` expression includes the value of the variable `var_name` or the storage read `storage_read`.
+fn logged_expr_includes(logged: &Expr, var_name: &str, storage_read: &str) -> bool {
+    let mut result = false;
+
+    utils::map_expr(logged, &mut |expr| {
+        if expr.span().as_str() == var_name || expr.span().as_str() == storage_read {
+            result = true;
+        }
+
+        // Check for shorthand struct fields, i.e: `Event { value }`
+        if let Expr::Struct { fields, .. } = expr {
+            if utils::fold_punctuated(&fields.inner).iter().any(|x| x.expr_opt.is_none() && x.field_name.as_str() == var_name) {
+                result = true;
+            }
+        }
+    });
+
+    result
+}
+
+/// Attempts to get the name of the struct or enum type of the supplied `logged` expression.
+fn logged_expr_type_name(logged: &Expr, type_definitions: &HashMap<String, Vec<PathBuf>>) -> Option<String> {
+    let idents = match logged {
+        // Check for `Event { ... }`
+        Expr::Struct { path, .. } => {
+            let name = utils::fold_path_idents(path).last()?.as_str().to_string();
+            return type_definitions.contains_key(&name).then_some(name);
+        }
+
+        // Check for `Event::new(...)` or `Event::Variant(...)`
+        Expr::FuncApp { func, .. } => {
+            let Expr::Path(path) = func.as_ref() else { return None };
+            utils::fold_path_idents(path)
+        }
+
+        // Check for `Event::Variant`
+        Expr::Path(path) => utils::fold_path_idents(path),
+
+        _ => return None,
+    };
+
+    if idents.len() < 2 {
+        return None;
+    }
+
+    let name = idents[idents.len() - 2].as_str().to_string();
+    type_definitions.contains_key(&name).then_some(name)
 }
 
 impl AstVisitor for MissingLogsVisitor {
-    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+    fn is_cross_module(&self) -> bool {
+        true
+    }
+
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        let package_root = project.packages().package_for_path(context.path).map(|x| x.root.clone());
+
+        // Collect the struct and enum types declared in each module of the package ahead of time
+        if !self.package_type_definitions.contains_key(&package_root) {
+            let mut type_definitions: HashMap<String, Vec<PathBuf>> = HashMap::new();
+
+            for (path, module) in project.modules().borrow().iter() {
+                if project.packages().package_for_path(path).map(|x| x.root.clone()) != package_root {
+                    continue;
+                }
+
+                for item in module.items.iter() {
+                    let name = match &item.value {
+                        ItemKind::Struct(item_struct) => item_struct.name.as_str(),
+                        ItemKind::Enum(item_enum) => item_enum.name.as_str(),
+                        _ => continue,
+                    };
+
+                    type_definitions.entry(name.to_string()).or_default().push(path.clone());
+                }
+            }
+
+            for paths in type_definitions.values_mut() {
+                paths.sort();
+            }
+
+            self.package_type_definitions.insert(package_root.clone(), type_definitions);
+        }
+
         // Create the module state
         if !self.module_states.contains_key(context.path) {
-            self.module_states.insert(context.path.into(), ModuleState::default());
+            self.module_states.insert(context.path.into(), ModuleState {
+                package_root,
+                ..Default::default()
+            });
         }
 
         Ok(())
@@ -63,6 +254,9 @@ impl AstVisitor for MissingLogsVisitor {
             module_state.log_names.push(name);
         }
 
+        // Check the use tree for the `std` functions which change state or revert
+        module_state.std_fns.visit_use(context.item_use);
+
         Ok(())
     }
 
@@ -72,9 +266,19 @@ impl AstVisitor for MissingLogsVisitor {
 
         // Create the function state
         let fn_signature = context.item_fn.fn_signature.span();
-        
-        module_state.fn_states.entry(fn_signature).or_default();
-        
+
+        module_state.fn_states.entry(fn_signature.clone()).or_insert_with(|| FnState {
+            name: context.item_fn.fn_signature.name.as_str().to_string(),
+            location: utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
+            span: fn_signature,
+            is_contract_fn: context.item_impl.map(|x| x.ty.span().as_str() == "Contract").unwrap_or(false),
+            state_changes: vec![],
+            logs: vec![],
+            reverts: vec![],
+            function_calls: vec![],
+            block_states: Default::default(),
+        });
+
         Ok(())
     }
 
@@ -90,7 +294,7 @@ impl AstVisitor for MissingLogsVisitor {
         let block_span = context.block.span();
 
         fn_state.block_states.entry(block_span).or_default();
-        
+
         Ok(())
     }
 
@@ -108,9 +312,9 @@ impl AstVisitor for MissingLogsVisitor {
 
         // Check each written storage variable to see if it has been logged
         for (storage_span, var_span) in block_state.written.iter() {
-            if !block_state.logged.iter().any(|logged| {
-                logged.as_str() == var_span.as_str() || logged.as_str() == format!("storage.{}.read()", storage_span.as_str())
-            }) {
+            let storage_read = format!("storage.{}.read()", storage_span.as_str());
+
+            if !block_state.logged.iter().any(|logged| logged_expr_includes(logged, var_span.as_str(), storage_read.as_str())) {
                 project.report.borrow_mut().add_entry(
                     context.path,
                     project.span_to_line(context.path, storage_span)?,
@@ -127,7 +331,7 @@ impl AstVisitor for MissingLogsVisitor {
         Ok(())
     }
 
-    fn visit_statement(&mut self, context: &StatementContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+    fn visit_statement(&mut self, context: &StatementContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
         // Get the module state
         let module_state = self.module_states.get_mut(context.path).unwrap();
 
@@ -137,58 +341,152 @@ impl AstVisitor for MissingLogsVisitor {
 
         // Get the block state
         let Some(block_span) = context.blocks.last() else { return Ok(()) };
-        let block_state = fn_state.block_states.get_mut(block_span).unwrap();
+        let block_state = fn_state.block_states.entry(block_span.clone()).or_default();
+
+        // Add the statement to the block state
+        block_state.statements.push(context.statement.span());
+
+        // Check for storage writes
+        let Some((storage_name, var_name)) = utils::statement_to_storage_write_idents(context.statement) else { return Ok(()) };
 
-        // Check for storage writes and add them to the block state
-        if let Some((storage_name, var_name)) = utils::statement_to_storage_write_idents(context.statement) {
-            block_state.written.push((storage_name.span(), var_name.span()));
+        // Writes to owner storage fields are considered ownership changes
+        let ownership_fields = project.detector_options().string_list("missing_logs", "ownership_fields");
+
+        if ownership_fields.iter().any(|x| x == storage_name.as_str()) && !fn_state.state_changes.contains(&"changes ownership") {
+            fn_state.state_changes.push("changes ownership");
         }
 
+        // Add the storage write to the block state
+        block_state.written.push((storage_name.span(), var_name.span()));
+
         Ok(())
     }
 
-    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
         // Get the module state
         let module_state = self.module_states.get_mut(context.path).unwrap();
 
         // Get the function state
         let Some(item_fn) = context.item_fn.as_ref() else { return Ok(()) };
         let fn_signature = item_fn.fn_signature.span();
-        let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
 
-        // Get the block state
-        let Some(block_span) = context.blocks.last() else { return Ok(()) };
-        let block_state = fn_state.block_states.get_mut(block_span).unwrap();
+        // Check for revert paths
+        if utils::get_require_args(context.expr).is_some() || utils::get_if_revert_condition(context.expr).is_some() {
+            let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
+            fn_state.reverts.push((context.expr.span(), context.blocks.clone()));
+        }
 
         // Destructure the expression into a function application
         let Expr::FuncApp { func, args } = context.expr else { return Ok(()) };
         let Expr::Path(path) = func.as_ref() else { return Ok(()) };
 
+        let is_log_fn = module_state.path_is_log_fn(path);
+        let std_fn_path = module_state.std_fns.called_path(context.expr);
+        let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
+
+        // Check for state changes and reverts performed by `std` functions
+        if let Some(std_fn_path) = std_fn_path {
+            if REVERT_FN_PATHS.contains(&std_fn_path) {
+                fn_state.reverts.push((context.expr.span(), context.blocks.clone()));
+            }
+
+            if let Some((_, state_change)) = STATE_CHANGE_FN_PATHS.iter().find(|(path, _)| *path == std_fn_path) {
+                if !fn_state.state_changes.contains(state_change) {
+                    fn_state.state_changes.push(state_change);
+                }
+            }
+        }
+
+        // Check for function calls
+        if !is_log_fn {
+            let func = func.span().as_str().to_string();
+            if !fn_state.function_calls.contains(&func) {
+                fn_state.function_calls.push(func);
+            }
+
+            return Ok(());
+        }
+
         let log_args = utils::fold_punctuated(&args.inner);
 
         if log_args.len() != 1 {
             return Ok(());
         }
 
-        let logged_span = log_args.last().unwrap().span();
-        
-        // Check for calls to the imported `log` function
-        if path.suffix.is_empty() {
-            for log_name in module_state.log_names.iter() {
-                if path.prefix.name.as_str() == log_name {
-                    // Add the `log` span to the block state
-                    block_state.logged.push(logged_span);
-                    break;
+        let logged = log_args[0];
+        fn_state.logs.push((context.expr.span(), context.blocks.clone()));
+
+        // Add the logged expression to the block state
+        let Some(block_span) = context.blocks.last() else { return Ok(()) };
+        let block_state = fn_state.block_states.get_mut(block_span).unwrap();
+
+        block_state.logged.push(logged.clone());
+
+        // Check if the logged struct or enum type name is declared in multiple modules of the package
+        let type_definitions = self.package_type_definitions.get(&module_state.package_root).unwrap();
+        let Some(type_name) = logged_expr_type_name(logged, type_definitions) else { return Ok(()) };
+        let declaring_paths = type_definitions.get(&type_name).unwrap();
+
+        if declaring_paths.len() < 2 || module_state.reported_type_names.contains(&type_name) {
+            return Ok(());
+        }
+
+        module_state.reported_type_names.push(type_name.clone());
+
+        project.report.borrow_mut().add_entry(
+            context.path,
+            project.span_to_line(context.path, &context.expr.span())?,
+            Severity::Low,
+            format!(
+                "{} logs the `{}` event type, which is declared in multiple modules: {}. Consider giving each event type a unique name so it can be distinguished by indexers.",
+                utils::get_item_location(context.item, &context.item_impl, &context.item_fn),
+                type_name,
+                declaring_paths.iter().map(|x| format!("`{}`", x.to_string_lossy())).collect::<Vec<_>>().join(", "),
+            ),
+        );
+
+        Ok(())
+    }
+
+    fn leave_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get(context.path).unwrap();
+
+        for fn_state in module_state.fn_states.values() {
+            // Check for contract functions that change state without emitting any logs
+            if fn_state.is_contract_fn && !fn_state.state_changes.is_empty() && !module_state.fn_logs(fn_state.name.as_str(), &mut vec![]) {
+                project.report.borrow_mut().add_entry(
+                    context.path,
+                    project.span_to_line(context.path, &fn_state.span)?,
+                    Severity::Medium,
+                    format!(
+                        "{} {} without emitting any logs.",
+                        fn_state.location,
+                        fn_state.state_changes.join(" and "),
+                    ),
+                );
+            }
+
+            // Check for logs that are emitted before a later revert path
+            for log in fn_state.logs.iter() {
+                if !fn_state.reverts.iter().any(|revert| fn_state.is_revert_reachable_after_log(log, revert)) {
+                    continue;
                 }
+
+                let log_span = &log.0;
+
+                project.report.borrow_mut().add_entry(
+                    context.path,
+                    project.span_to_line(context.path, log_span)?,
+                    Severity::Low,
+                    format!(
+                        "{} emits a log before a later revert path: `{}`. Consider emitting logs after all requirements have been checked.",
+                        fn_state.location,
+                        log_span.as_str(),
+                    ),
+                );
             }
         }
-        // Check for calls to the `std::logging::log` function
-        else if path.suffix.len() == 2 {
-            let "std" = path.prefix.name.as_str() else { return Ok(()) };
-            let "logging" = path.suffix[0].1.name.as_str() else { return Ok(()) };
-            let "log" = path.suffix[1].1.name.as_str() else { return Ok(()) };
-            block_state.logged.push(logged_span);
-        }
 
         Ok(())
     }
@@ -198,6 +496,6 @@ impl AstVisitor for MissingLogsVisitor {
 mod tests {
     #[test]
     fn test_missing_logs() {
-        crate::tests::test_detector("missing_logs", 2);
+        crate::tests::test_detector("missing_logs", 7);
     }
 }
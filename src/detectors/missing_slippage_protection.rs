@@ -0,0 +1,369 @@
+use crate::{
+    error::Error,
+    project::Project,
+    report::Severity,
+    scope::AstScope,
+    utils,
+    visitor::{
+        AstVisitor, ExprContext, FnContext, ModuleContext, StatementLetContext,
+        StorageFieldContext, UseContext,
+    },
+};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
+use sway_ast::{Expr, FnArgs, IfCondition};
+use sway_types::{Span, Spanned};
+
+/// Words of storage field names which are considered to hold prices or pool reserves, in addition to balances.
+const PRICE_FIELD_WORDS: &[&str] = &["price", "prices", "reserve", "reserves", "liquidity"];
+
+/// Words of parameter names which are considered to hold a minimum output amount.
+const MIN_OUTPUT_PARAM_WORDS: &[&str] = &["min", "minimum"];
+
+/// Words of parameter names which are considered to hold a deadline.
+const DEADLINE_PARAM_WORDS: &[&str] = &["deadline", "expiry", "expiration", "until"];
+
+#[derive(Default)]
+pub struct MissingSlippageProtectionVisitor {
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+struct ModuleState {
+    price_fields: Vec<String>,
+    balance_fns: utils::FnCallMatcher,
+    timestamp_fns: utils::FnCallMatcher,
+    transfer_fns: utils::FnCallMatcher,
+    fn_states: HashMap<Span, FnState>,
+}
+
+impl Default for ModuleState {
+    fn default() -> Self {
+        let transfer_fn_paths = utils::TRANSFER_FN_AMOUNT_ARGS.iter().map(|(path, _)| *path).collect::<Vec<_>>();
+        let mut transfer_fns = utils::FnCallMatcher::new(transfer_fn_paths.as_slice());
+
+        // Since `std::asset::transfer` is part of the prelude, include it here
+        transfer_fns.add_name("transfer", "std::asset::transfer");
+
+        Self {
+            price_fields: vec![],
+            balance_fns: utils::FnCallMatcher::new(utils::BALANCE_FN_PATHS),
+            timestamp_fns: utils::FnCallMatcher::new(utils::TIMESTAMP_FN_PATHS),
+            transfer_fns,
+            fn_states: Default::default(),
+        }
+    }
+}
+
+impl ModuleState {
+    /// Checks if the supplied `expr` depends on a storage-read price, a pool balance or a variable derived from either.
+    fn expr_is_price_dependent(&self, expr: &Expr, fn_state: &FnState) -> bool {
+        let mut result = false;
+
+        utils::map_expr(expr, &mut |expr| {
+            if result {
+                return;
+            }
+
+            if self.balance_fns.is_call(expr) {
+                result = true;
+                return;
+            }
+
+            let span = expr.span();
+            let s = span.as_str();
+
+            if self.price_fields.iter().any(|x| s.starts_with(format!("storage.{x}.").as_str())) {
+                result = true;
+                return;
+            }
+
+            if let Expr::Path(_) = expr {
+                if fn_state.price_vars.iter().any(|x| x == s) {
+                    result = true;
+                }
+            }
+        });
+
+        result
+    }
+
+    /// Gets the signature spans of the functions the supplied function `call` may resolve to from the supplied `caller`.
+    ///
+    /// Calls through `Self::` resolve to the functions of the caller's impl, and other calls resolve to free functions.
+    fn resolve_fn_call(&self, caller: &FnState, call: &str) -> Vec<&Span> {
+        let (name, impl_span) = match call.strip_prefix("Self::") {
+            Some(name) => (name, caller.impl_span.as_ref()),
+            None => (call, None),
+        };
+
+        self.fn_states.iter()
+            .filter(|(_, x)| x.name == name && x.impl_span.as_ref() == impl_span)
+            .map(|(span, _)| span)
+            .collect()
+    }
+
+    /// Gets the states of the function with the supplied `signature_span` and of all of the functions it calls, directly or indirectly.
+    fn get_reachable_fn_states(&self, signature_span: &Span) -> Vec<&FnState> {
+        let mut visited = vec![];
+        let mut queue = vec![signature_span];
+        let mut result = vec![];
+
+        while let Some(span) = queue.pop() {
+            if visited.contains(&span) {
+                continue;
+            }
+
+            visited.push(span);
+
+            let Some(fn_state) = self.fn_states.get(span) else { continue };
+
+            for call in fn_state.function_calls.iter() {
+                queue.extend(self.resolve_fn_call(fn_state, call));
+            }
+
+            result.push(fn_state);
+        }
+
+        result
+    }
+}
+
+struct FnState {
+    name: String,
+    impl_span: Option<Span>,
+    location: String,
+    is_contract_fn: bool,
+    min_output_params: Vec<String>,
+    deadline_params: Vec<String>,
+    price_vars: Vec<String>,
+    has_min_output_check: bool,
+    has_deadline_check: bool,
+    transfers: Vec<Span>,
+    function_calls: Vec<String>,
+}
+
+/// Checks if the supplied `expr` references the variable with the supplied `name`.
+fn expr_references(expr: &Expr, name: &str) -> bool {
+    let mut result = false;
+
+    utils::map_expr(expr, &mut |expr| {
+        if let Expr::Path(_) = expr {
+            if expr.span().as_str() == name {
+                result = true;
+            }
+        }
+    });
+
+    result
+}
+
+impl AstVisitor for MissingSlippageProtectionVisitor {
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Create the module state
+        if !self.module_states.contains_key(context.path) {
+            self.module_states.insert(context.path.into(), ModuleState::default());
+        }
+
+        Ok(())
+    }
+
+    fn visit_use(&mut self, context: &UseContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Check the use tree for `std::context::{this_balance, balance_of}`
+        module_state.balance_fns.visit_use(context.item_use);
+
+        // Check the use tree for `std::block::{timestamp, timestamp_of_block}`
+        module_state.timestamp_fns.visit_use(context.item_use);
+
+        // Check the use tree for `std::asset::{transfer, transfer_to_address, force_transfer_to_contract}`
+        module_state.transfer_fns.visit_use(context.item_use);
+
+        Ok(())
+    }
+
+    fn visit_storage_field(&mut self, context: &StorageFieldContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Only check top-level storage fields, since namespaced fields are not accessed through `storage.`
+        if !context.namespace.is_empty() {
+            return Ok(());
+        }
+
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Check if the storage field holds prices or pool balances
+        let name = context.field.name.as_str();
+
+        if utils::is_balance_storage_field_name(name) || utils::name_contains_word(name, PRICE_FIELD_WORDS) {
+            module_state.price_fields.push(name.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn visit_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Collect the names of the function's parameters
+        let args = match &context.item_fn.fn_signature.arguments.inner {
+            FnArgs::Static(args) => Some(args),
+            FnArgs::NonStatic { args_opt, .. } => args_opt.as_ref().map(|(_, args)| args),
+        };
+
+        let param_names = args
+            .map(|args| {
+                utils::fold_punctuated(args).iter()
+                    .flat_map(|arg| utils::fold_pattern_idents(&arg.pattern))
+                    .map(|ident| ident.as_str().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        // Create the function state
+        module_state.fn_states.entry(context.item_fn.fn_signature.span()).or_insert_with(|| FnState {
+            name: context.item_fn.fn_signature.name.as_str().to_string(),
+            impl_span: context.item_impl.map(|x| x.span()),
+            location: utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
+            is_contract_fn: context.item_impl.map(|x| x.ty.span().as_str() == "Contract").unwrap_or(false),
+            min_output_params: param_names.iter().filter(|x| utils::name_contains_word(x, MIN_OUTPUT_PARAM_WORDS)).cloned().collect(),
+            deadline_params: param_names.iter().filter(|x| utils::name_contains_word(x, DEADLINE_PARAM_WORDS)).cloned().collect(),
+            price_vars: vec![],
+            has_min_output_check: false,
+            has_deadline_check: false,
+            transfers: vec![],
+            function_calls: vec![],
+        });
+
+        Ok(())
+    }
+
+    fn visit_statement_let(&mut self, context: &StatementLetContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Get the function state
+        let fn_signature = context.item_fn.fn_signature.span();
+        let fn_state = module_state.fn_states.get(&fn_signature).unwrap();
+
+        // Check if the variable is derived from a storage-read price or a pool balance
+        if !module_state.expr_is_price_dependent(&context.statement_let.expr, fn_state) {
+            return Ok(());
+        }
+
+        let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
+
+        for ident in utils::fold_pattern_idents(&context.statement_let.pattern) {
+            fn_state.price_vars.push(ident.as_str().to_string());
+        }
+
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Get the function state
+        let Some(item_fn) = context.item_fn.as_ref() else { return Ok(()) };
+        let fn_signature = item_fn.fn_signature.span();
+        let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
+
+        // Check for function calls
+        if let Expr::FuncApp { func, .. } = context.expr {
+            if let Expr::Path(_) = func.as_ref() {
+                let func = func.span().as_str().to_string();
+
+                if !fn_state.function_calls.contains(&func) {
+                    fn_state.function_calls.push(func);
+                }
+            }
+        }
+
+        let fn_state = module_state.fn_states.get(&fn_signature).unwrap();
+
+        // Check for a `require` or `if`-`revert` that checks the minimum output amount or the deadline
+        let condition = if let Some(require_args) = utils::get_require_args(context.expr) {
+            require_args.first().copied()
+        } else if let Some(IfCondition::Expr(condition)) = utils::get_if_revert_condition(context.expr) {
+            Some(condition.as_ref())
+        } else {
+            None
+        };
+
+        if let Some(condition) = condition {
+            let has_min_output_check = fn_state.min_output_params.iter().any(|x| expr_references(condition, x));
+
+            let mut has_timestamp = false;
+            utils::map_expr(condition, &mut |expr| has_timestamp |= module_state.timestamp_fns.is_call(expr));
+
+            let has_deadline_check = has_timestamp && fn_state.deadline_params.iter().any(|x| expr_references(condition, x));
+
+            let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
+            fn_state.has_min_output_check |= has_min_output_check;
+            fn_state.has_deadline_check |= has_deadline_check;
+
+            return Ok(());
+        }
+
+        // Check for transfers whose amount depends on a storage-read price or a pool balance
+        let Some(amount) = utils::get_transfer_amount_arg(context.expr, &module_state.transfer_fns) else { return Ok(()) };
+
+        if !module_state.expr_is_price_dependent(amount, fn_state) {
+            return Ok(());
+        }
+
+        let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
+        fn_state.transfers.push(context.expr.span());
+
+        Ok(())
+    }
+
+    fn leave_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get(context.path).unwrap();
+
+        for (fn_signature, fn_state) in module_state.fn_states.iter() {
+            // Only check publicly-accessible contract functions
+            if !fn_state.is_contract_fn {
+                continue;
+            }
+
+            // Include the checks and transfers of the functions called by the contract function
+            let reachable_fn_states = module_state.get_reachable_fn_states(fn_signature);
+            let has_min_output_check = reachable_fn_states.iter().any(|x| x.has_min_output_check);
+            let has_deadline_check = reachable_fn_states.iter().any(|x| x.has_deadline_check);
+
+            let missing = match (has_min_output_check, has_deadline_check) {
+                (true, true) => continue,
+                (false, true) => "a user-supplied minimum output amount",
+                (true, false) => "a user-supplied deadline",
+                (false, false) => "a user-supplied minimum output amount or deadline",
+            };
+
+            for span in reachable_fn_states.iter().flat_map(|x| x.transfers.iter()) {
+                project.report.borrow_mut().add_entry(
+                    context.path,
+                    project.span_to_line(context.path, span)?,
+                    Severity::Medium,
+                    format!(
+                        "{} performs a transfer whose amount depends on a storage-read price or pool balance without checking {}: `{}`. Consider adding slippage and deadline protection.",
+                        fn_state.location,
+                        missing,
+                        span.as_str(),
+                    ),
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_missing_slippage_protection() {
+        crate::tests::test_detector("missing_slippage_protection", 5);
+    }
+}
@@ -1,6 +1,7 @@
 mod arbitrary_asset_transfer;
 mod arbitrary_code_execution;
 mod boolean_comparison;
+mod dead_code;
 mod discarded_assignment;
 mod division_before_multiplication;
 mod explicit_return_statement;
@@ -11,43 +12,66 @@ mod locked_native_asset;
 mod magic_number;
 mod manipulatable_balance_usage;
 mod missing_logs;
+mod missing_slippage_protection;
 mod msg_amount_in_loop;
 mod non_zero_identity_validation;
 mod potential_infinite_loop;
+mod predicate_always_true;
+mod predicate_missing_count_check;
+mod predicate_uncompared_owner_key;
+mod predicate_unconstrained_output;
 mod redundant_comparison;
 mod redundant_storage_access;
+mod script_fixed_input_index;
+mod script_missing_revert;
+mod script_unbounded_contract_calls;
+mod script_unchecked_call_result;
+mod src_standard_conformance;
+mod storage_attribute_consistency;
 mod storage_field_mutability;
 mod storage_not_updated;
 mod storage_read_in_loop_condition;
+mod storage_slot_collision;
 mod strict_equality;
 mod unchecked_call_payload;
+mod unnamespaced_implementation_storage;
 mod unprotected_initialization;
+mod unprotected_proxy_fallback;
+mod unprotected_proxy_target;
 mod unprotected_storage_variable;
+mod unsafe_message_handling;
 mod unsafe_timestamp_usage;
 mod unused_import;
 mod weak_prng;
 
-use crate::visitor::AstVisitor;
+use crate::{config::DetectorOption, visitor::AstVisitor};
 
 use self::{
-    arbitrary_asset_transfer::*, arbitrary_code_execution::*, boolean_comparison::*,
+    arbitrary_asset_transfer::*, arbitrary_code_execution::*, boolean_comparison::*, dead_code::*,
     discarded_assignment::*, division_before_multiplication::*, explicit_return_statement::*,
     external_call_in_loop::*, inline_assembly_usage::*, large_literal::*, locked_native_asset::*,
-    magic_number::*, manipulatable_balance_usage::*, missing_logs::*, msg_amount_in_loop::*,
-    non_zero_identity_validation::*, potential_infinite_loop::*, redundant_comparison::*,
-    redundant_storage_access::*, storage_field_mutability::*, storage_not_updated::*,
-    storage_read_in_loop_condition::*, strict_equality::*, unchecked_call_payload::*,
-    unprotected_initialization::*, unprotected_storage_variable::*, unsafe_timestamp_usage::*,
+    magic_number::*, manipulatable_balance_usage::*, missing_logs::*,
+    missing_slippage_protection::*, msg_amount_in_loop::*, non_zero_identity_validation::*,
+    potential_infinite_loop::*, predicate_always_true::*, predicate_missing_count_check::*,
+    predicate_uncompared_owner_key::*, predicate_unconstrained_output::*, redundant_comparison::*,
+    redundant_storage_access::*, script_fixed_input_index::*, script_missing_revert::*,
+    script_unbounded_contract_calls::*, script_unchecked_call_result::*,
+    src_standard_conformance::*, storage_attribute_consistency::*, storage_field_mutability::*,
+    storage_not_updated::*, storage_read_in_loop_condition::*, storage_slot_collision::*,
+    strict_equality::*, unchecked_call_payload::*, unnamespaced_implementation_storage::*,
+    unprotected_initialization::*, unprotected_proxy_fallback::*, unprotected_proxy_target::*,
+    unprotected_storage_variable::*, unsafe_message_handling::*, unsafe_timestamp_usage::*,
     unused_import::*, weak_prng::*,
 };
 
-type DetectorConstructor = fn() -> Box<dyn AstVisitor>;
-type DetectorEntry = (&'static str, DetectorConstructor);
+pub type DetectorConstructor = fn() -> Box<dyn AstVisitor>;
+pub type DetectorEntry = (&'static str, DetectorConstructor);
 
 pub const DETECTOR_TYPES: &[DetectorEntry] = &[
     ("arbitrary_asset_transfer", || Box::<ArbitraryAssetTransferVisitor>::default()),
     ("arbitrary_code_execution", || Box::<ArbitraryCodeExecutionVisitor>::default()),
     ("boolean_comparison", || Box::<BooleanComparisonVisitor>::default()),
+    ("dead_code", || Box::<DeadCodeVisitor>::default()),
     ("discarded_assignment", || Box::<DiscardedAssignmentVisitor>::default()),
     ("division_before_multiplication", || Box::<DivisionBeforeMultiplicationVisitor>::default()),
     ("explicit_return_statement", || Box::<ExplicitReturnStatementVisitor>::default()),
@@ -58,19 +82,61 @@ pub const DETECTOR_TYPES: &[DetectorEntry] = &[
     ("magic_number", || Box::<MagicNumberVisitor>::default()),
     ("manipulatable_balance_usage", || Box::<ManipulatableBalanceUsageVisitor>::default()),
     ("missing_logs", || Box::<MissingLogsVisitor>::default()),
+    ("missing_slippage_protection", || Box::<MissingSlippageProtectionVisitor>::default()),
     ("msg_amount_in_loop", || Box::<MsgAmountInLoopVisitor>::default()),
     ("non_zero_identity_validation", || Box::<NonZeroIdentityValidationVisitor>::default()),
     ("potential_infinite_loop", || Box::<PotentialInfiniteLoopVisitor>::default()),
+    ("predicate_always_true", || Box::<PredicateAlwaysTrueVisitor>::default()),
+    ("predicate_missing_count_check", || Box::<PredicateMissingCountCheckVisitor>::default()),
+    ("predicate_uncompared_owner_key", || Box::<PredicateUncomparedOwnerKeyVisitor>::default()),
+    ("predicate_unconstrained_output", || Box::<PredicateUnconstrainedOutputVisitor>::default()),
     ("redundant_comparison", || Box::<RedundantComparisonVisitor>::default()),
     ("redundant_storage_access", || Box::<RedundantStorageAccessVisitor>::default()),
+    ("script_fixed_input_index", || Box::<ScriptFixedInputIndexVisitor>::default()),
+    ("script_missing_revert", || Box::<ScriptMissingRevertVisitor>::default()),
+    ("script_unbounded_contract_calls", || Box::<ScriptUnboundedContractCallsVisitor>::default()),
+    ("script_unchecked_call_result", || Box::<ScriptUncheckedCallResultVisitor>::default()),
+    ("src_standard_conformance", || Box::<SrcStandardConformanceVisitor>::default()),
+    ("storage_attribute_consistency", || Box::<StorageAttributeConsistencyVisitor>::default()),
     ("storage_field_mutability", || Box::<StorageFieldMutabilityVisitor>::default()),
     ("storage_not_updated", || Box::<StorageNotUpdatedVisitor>::default()),
     ("storage_read_in_loop_condition", || Box::<StorageReadInLoopConditionVisitor>::default()),
+    ("storage_slot_collision", || Box::<StorageSlotCollisionVisitor>::default()),
     ("strict_equality", || Box::<StrictEqualityVisitor>::default()),
     ("unchecked_call_payload", || Box::<UncheckedCallPayloadVisitor>::default()),
+    ("unnamespaced_implementation_storage", || Box::<UnnamespacedImplementationStorageVisitor>::default()),
     ("unprotected_initialization", || Box::<UnprotectedInitializationVisitor>::default()),
+    ("unprotected_proxy_fallback", || Box::<UnprotectedProxyFallbackVisitor>::default()),
+    ("unprotected_proxy_target", || Box::<UnprotectedProxyTargetVisitor>::default()),
     ("unprotected_storage_variable", || Box::<UnprotectedStorageVariableVisitor>::default()),
+    ("unsafe_message_handling", || Box::<UnsafeMessageHandlingVisitor>::default()),
     ("unsafe_timestamp_usage", || Box::<UnsafeTimestampUsageVisitor>::default()),
     ("unused_import", || Box::<UnusedImportVisitor>::default()),
     ("weak_prng", || Box::<WeakPrngVisitor>::default()),
 ];
+
+/// The configurable options declared by the detectors, which can be set from the config file or the command line.
+pub const DETECTOR_OPTIONS: &[(&str, &[DetectorOption])] = &[
+    ("large_literal", LargeLiteralVisitor::OPTIONS),
+    ("locked_native_asset", LockedNativeAssetVisitor::OPTIONS),
+    ("magic_number", MagicNumberVisitor::OPTIONS),
+    ("manipulatable_balance_usage", ManipulatableBalanceUsageVisitor::OPTIONS),
+    ("missing_logs", MissingLogsVisitor::OPTIONS),
+    ("unprotected_storage_variable", UnprotectedStorageVariableVisitor::OPTIONS),
+];
+
+/// Gets the options declared by the detector with the supplied `name`.
+pub fn detector_options(name: &str) -> &'static [DetectorOption] {
+    DETECTOR_OPTIONS.iter().find(|(x, _)| *x == name).map(|(_, x)| *x).unwrap_or_default()
+}
+
+/// Gets the URL of the documentation for the built-in detector with the supplied `name`, if any.
+///
+/// Custom rules and plugin detectors are not documented on the wiki, so they do not have a URL.
+pub fn detector_url(name: &str) -> Option<String> {
+    if !DETECTOR_TYPES.iter().any(|(x, _)| *x == name) {
+        return None;
+    }
+
+    Some(format!("https://github.com/camden-smallwood/sway-analyzer/wiki/Detectors#{}", name.replace('_', "-")))
+}
@@ -1,16 +1,18 @@
 use crate::{
     error::Error,
     project::Project,
-    report::Severity,
+    report::{Confidence, Severity},
     scope::AstScope,
     utils,
     visitor::{
-        AstVisitor, BlockContext, FnContext, IfExprContext, ModuleContext, StatementContext,
+        AstVisitor, BlockContext, ExprContext, FnContext, IfExprContext, ModuleContext,
+        StatementContext,
     },
 };
 use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
 use sway_ast::{
-    expr::LoopControlFlow, Expr, FnArg, FnArgs, IfCondition, IfExpr, MatchBranchKind, Statement,
+    expr::LoopControlFlow, Expr, FnArg, FnArgs, IfCondition, IfExpr, ItemImplItem, ItemKind,
+    MatchBranchKind, Statement,
 };
 use sway_types::{Span, Spanned};
 
@@ -30,6 +32,7 @@ struct FnState {
     address_checks: HashMap<Span, bool>,
     contract_id_checks: HashMap<Span, bool>,
     identity_checks: HashMap<Span, (Rc<RefCell<bool>>, Rc<RefCell<bool>>)>,
+    helper_call_args: Vec<String>,
 }
 
 impl FnState {
@@ -152,10 +155,18 @@ impl AstVisitor for NonZeroIdentityValidationVisitor {
         let fn_state = module_state.fn_states.get(&fn_signature).unwrap();
 
         let create_report_entry = |parameter_span: &Span| -> Result<(), Error> {
-            project.report.borrow_mut().add_entry(
+            // The parameter may be checked by a helper function it is passed to, which we do not follow
+            let confidence = if fn_state.helper_call_args.iter().any(|x| x == parameter_span.as_str()) {
+                Confidence::Low
+            } else {
+                Confidence::Medium
+            };
+
+            project.report.borrow_mut().add_entry_with_confidence(
                 context.path,
                 project.span_to_line(context.path, parameter_span)?,
                 Severity::Low,
+                confidence,
                 format!(
                     "{} does not check its `{}` parameter for a zero value.",
                     utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
@@ -229,6 +240,43 @@ impl AstVisitor for NonZeroIdentityValidationVisitor {
         Ok(())
     }
 
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Only check function calls inside of functions
+        let Expr::FuncApp { func, args } = context.expr else { return Ok(()) };
+        let Some(item_fn) = context.item_fn.as_ref() else { return Ok(()) };
+
+        // Only check calls to functions declared in the module or the current impl, which may check their arguments
+        let fn_name = func.span();
+
+        let is_helper_call = context.module.items.iter().any(|item| {
+            matches!(&item.value, ItemKind::Fn(x) if x.fn_signature.name.as_str() == fn_name.as_str())
+        }) || context.item_impl.map(|item_impl| item_impl.contents.inner.iter().any(|item| {
+            matches!(&item.value, ItemImplItem::Fn(x) if x.fn_signature.name.as_str() == fn_name.as_str())
+        })).unwrap_or(false);
+
+        if !is_helper_call {
+            return Ok(());
+        }
+
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Get the function state
+        let fn_signature = item_fn.fn_signature.span();
+        let Some(fn_state) = module_state.fn_states.get_mut(&fn_signature) else { return Ok(()) };
+
+        // Store the arguments passed to the helper function, skipping variables which shadow a parameter
+        for arg in &args.inner {
+            if fn_state.expr_is_variable(arg, context.blocks.as_slice()) {
+                continue;
+            }
+
+            fn_state.helper_call_args.push(arg.span().as_str().to_string());
+        }
+
+        Ok(())
+    }
+
     fn visit_statement(&mut self, context: &StatementContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
         // Get the module state
         let module_state = self.module_states.get_mut(context.path).unwrap();
@@ -765,6 +813,6 @@ impl AstVisitor for NonZeroIdentityValidationVisitor {
 mod tests {
     #[test]
     fn test_non_zero_identity_validation() {
-        crate::tests::test_detector("non_zero_identity_validation", 3);
+        crate::tests::test_detector("non_zero_identity_validation", 4);
     }
 }
@@ -0,0 +1,184 @@
+use crate::{
+    error::Error,
+    project::Project,
+    report::Severity,
+    scope::AstScope,
+    utils,
+    visitor::{AstVisitor, FnContext, IfExprContext, MatchExprContext},
+};
+use std::{cell::RefCell, rc::Rc};
+use sway_ast::{expr::LoopControlFlow, CodeBlockContents, Expr, MatchBranchKind, Pattern, Statement};
+use sway_types::Spanned;
+
+#[derive(Default)]
+pub struct PredicateAlwaysTrueVisitor;
+
+/// Gets the final expression of the supplied `block` if the block consists of nothing else.
+fn block_to_sole_expr(block: &CodeBlockContents) -> Option<&Expr> {
+    if !block.statements.is_empty() {
+        return None;
+    }
+
+    block.final_expr_opt.as_ref().map(|x| x.as_ref())
+}
+
+/// Checks if the supplied `expr` is the boolean literal `false`, ignoring parentheses.
+fn is_false_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Parens(expr) => is_false_literal(expr.inner.as_ref()),
+        Expr::Literal(x) => x.span().as_str() == "false",
+        _ => false,
+    }
+}
+
+/// Checks if the supplied `block` contains a `require`, `assert` or `revert` that can make the predicate fail.
+fn block_has_check(block: &CodeBlockContents) -> bool {
+    let mut result = false;
+
+    let mut check_expr = |expr: &Expr| {
+        utils::map_expr(expr, &mut |expr| {
+            if let Some(name) = utils::get_fn_call_name(expr) {
+                if matches!(name.as_str(), "require" | "assert" | "assert_eq" | "assert_ne" | "revert") {
+                    result = true;
+                }
+            }
+        });
+    };
+
+    for statement in block.statements.iter() {
+        match statement {
+            Statement::Let(statement_let) => check_expr(&statement_let.expr),
+            Statement::Expr { expr, .. } => check_expr(expr),
+            _ => {}
+        }
+    }
+
+    if let Some(expr) = block.final_expr_opt.as_ref() {
+        check_expr(expr);
+    }
+
+    result
+}
+
+impl AstVisitor for PredicateAlwaysTrueVisitor {
+    fn visit_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Only check the `main` function of predicates
+        if !utils::is_predicate_module(context.module) || context.item_impl.is_some() || context.item_fn.fn_signature.name.as_str() != "main" {
+            return Ok(());
+        }
+
+        // Check if the predicate unconditionally returns `true`
+        let body = &context.item_fn.body.inner;
+        let Some(final_expr) = body.final_expr_opt.as_ref() else { return Ok(()) };
+
+        if !utils::is_true_literal(final_expr) || block_has_check(body) {
+            return Ok(());
+        }
+
+        project.report.borrow_mut().add_entry(
+            context.path,
+            project.span_to_line(context.path, &context.item_fn.fn_signature.span())?,
+            Severity::High,
+            "The predicate always returns `true` without checking any condition, which allows anyone to spend its coins. Consider validating the transaction before returning `true`.",
+        );
+
+        Ok(())
+    }
+
+    fn visit_if_expr(&mut self, context: &IfExprContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Only check predicates
+        if !utils::is_predicate_module(context.module) {
+            return Ok(());
+        }
+
+        // Check for an `else` branch which returns `true` when the `if` branch does not simply return `false`
+        let Some((_, LoopControlFlow::Break(else_block))) = context.if_expr.else_opt.as_ref() else { return Ok(()) };
+        let Some(else_expr) = block_to_sole_expr(&else_block.inner) else { return Ok(()) };
+
+        if !utils::is_true_literal(else_expr) {
+            return Ok(());
+        }
+
+        if block_to_sole_expr(&context.if_expr.then_block.inner).map(is_false_literal).unwrap_or(false) {
+            return Ok(());
+        }
+
+        project.report.borrow_mut().add_entry(
+            context.path,
+            project.span_to_line(context.path, &else_expr.span())?,
+            Severity::High,
+            format!(
+                "{} returns `true` in the `else` branch of `if {}`, which skips the validation performed in the other branch. Consider validating the transaction in every branch.",
+                utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
+                context.if_expr.condition.span().as_str(),
+            ),
+        );
+
+        Ok(())
+    }
+
+    fn visit_match_expr(&mut self, context: &MatchExprContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Only check predicates
+        if !utils::is_predicate_module(context.module) {
+            return Ok(());
+        }
+
+        let branch_exprs = context.branches.inner.iter()
+            .map(|branch| {
+                let expr = match &branch.kind {
+                    MatchBranchKind::Block { block, .. } => block_to_sole_expr(&block.inner),
+                    MatchBranchKind::Expr { expr, .. } => Some(expr),
+                };
+
+                (branch, expr)
+            })
+            .collect::<Vec<_>>();
+
+        // Skip matches where the other branches simply return `false`
+        let validates = branch_exprs.iter().any(|(_, expr)| !expr.map(|x| utils::is_true_literal(x) || is_false_literal(x)).unwrap_or(false));
+
+        if !validates {
+            return Ok(());
+        }
+
+        // Check for catch-all or `None` branches which return `true`
+        for (branch, expr) in branch_exprs {
+            let Some(expr) = expr else { continue };
+
+            if !utils::is_true_literal(expr) {
+                continue;
+            }
+
+            let is_fallback = match &branch.pattern {
+                Pattern::Wildcard { .. } => true,
+                pattern => matches!(pattern.span().as_str(), "None" | "Option::None"),
+            };
+
+            if !is_fallback {
+                continue;
+            }
+
+            project.report.borrow_mut().add_entry(
+                context.path,
+                project.span_to_line(context.path, &branch.span())?,
+                Severity::High,
+                format!(
+                    "{} returns `true` in the `{}` branch of `match {}`, which skips the validation performed in the other branches. Consider validating the transaction in every branch.",
+                    utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
+                    branch.pattern.span().as_str(),
+                    context.value.span().as_str(),
+                ),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_predicate_always_true() {
+        crate::tests::test_detector("predicate_always_true", 3);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{
+    error::Error,
+    project::Project,
+    report::Severity,
+    scope::AstScope,
+    utils,
+    visitor::{AstVisitor, ExprContext, ModuleContext, StatementLetContext},
+};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
+use sway_ast::Expr;
+use sway_types::{Span, Spanned};
+
+#[derive(Default)]
+pub struct PredicateMissingCountCheckVisitor {
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+#[derive(Default)]
+struct ModuleState {
+    input_read_span: Option<Span>,
+    output_read_span: Option<Span>,
+    count_variables: Vec<(String, &'static str)>,
+    has_input_count_check: bool,
+    has_output_count_check: bool,
+}
+
+impl ModuleState {
+    /// Gets the count functions called in the supplied `expr`, either directly or through variables bound to them.
+    fn get_count_fns(&self, expr: &Expr) -> Vec<&'static str> {
+        let mut result = vec![];
+
+        utils::map_expr(expr, &mut |expr| {
+            match utils::get_fn_call_name(expr).as_deref() {
+                Some("input_count") => result.push("input_count"),
+                Some("output_count") => result.push("output_count"),
+                _ => {}
+            }
+
+            if let Expr::Path(_) = expr {
+                let span = expr.span();
+
+                for (name, fn_name) in self.count_variables.iter() {
+                    if name == span.as_str() {
+                        result.push(*fn_name);
+                    }
+                }
+            }
+        });
+
+        result
+    }
+}
+
+impl AstVisitor for PredicateMissingCountCheckVisitor {
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Only check predicates
+        if !utils::is_predicate_module(context.module) {
+            return Ok(());
+        }
+
+        // Create the module state
+        if !self.module_states.contains_key(context.path) {
+            self.module_states.insert(context.path.into(), ModuleState::default());
+        }
+
+        Ok(())
+    }
+
+    fn visit_statement_let(&mut self, context: &StatementLetContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Check if the variable is bound to an input or output count
+        for fn_name in module_state.get_count_fns(&context.statement_let.expr) {
+            for ident in utils::fold_pattern_idents(&context.statement_let.pattern) {
+                module_state.count_variables.push((ident.as_str().to_string(), fn_name));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Check for inputs or outputs which are read by index
+        if let Some(name) = utils::get_fn_call_name(context.expr) {
+            if utils::INDEXED_INPUT_FNS.contains(&name.as_str()) && module_state.input_read_span.is_none() {
+                module_state.input_read_span = Some(context.expr.span());
+            }
+
+            if utils::INDEXED_OUTPUT_FNS.contains(&name.as_str()) && module_state.output_read_span.is_none() {
+                module_state.output_read_span = Some(context.expr.span());
+            }
+        }
+
+        // Check for comparisons against the input or output count
+        if !utils::is_comparison_expr(context.expr) {
+            return Ok(());
+        }
+
+        for fn_name in module_state.get_count_fns(context.expr) {
+            match fn_name {
+                "input_count" => module_state.has_input_count_check = true,
+                "output_count" => module_state.has_output_count_check = true,
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn leave_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get(context.path) else { return Ok(()) };
+
+        let checks = [
+            ("input", "input_count", module_state.input_read_span.as_ref(), module_state.has_input_count_check),
+            ("output", "output_count", module_state.output_read_span.as_ref(), module_state.has_output_count_check),
+        ];
+
+        for (kind, count_fn, read_span, has_count_check) in checks {
+            let Some(read_span) = read_span else { continue };
+
+            if has_count_check {
+                continue;
+            }
+
+            project.report.borrow_mut().add_entry(
+                context.path,
+                project.span_to_line(context.path, read_span)?,
+                Severity::Medium,
+                format!(
+                    "The predicate reads transaction {kind}s by index without checking the number of {kind}s: `{}`. Consider comparing `{count_fn}()` against the expected number of {kind}s, otherwise additional {kind}s can be smuggled into the transaction.",
+                    read_span.as_str(),
+                ),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_predicate_missing_count_check() {
+        crate::tests::test_detector("predicate_missing_count_check", 2);
+    }
+}
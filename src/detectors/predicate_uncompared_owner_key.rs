@@ -0,0 +1,180 @@
+use crate::{
+    error::Error,
+    project::Project,
+    report::Severity,
+    scope::AstScope,
+    utils,
+    visitor::{AstVisitor, ConfigurableFieldContext, ExprContext, ModuleContext, StatementLetContext},
+};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
+use sway_ast::Expr;
+use sway_types::{Span, Spanned};
+
+/// Name fragments of configurables which are considered to hold owner keys.
+const OWNER_KEY_NAMES: &[&str] = &["owner", "signer", "maker", "admin", "authority", "key"];
+
+/// Types of configurables which can hold owner keys.
+const OWNER_KEY_TYPES: &[&str] = &["Address", "b256", "B512", "Identity", "EvmAddress"];
+
+#[derive(Default)]
+pub struct PredicateUncomparedOwnerKeyVisitor {
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+#[derive(Default)]
+struct ModuleState {
+    owner_keys: Vec<OwnerKeyState>,
+    aliases: Vec<(String, String)>,
+}
+
+struct OwnerKeyState {
+    name: String,
+    span: Span,
+    is_compared: bool,
+    is_passed: bool,
+}
+
+impl ModuleState {
+    /// Gets the names of the owner keys referenced in the supplied `expr`, either directly or through variables bound to them.
+    fn get_owner_keys(&self, expr: &Expr) -> Vec<String> {
+        let mut result = vec![];
+
+        utils::map_expr(expr, &mut |expr| {
+            let Expr::Path(_) = expr else { return };
+            let span = expr.span();
+
+            if let Some(owner_key) = self.owner_keys.iter().find(|x| x.name == span.as_str()) {
+                result.push(owner_key.name.clone());
+            }
+
+            for (alias, name) in self.aliases.iter() {
+                if alias == span.as_str() {
+                    result.push(name.clone());
+                }
+            }
+        });
+
+        result
+    }
+}
+
+impl AstVisitor for PredicateUncomparedOwnerKeyVisitor {
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Only check predicates
+        if !utils::is_predicate_module(context.module) {
+            return Ok(());
+        }
+
+        // Create the module state
+        if !self.module_states.contains_key(context.path) {
+            self.module_states.insert(context.path.into(), ModuleState::default());
+        }
+
+        Ok(())
+    }
+
+    fn visit_configurable_field(&mut self, context: &ConfigurableFieldContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Check if the configurable holds an owner key
+        let name = context.field.name.as_str();
+        let lowercase_name = name.to_ascii_lowercase();
+
+        if !OWNER_KEY_NAMES.iter().any(|x| lowercase_name.contains(x)) {
+            return Ok(());
+        }
+
+        let type_name = utils::ty_to_string(&context.field.ty);
+        let type_name = type_name.rsplit("::").next().unwrap_or(type_name.as_str());
+
+        if !OWNER_KEY_TYPES.contains(&type_name) {
+            return Ok(());
+        }
+
+        module_state.owner_keys.push(OwnerKeyState {
+            name: name.to_string(),
+            span: context.field.name.span(),
+            is_compared: false,
+            is_passed: false,
+        });
+
+        Ok(())
+    }
+
+    fn visit_statement_let(&mut self, context: &StatementLetContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Check if the variable is bound to an owner key
+        for name in module_state.get_owner_keys(&context.statement_let.expr) {
+            for ident in utils::fold_pattern_idents(&context.statement_let.pattern) {
+                module_state.aliases.push((ident.as_str().to_string(), name.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Check for comparisons against owner keys
+        if utils::is_comparison_expr(context.expr) {
+            for name in module_state.get_owner_keys(context.expr) {
+                if let Some(owner_key) = module_state.owner_keys.iter_mut().find(|x| x.name == name) {
+                    owner_key.is_compared = true;
+                }
+            }
+        }
+
+        // Check for owner keys which are passed to functions, since the comparison can happen there
+        let args = match context.expr {
+            Expr::FuncApp { args, .. } => utils::fold_punctuated(&args.inner),
+            Expr::MethodCall { args, .. } => utils::fold_punctuated(&args.inner),
+            _ => return Ok(()),
+        };
+
+        for arg in args {
+            for name in module_state.get_owner_keys(arg) {
+                if let Some(owner_key) = module_state.owner_keys.iter_mut().find(|x| x.name == name) {
+                    owner_key.is_passed = true;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn leave_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get(context.path) else { return Ok(()) };
+
+        for owner_key in module_state.owner_keys.iter() {
+            if owner_key.is_compared || owner_key.is_passed {
+                continue;
+            }
+
+            project.report.borrow_mut().add_entry(
+                context.path,
+                project.span_to_line(context.path, &owner_key.span)?,
+                Severity::High,
+                format!(
+                    "The predicate declares the `{}` configurable as an owner key, but never compares it against a transaction input or recovered signer. Consider verifying the owner, otherwise anyone can spend the predicate's coins.",
+                    owner_key.name,
+                ),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_predicate_uncompared_owner_key() {
+        crate::tests::test_detector("predicate_uncompared_owner_key", 1);
+    }
+}
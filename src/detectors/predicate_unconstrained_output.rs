@@ -0,0 +1,187 @@
+use crate::{
+    error::Error,
+    project::Project,
+    report::Severity,
+    scope::AstScope,
+    utils,
+    visitor::{AstVisitor, ExprContext, FnContext, MatchExprContext, ModuleContext, StatementLetContext},
+};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
+use sway_ast::Expr;
+use sway_types::{Span, Spanned};
+
+/// The functions of `std::outputs` which read a field of an output that needs to be constrained, along with the name of the field.
+const OUTPUT_FIELD_FNS: &[(&str, &str)] = &[
+    ("output_asset_id", "asset id"),
+    ("output_asset_to", "recipient"),
+    ("output_amount", "amount"),
+];
+
+#[derive(Default)]
+pub struct PredicateUnconstrainedOutputVisitor {
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+#[derive(Default)]
+struct ModuleState {
+    main_span: Option<Span>,
+    read_fields: Vec<&'static str>,
+    constrained_fields: Vec<&'static str>,
+    field_variables: Vec<(String, &'static str)>,
+}
+
+impl ModuleState {
+    /// Gets the output fields read in the supplied `expr`, either directly or through variables bound to them.
+    fn get_output_fields(&self, expr: &Expr) -> Vec<&'static str> {
+        let mut result = vec![];
+
+        utils::map_expr(expr, &mut |expr| {
+            if let Some(name) = utils::get_fn_call_name(expr) {
+                if let Some((fn_name, _)) = OUTPUT_FIELD_FNS.iter().find(|(x, _)| *x == name) {
+                    result.push(*fn_name);
+                }
+            }
+
+            if let Expr::Path(_) = expr {
+                let span = expr.span();
+
+                for (name, fn_name) in self.field_variables.iter() {
+                    if name == span.as_str() {
+                        result.push(*fn_name);
+                    }
+                }
+            }
+        });
+
+        result
+    }
+}
+
+impl AstVisitor for PredicateUnconstrainedOutputVisitor {
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Only check predicates
+        if !utils::is_predicate_module(context.module) {
+            return Ok(());
+        }
+
+        // Create the module state
+        if !self.module_states.contains_key(context.path) {
+            self.module_states.insert(context.path.into(), ModuleState::default());
+        }
+
+        Ok(())
+    }
+
+    fn visit_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Store the span of the predicate's `main` function
+        if context.item_impl.is_none() && context.item_fn.fn_signature.name.as_str() == "main" {
+            module_state.main_span = Some(context.item_fn.fn_signature.span());
+        }
+
+        Ok(())
+    }
+
+    fn visit_statement_let(&mut self, context: &StatementLetContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Check if the variable is bound to an output field
+        for fn_name in module_state.get_output_fields(&context.statement_let.expr) {
+            for ident in utils::fold_pattern_idents(&context.statement_let.pattern) {
+                module_state.field_variables.push((ident.as_str().to_string(), fn_name));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_match_expr(&mut self, context: &MatchExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Check if the variables of the match branches are bound to an output field
+        for fn_name in module_state.get_output_fields(context.value) {
+            for branch in context.branches.inner.iter() {
+                for ident in utils::fold_pattern_idents(&branch.pattern) {
+                    module_state.field_variables.push((ident.as_str().to_string(), fn_name));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Check for reads of output fields
+        if let Some(name) = utils::get_fn_call_name(context.expr) {
+            if let Some((fn_name, _)) = OUTPUT_FIELD_FNS.iter().find(|(x, _)| *x == name) {
+                if !module_state.read_fields.contains(fn_name) {
+                    module_state.read_fields.push(fn_name);
+                }
+            }
+        }
+
+        // Check for comparisons which constrain output fields
+        if !utils::is_comparison_expr(context.expr) {
+            return Ok(());
+        }
+
+        for fn_name in module_state.get_output_fields(context.expr) {
+            if !module_state.constrained_fields.contains(&fn_name) {
+                module_state.constrained_fields.push(fn_name);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn leave_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get(context.path) else { return Ok(()) };
+
+        // Only check predicates which inspect their outputs
+        if module_state.read_fields.is_empty() {
+            return Ok(());
+        }
+
+        let unconstrained_fields = OUTPUT_FIELD_FNS.iter()
+            .filter(|(fn_name, _)| !module_state.constrained_fields.contains(fn_name))
+            .map(|(_, field_name)| *field_name)
+            .collect::<Vec<_>>();
+
+        if unconstrained_fields.is_empty() {
+            return Ok(());
+        }
+
+        let line = match module_state.main_span.as_ref() {
+            Some(span) => project.span_to_line(context.path, span)?,
+            None => None,
+        };
+
+        project.report.borrow_mut().add_entry(
+            context.path,
+            line,
+            Severity::High,
+            format!(
+                "The predicate inspects its transaction outputs without constraining their {}. Consider comparing every output field against the expected value, otherwise the spent coins can be redirected.",
+                unconstrained_fields.join(", "),
+            ),
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_predicate_unconstrained_output() {
+        crate::tests::test_detector("predicate_unconstrained_output", 1);
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{
+    error::Error,
+    project::Project,
+    report::Severity,
+    scope::AstScope,
+    utils,
+    visitor::{AstVisitor, ExprContext, FnContext, ModuleContext},
+};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
+use sway_ast::{Expr, IfCondition, IfExpr};
+use sway_types::{Span, Spanned};
+
+/// The functions which check the type of an input or output by its index.
+const INDEXED_TYPE_FNS: &[&str] = &["input_type", "output_type"];
+
+#[derive(Default)]
+pub struct ScriptFixedInputIndexVisitor {
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+#[derive(Default)]
+struct ModuleState {
+    fn_states: HashMap<Span, FnState>,
+}
+
+#[derive(Default)]
+struct FnState {
+    accesses: Vec<(Span, &'static str, String)>,
+    checked_indices: Vec<(&'static str, String)>,
+}
+
+/// Gets the kind of transaction element and the hard-coded index read by the supplied `expr`, if any.
+fn get_fixed_index_access(expr: &Expr) -> Option<(String, &'static str, String)> {
+    let name = utils::get_fn_call_name(expr)?;
+
+    let kind = if utils::INDEXED_INPUT_FNS.contains(&name.as_str()) {
+        "input"
+    } else if utils::INDEXED_OUTPUT_FNS.contains(&name.as_str()) {
+        "output"
+    } else {
+        return None;
+    };
+
+    // Check if the index is a hard-coded literal
+    let Expr::FuncApp { args, .. } = expr else { return None };
+    let args = utils::fold_punctuated(&args.inner);
+
+    let Some(Expr::Literal(index)) = args.first() else { return None };
+
+    Some((name, kind, index.span().as_str().to_string()))
+}
+
+impl AstVisitor for ScriptFixedInputIndexVisitor {
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Create the module state
+        if !self.module_states.contains_key(context.path) {
+            self.module_states.insert(context.path.into(), ModuleState::default());
+        }
+
+        Ok(())
+    }
+
+    fn visit_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Create the function state
+        module_state.fn_states.entry(context.item_fn.fn_signature.span()).or_default();
+
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Only check scripts
+        if !utils::is_script_module(context.module) {
+            return Ok(());
+        }
+
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Get the function state
+        let Some(item_fn) = context.item_fn.as_ref() else { return Ok(()) };
+        let fn_state = module_state.fn_states.get_mut(&item_fn.fn_signature.span()).unwrap();
+
+        // Check the inputs or outputs whose fields are validated in a `require` or `if` condition
+        let condition = if let Some(require_args) = utils::get_require_args(context.expr) {
+            require_args.first().copied()
+        } else if let Expr::If(IfExpr { condition: IfCondition::Expr(condition), .. }) = context.expr {
+            Some(condition.as_ref())
+        } else {
+            None
+        };
+
+        if let Some(condition) = condition {
+            utils::map_expr(condition, &mut |expr| {
+                if let Some((_, kind, index)) = get_fixed_index_access(expr) {
+                    fn_state.checked_indices.push((kind, index));
+                }
+            });
+        }
+
+        // Check for inputs or outputs which are read by a hard-coded index
+        let Some((name, kind, index)) = get_fixed_index_access(context.expr) else { return Ok(()) };
+
+        // Checking the type of the input or output validates it wherever it is checked
+        if INDEXED_TYPE_FNS.contains(&name.as_str()) {
+            fn_state.checked_indices.push((kind, index));
+            return Ok(());
+        }
+
+        fn_state.accesses.push((context.expr.span(), kind, index));
+
+        Ok(())
+    }
+
+    fn leave_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get(context.path).unwrap();
+
+        // Get the function state
+        let fn_state = module_state.fn_states.get(&context.item_fn.fn_signature.span()).unwrap();
+
+        // Only report the inputs or outputs whose type or fields are not checked anywhere in the function
+        for (span, kind, index) in fn_state.accesses.iter() {
+            if fn_state.checked_indices.iter().any(|(x, y)| x == kind && y == index) {
+                continue;
+            }
+
+            project.report.borrow_mut().add_entry(
+                context.path,
+                project.span_to_line(context.path, span)?,
+                Severity::Medium,
+                format!(
+                    "{} assumes a fixed transaction {kind} ordering: `{}`. Consider locating the {kind} by its type and fields, since the transaction builder controls the order of {kind}s.",
+                    utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
+                    span.as_str(),
+                ),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_script_fixed_input_index() {
+        crate::tests::test_detector("script_fixed_input_index", 2);
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{
+    error::Error,
+    project::Project,
+    report::Severity,
+    scope::AstScope,
+    utils,
+    visitor::{AstVisitor, FnContext, IfExprContext, ModuleContext, StatementLetContext, UseContext},
+};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
+use sway_ast::{Expr, IfCondition, Statement};
+use sway_types::{Span, Spanned};
+
+#[derive(Default)]
+pub struct ScriptMissingRevertVisitor {
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+struct ModuleState {
+    balance_fns: utils::FnCallMatcher,
+    fn_states: HashMap<Span, FnState>,
+}
+
+impl Default for ModuleState {
+    fn default() -> Self {
+        Self {
+            balance_fns: utils::FnCallMatcher::new(utils::BALANCE_FN_PATHS),
+            fn_states: Default::default(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct FnState {
+    abi_variables: Vec<String>,
+    result_variables: Vec<String>,
+}
+
+impl ModuleState {
+    /// Checks if the supplied `expr` depends on the result of an external call, a balance or a variable derived from either.
+    fn expr_is_call_result_dependent(&self, expr: &Expr, fn_state: &FnState) -> bool {
+        let mut result = false;
+
+        utils::map_expr(expr, &mut |expr| {
+            if result {
+                return;
+            }
+
+            match expr {
+                Expr::FuncApp { .. } => result = self.balance_fns.is_call(expr),
+
+                Expr::MethodCall { target, .. } => {
+                    result = match target.as_ref() {
+                        Expr::AbiCast { .. } => true,
+                        Expr::Path(_) => fn_state.abi_variables.iter().any(|x| x == target.span().as_str()),
+                        _ => false,
+                    };
+                }
+
+                Expr::Path(_) => result = fn_state.result_variables.iter().any(|x| x == expr.span().as_str()),
+
+                _ => {}
+            }
+        });
+
+        result
+    }
+}
+
+impl AstVisitor for ScriptMissingRevertVisitor {
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Only check scripts
+        if !utils::is_script_module(context.module) {
+            return Ok(());
+        }
+
+        // Create the module state
+        if !self.module_states.contains_key(context.path) {
+            self.module_states.insert(context.path.into(), ModuleState::default());
+        }
+
+        Ok(())
+    }
+
+    fn visit_use(&mut self, context: &UseContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Check the use tree for `std::context::{this_balance, balance_of}`
+        module_state.balance_fns.visit_use(context.item_use);
+
+        Ok(())
+    }
+
+    fn visit_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Create the function state
+        module_state.fn_states.entry(context.item_fn.fn_signature.span()).or_default();
+
+        Ok(())
+    }
+
+    fn visit_statement_let(&mut self, context: &StatementLetContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Get the function state
+        let fn_signature = context.item_fn.fn_signature.span();
+        let fn_state = module_state.fn_states.get(&fn_signature).unwrap();
+
+        let idents = utils::fold_pattern_idents(&context.statement_let.pattern).iter().map(|x| x.as_str().to_string()).collect::<Vec<_>>();
+
+        // Check if the variable is bound to an abi cast
+        if let Expr::AbiCast { .. } = &context.statement_let.expr {
+            let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
+            fn_state.abi_variables.extend(idents);
+            return Ok(());
+        }
+
+        // Check if the variable is derived from the result of an external call or a balance
+        if !module_state.expr_is_call_result_dependent(&context.statement_let.expr, fn_state) {
+            return Ok(());
+        }
+
+        let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
+        fn_state.result_variables.extend(idents);
+
+        Ok(())
+    }
+
+    fn visit_if_expr(&mut self, context: &IfExprContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Only check the `main` function of scripts
+        let Some(module_state) = self.module_states.get(context.path) else { return Ok(()) };
+
+        if context.item_impl.is_some() || context.item_fn.fn_signature.name.as_str() != "main" {
+            return Ok(());
+        }
+
+        // Get the function state
+        let fn_state = module_state.fn_states.get(&context.item_fn.fn_signature.span()).unwrap();
+
+        // Only check conditions which test the result of an external call or a balance
+        let IfCondition::Expr(condition) = &context.if_expr.condition else { return Ok(()) };
+
+        if !module_state.expr_is_call_result_dependent(condition, fn_state) {
+            return Ok(());
+        }
+
+        // Skip `if` expressions which revert
+        let then_block = &context.if_expr.then_block;
+
+        if utils::block_has_revert(then_block) {
+            return Ok(());
+        }
+
+        // Check if the `if` branch returns early from the script
+        let mut return_expr = None;
+
+        for statement in then_block.inner.statements.iter() {
+            if let Statement::Expr { expr: expr @ Expr::Return { .. }, .. } = statement {
+                return_expr = Some(expr);
+            }
+        }
+
+        if let Some(expr @ Expr::Return { .. }) = then_block.inner.final_expr_opt.as_deref() {
+            return_expr = Some(expr);
+        }
+
+        let Some(return_expr) = return_expr else { return Ok(()) };
+
+        project.report.borrow_mut().add_entry(
+            context.path,
+            project.span_to_line(context.path, &return_expr.span())?,
+            Severity::Medium,
+            format!(
+                "{} returns early without reverting when `{}` holds. Consider reverting on failed preconditions, otherwise the transaction succeeds and keeps the effects of the calls made so far.",
+                utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
+                context.if_expr.condition.span().as_str(),
+            ),
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_script_missing_revert() {
+        crate::tests::test_detector("script_missing_revert", 2);
+    }
+}
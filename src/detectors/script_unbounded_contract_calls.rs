@@ -0,0 +1,246 @@
+use crate::{
+    error::Error,
+    project::Project,
+    report::Severity,
+    scope::AstScope,
+    utils,
+    visitor::{AstVisitor, ExprContext, FnContext, ForExprContext, ModuleContext, StatementLetContext, WhileExprContext},
+};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
+use sway_ast::{Expr, FnArgs};
+use sway_types::{Span, Spanned};
+
+#[derive(Default)]
+pub struct ScriptUnboundedContractCallsVisitor {
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+#[derive(Default)]
+struct ModuleState {
+    fn_states: HashMap<Span, FnState>,
+}
+
+#[derive(Default)]
+struct FnState {
+    contract_ids_args: Vec<ContractIdsArgState>,
+    aliases: Vec<(String, String)>, // (variable name, argument name)
+    loop_bodies: Vec<Span>,
+}
+
+struct ContractIdsArgState {
+    name: String,
+    is_bounded: bool,
+    call_span: Option<Span>,
+}
+
+impl FnState {
+    /// Gets the names of the contract id arguments referenced in the supplied `expr`, either directly or through variables bound to them.
+    fn get_contract_ids_args(&self, expr: &Expr) -> Vec<String> {
+        let mut result = vec![];
+
+        for span in utils::fold_expr_ident_spans(expr) {
+            if let Some(arg_state) = self.contract_ids_args.iter().find(|x| x.name == span.as_str()) {
+                result.push(arg_state.name.clone());
+            }
+
+            for (alias, name) in self.aliases.iter() {
+                if alias == span.as_str() {
+                    result.push(name.clone());
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl AstVisitor for ScriptUnboundedContractCallsVisitor {
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Only check scripts
+        if !utils::is_script_module(context.module) {
+            return Ok(());
+        }
+
+        // Create the module state
+        if !self.module_states.contains_key(context.path) {
+            self.module_states.insert(context.path.into(), ModuleState::default());
+        }
+
+        Ok(())
+    }
+
+    fn visit_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Create the function state
+        let fn_signature = context.item_fn.fn_signature.span();
+        let fn_state = module_state.fn_states.entry(fn_signature).or_default();
+
+        // Only check the `main` function of scripts
+        if context.item_impl.is_some() || context.item_fn.fn_signature.name.as_str() != "main" {
+            return Ok(());
+        }
+
+        // Check to see if the function takes a `Vec` of contract ids
+        let FnArgs::Static(args) = &context.item_fn.fn_signature.arguments.inner else { return Ok(()) };
+
+        for arg in args {
+            let type_name = utils::ty_to_string(&arg.ty);
+
+            if !type_name.starts_with("Vec<") || !type_name.contains("ContractId") {
+                continue;
+            }
+
+            for ident in utils::fold_pattern_idents(&arg.pattern) {
+                fn_state.contract_ids_args.push(ContractIdsArgState {
+                    name: ident.as_str().to_string(),
+                    is_bounded: false,
+                    call_span: None,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_statement_let(&mut self, context: &StatementLetContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Get the function state
+        let fn_signature = context.item_fn.fn_signature.span();
+        let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
+
+        // Check if the variable is bound to an element of a contract id argument
+        for name in fn_state.get_contract_ids_args(&context.statement_let.expr) {
+            for ident in utils::fold_pattern_idents(&context.statement_let.pattern) {
+                fn_state.aliases.push((ident.as_str().to_string(), name.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_while_expr(&mut self, context: &WhileExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Get the function state
+        let fn_signature = context.item_fn.fn_signature.span();
+        let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
+
+        // Store the while expression's body block as a loop
+        fn_state.loop_bodies.push(context.body.span());
+
+        Ok(())
+    }
+
+    fn visit_for_expr(&mut self, context: &ForExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Get the function state
+        let fn_signature = context.item_fn.fn_signature.span();
+        let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
+
+        // Store the for expression's body block as a loop
+        fn_state.loop_bodies.push(context.body.span());
+
+        // Check if the loop variable is bound to an element of a contract id argument
+        for name in fn_state.get_contract_ids_args(context.iterator) {
+            for ident in utils::fold_pattern_idents(context.pattern) {
+                fn_state.aliases.push((ident.as_str().to_string(), name.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Get the function state
+        let Some(item_fn) = context.item_fn.as_ref() else { return Ok(()) };
+        let fn_signature = item_fn.fn_signature.span();
+        let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
+
+        if fn_state.contract_ids_args.is_empty() {
+            return Ok(());
+        }
+
+        // Check for `require`, `assert` or `if ... { revert(...) }` expressions that limit the length of a contract id argument
+        let condition_span = match utils::get_fn_call_name(context.expr).as_deref() {
+            Some("require" | "assert") => Some(context.expr.span()),
+            _ => utils::get_if_revert_condition(context.expr).map(|x| x.span()),
+        };
+
+        if let Some(condition_span) = condition_span {
+            for arg_state in fn_state.contract_ids_args.iter_mut() {
+                if condition_span.as_str().contains(format!("{}.len()", arg_state.name).as_str()) {
+                    arg_state.is_bounded = true;
+                }
+            }
+
+            return Ok(());
+        }
+
+        // Check for abi casts of contract id arguments in a loop
+        let Expr::AbiCast { args, .. } = context.expr else { return Ok(()) };
+
+        if !context.blocks.iter().any(|block_span| fn_state.loop_bodies.contains(block_span)) {
+            return Ok(());
+        }
+
+        for name in fn_state.get_contract_ids_args(args.inner.address.as_ref()) {
+            let arg_state = fn_state.contract_ids_args.iter_mut().find(|x| x.name == name).unwrap();
+
+            if arg_state.call_span.is_none() {
+                arg_state.call_span = Some(context.expr.span());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn leave_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get(context.path) else { return Ok(()) };
+
+        // Get the function state
+        let fn_signature = context.item_fn.fn_signature.span();
+        let fn_state = module_state.fn_states.get(&fn_signature).unwrap();
+
+        for arg_state in fn_state.contract_ids_args.iter() {
+            let Some(call_span) = arg_state.call_span.as_ref() else { continue };
+
+            if arg_state.is_bounded {
+                continue;
+            }
+
+            project.report.borrow_mut().add_entry(
+                context.path,
+                project.span_to_line(context.path, call_span)?,
+                Severity::Medium,
+                format!(
+                    "{} calls every contract in the unbounded `{}` argument: `{}`. Consider limiting the length of `{}`, otherwise a large batch can exceed the transaction gas limit.",
+                    utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
+                    arg_state.name,
+                    call_span.as_str(),
+                    arg_state.name,
+                ),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_script_unbounded_contract_calls() {
+        crate::tests::test_detector("script_unbounded_contract_calls", 1);
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{
+    error::Error,
+    project::Project,
+    report::Severity,
+    scope::AstScope,
+    utils,
+    visitor::{AstVisitor, ExprContext, FnContext, ModuleContext, StatementLetContext},
+};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
+use sway_ast::{Expr, ItemKind, ItemTraitItem, Pattern, Statement};
+use sway_types::{Span, Spanned};
+
+#[derive(Default)]
+pub struct ScriptUncheckedCallResultVisitor {
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+#[derive(Default)]
+struct ModuleState {
+    fn_states: HashMap<Span, FnState>,
+}
+
+#[derive(Default)]
+struct FnState {
+    abi_variables: Vec<(String, String)>, // (variable name, abi name)
+}
+
+/// Gets the name of the `abi` cast in the supplied `expr`, without its path prefix.
+fn get_abi_cast_name(expr: &Expr) -> Option<String> {
+    let Expr::AbiCast { args, .. } = expr else { return None };
+    let abi_name = utils::path_type_to_string(&args.inner.name);
+    Some(abi_name.rsplit("::").next().unwrap_or(abi_name.as_str()).to_string())
+}
+
+/// Checks if the `fn_name` function of the `abi_name` abi is declared with a return value in any module of the project.
+fn abi_fn_has_return_value(project: &Project, abi_name: &str, fn_name: &str) -> bool {
+    let modules = project.modules();
+    let modules = modules.borrow();
+
+    for module in modules.values() {
+        for item in module.items.iter() {
+            let ItemKind::Abi(item_abi) = &item.value else { continue };
+
+            if item_abi.name.as_str() != abi_name {
+                continue;
+            }
+
+            for abi_item in item_abi.abi_items.inner.iter() {
+                let ItemTraitItem::Fn(fn_signature, _) = &abi_item.value else { continue };
+
+                if fn_signature.name.as_str() != fn_name {
+                    continue;
+                }
+
+                return fn_signature.return_type_opt.as_ref()
+                    .map(|(_, ty)| utils::ty_to_string(ty) != "()")
+                    .unwrap_or(false);
+            }
+        }
+    }
+
+    false
+}
+
+impl AstVisitor for ScriptUncheckedCallResultVisitor {
+    fn is_cross_module(&self) -> bool {
+        true
+    }
+
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Only check scripts
+        if !utils::is_script_module(context.module) {
+            return Ok(());
+        }
+
+        // Create the module state
+        if !self.module_states.contains_key(context.path) {
+            self.module_states.insert(context.path.into(), ModuleState::default());
+        }
+
+        Ok(())
+    }
+
+    fn visit_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Create the function state
+        let fn_signature = context.item_fn.fn_signature.span();
+
+        module_state.fn_states.entry(fn_signature).or_default();
+
+        Ok(())
+    }
+
+    fn visit_statement_let(&mut self, context: &StatementLetContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Get the function state
+        let fn_signature = context.item_fn.fn_signature.span();
+        let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
+
+        // Check if the variable is bound to an abi cast
+        let Some(abi_name) = get_abi_cast_name(&context.statement_let.expr) else { return Ok(()) };
+        let idents = utils::fold_pattern_idents(&context.statement_let.pattern);
+
+        if idents.len() == 1 {
+            fn_state.abi_variables.push((idents[0].as_str().to_string(), abi_name));
+        }
+
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get(context.path) else { return Ok(()) };
+
+        // Get the function state
+        let Some(item_fn) = context.item_fn.as_ref() else { return Ok(()) };
+        let fn_signature = item_fn.fn_signature.span();
+        let fn_state = module_state.fn_states.get(&fn_signature).unwrap();
+
+        // Check to see if the expression is a method call on an abi
+        let Expr::MethodCall { target, path_seg, .. } = context.expr else { return Ok(()) };
+
+        let abi_name = match target.as_ref() {
+            Expr::Path(_) => {
+                let target_name = target.span();
+
+                let Some((_, abi_name)) = fn_state.abi_variables.iter().rev().find(|(name, _)| name == target_name.as_str()) else {
+                    return Ok(());
+                };
+
+                abi_name.clone()
+            }
+
+            expr => {
+                let Some(abi_name) = get_abi_cast_name(expr) else { return Ok(()) };
+                abi_name
+            }
+        };
+
+        // Check to see if the result of the call is discarded
+        let is_discarded = match context.statement {
+            Some(Statement::Expr { expr, .. }) => expr.span() == context.expr.span(),
+            Some(Statement::Let(statement_let)) => matches!(statement_let.pattern, Pattern::Wildcard { .. }) && statement_let.expr.span() == context.expr.span(),
+            _ => false,
+        };
+
+        if !is_discarded || !abi_fn_has_return_value(project, &abi_name, path_seg.name.as_str()) {
+            return Ok(());
+        }
+
+        project.report.borrow_mut().add_entry(
+            context.path,
+            project.span_to_line(context.path, &context.expr.span())?,
+            Severity::Medium,
+            format!(
+                "{} discards the result of an external call: `{}`. Consider checking the returned value, otherwise a failed call in the batch goes unnoticed.",
+                utils::get_item_location(context.item, &context.item_impl, &context.item_fn),
+                context.expr.span().as_str(),
+            ),
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_script_unchecked_call_result() {
+        crate::tests::test_detector("script_unchecked_call_result", 2);
+    }
+}
@@ -0,0 +1,509 @@
+use crate::{
+    error::Error,
+    project::Project,
+    report::Severity,
+    scope::AstScope,
+    utils,
+    visitor::{AstVisitor, ExprContext, FnContext, ImplContext, ModuleContext},
+};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
+use sway_ast::{Expr, FnArgs, FnSignature, ItemImplItem, ItemKind, Literal};
+use sway_types::{Span, Spanned};
+
+struct Standard {
+    name: &'static str,
+    fns: &'static [StandardFn],
+    events: &'static [(&'static str, &'static str)], // (setter function name, event name)
+}
+
+struct StandardFn {
+    name: &'static str,
+    signatures: &'static [&'static str],
+    payable: bool,
+}
+
+const STANDARDS: &[Standard] = &[
+    Standard {
+        name: "SRC20",
+        fns: &[
+            StandardFn { name: "total_assets", signatures: &["fn total_assets() -> u64"], payable: false },
+            StandardFn { name: "total_supply", signatures: &["fn total_supply(AssetId) -> Option<u64>"], payable: false },
+            StandardFn { name: "name", signatures: &["fn name(AssetId) -> Option<String>"], payable: false },
+            StandardFn { name: "symbol", signatures: &["fn symbol(AssetId) -> Option<String>"], payable: false },
+            StandardFn { name: "decimals", signatures: &["fn decimals(AssetId) -> Option<u8>"], payable: false },
+        ],
+        events: &[
+            ("set_name", "SetNameEvent"),
+            ("set_symbol", "SetSymbolEvent"),
+            ("set_decimals", "SetDecimalsEvent"),
+        ],
+    },
+    Standard {
+        name: "SRC3",
+        fns: &[
+            StandardFn {
+                name: "mint",
+                // Earlier revisions of SRC-3 did not make the sub id optional
+                signatures: &["fn mint(Identity, Option<SubId>, u64)", "fn mint(Identity, SubId, u64)"],
+                payable: false,
+            },
+            StandardFn { name: "burn", signatures: &["fn burn(SubId, u64)"], payable: true },
+        ],
+        events: &[],
+    },
+    Standard {
+        name: "SRC7",
+        fns: &[
+            StandardFn { name: "metadata", signatures: &["fn metadata(AssetId, String) -> Option<Metadata>"], payable: false },
+        ],
+        events: &[
+            ("set_metadata", "SetMetadataEvent"),
+        ],
+    },
+    Standard {
+        name: "SRC5",
+        fns: &[
+            StandardFn { name: "owner", signatures: &["fn owner() -> State"], payable: false },
+        ],
+        events: &[],
+    },
+];
+
+/// The event names that are required to be logged by the supported standards.
+const EVENT_NAMES: &[&str] = &["SetNameEvent", "SetSymbolEvent", "SetDecimalsEvent", "SetMetadataEvent", "TotalSupplyEvent"];
+
+/// The `sway_libs` helper functions that log standard events internally.
+const LOGGING_HELPERS: &[(&str, &str)] = &[
+    ("_set_name", "SetNameEvent"),
+    ("_set_symbol", "SetSymbolEvent"),
+    ("_set_decimals", "SetDecimalsEvent"),
+    ("_set_metadata", "SetMetadataEvent"),
+    ("_mint", "TotalSupplyEvent"),
+    ("_burn", "TotalSupplyEvent"),
+];
+
+#[derive(Default)]
+pub struct SrcStandardConformanceVisitor {
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+#[derive(Default)]
+struct ModuleState {
+    standards: Vec<&'static str>,
+    constants: HashMap<String, Expr>,
+    fn_states: HashMap<Span, FnState>,
+    decimals_returned: Vec<String>,
+    decimals_logged: Vec<LoggedDecimals>,
+}
+
+impl ModuleState {
+    /// Attempts to resolve the integer value of the supplied `expr`, following constants and configurables.
+    fn expr_to_int_value(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Literal(Literal::Int(value)) => Some(value.parsed.to_string()),
+            Expr::Parens(parens) => self.expr_to_int_value(parens.inner.as_ref()),
+
+            Expr::Path(path) if path.root_opt.is_none() && path.suffix.is_empty() => {
+                self.expr_to_int_value(self.constants.get(path.prefix.name.as_str())?)
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Gets the signature spans of the functions the supplied function `call` may resolve to from the supplied `caller`.
+    ///
+    /// Calls through `Self::` resolve to the functions of the caller's impl, and other calls resolve to free functions.
+    fn resolve_fn_call(&self, caller: &FnState, call: &str) -> Vec<&Span> {
+        let (name, impl_span) = match call.strip_prefix("Self::") {
+            Some(name) => (name, caller.impl_span.as_ref()),
+            None => (call, None),
+        };
+
+        self.fn_states.iter()
+            .filter(|(_, x)| x.name == name && x.impl_span.as_ref() == impl_span)
+            .map(|(span, _)| span)
+            .collect()
+    }
+
+    /// Checks if the function with the supplied `signature_span` logs the `event`, either directly or through one of the functions it calls.
+    fn fn_logs_event(&self, signature_span: &Span, event: &str, visited: &mut Vec<Span>) -> bool {
+        if visited.contains(signature_span) {
+            return false;
+        }
+
+        visited.push(signature_span.clone());
+
+        let Some(fn_state) = self.fn_states.get(signature_span) else { return false };
+
+        fn_state.logged_events.iter().any(|x| x == event)
+            || fn_state.function_calls.iter()
+                .flat_map(|x| self.resolve_fn_call(fn_state, x))
+                .any(|x| self.fn_logs_event(x, event, visited))
+    }
+
+    /// Checks if the function with the supplied `signature_span` mints or burns assets, either directly or through one of the functions it calls.
+    fn fn_changes_supply(&self, signature_span: &Span, visited: &mut Vec<Span>) -> bool {
+        if visited.contains(signature_span) {
+            return false;
+        }
+
+        visited.push(signature_span.clone());
+
+        let Some(fn_state) = self.fn_states.get(signature_span) else { return false };
+
+        fn_state.changes_supply
+            || fn_state.function_calls.iter()
+                .flat_map(|x| self.resolve_fn_call(fn_state, x))
+                .any(|x| self.fn_changes_supply(x, visited))
+    }
+}
+
+struct FnState {
+    name: String,
+    impl_span: Option<Span>,
+    location: String,
+    span: Span,
+    is_contract_fn: bool,
+    changes_supply: bool,
+    logged_events: Vec<String>,
+    function_calls: Vec<String>,
+}
+
+struct LoggedDecimals {
+    location: String,
+    span: Span,
+    value: String,
+}
+
+/// Converts the supplied `fn_signature` into the normalized form used by the `STANDARDS` table.
+fn fn_signature_to_string(fn_signature: &FnSignature) -> String {
+    let args = match &fn_signature.arguments.inner {
+        FnArgs::Static(args) => Some(args),
+        FnArgs::NonStatic { args_opt, .. } => args_opt.as_ref().map(|(_, args)| args),
+    };
+
+    let args = args
+        .map(|args| utils::fold_punctuated(args).iter().map(|arg| utils::ty_to_string(&arg.ty)).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    let mut result = format!("fn {}({})", fn_signature.name.as_str(), args.join(", "));
+
+    if let Some((_, ty)) = fn_signature.return_type_opt.as_ref() {
+        result.push_str(format!(" -> {}", utils::ty_to_string(ty)).as_str());
+    }
+
+    result
+}
+
+/// Gets the name of the struct type constructed by the supplied logged `expr`, i.e. `Event` for `Event { .. }` or `Event::new(..)`.
+fn get_logged_struct_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Struct { path, .. } => utils::fold_path_idents(path).last().map(|x| x.as_str().to_string()),
+
+        Expr::FuncApp { func, .. } => {
+            let Expr::Path(path) = func.as_ref() else { return None };
+            let idents = utils::fold_path_idents(path);
+            let [.., ty, _] = idents.as_slice() else { return None };
+            Some(ty.as_str().to_string())
+        }
+
+        Expr::Parens(parens) => get_logged_struct_name(parens.inner.as_ref()),
+
+        _ => None,
+    }
+}
+
+#[inline]
+fn signatures_match(a: &str, b: &str) -> bool {
+    a.chars().filter(|c| !c.is_whitespace()).eq(b.chars().filter(|c| !c.is_whitespace()))
+}
+
+impl AstVisitor for SrcStandardConformanceVisitor {
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Create the module state
+        let module_state = self.module_states.entry(context.path.into()).or_default();
+
+        // Collect module-level constants and configurables ahead of time so they can be used to resolve decimals
+        for item in context.module.items.iter() {
+            match &item.value {
+                ItemKind::Const(item_const) => {
+                    let Some(expr) = item_const.expr_opt.as_ref() else { continue };
+                    module_state.constants.insert(item_const.name.as_str().to_string(), expr.clone());
+                }
+
+                ItemKind::Configurable(item_configurable) => {
+                    for field in utils::fold_punctuated(&item_configurable.fields.inner) {
+                        module_state.constants.insert(field.value.name.as_str().to_string(), field.value.initializer.clone());
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_impl(&mut self, context: &ImplContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Only check standard abi implementations for `Contract`
+        let Some((path_type, _)) = context.item_impl.trait_opt.as_ref() else { return Ok(()) };
+
+        if context.item_impl.ty.span().as_str() != "Contract" {
+            return Ok(());
+        }
+
+        let trait_name = path_type.suffix.last().map(|(_, x)| x).unwrap_or(&path_type.prefix).name.as_str();
+        let Some(standard) = STANDARDS.iter().find(|x| x.name == trait_name) else { return Ok(()) };
+
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        if !module_state.standards.contains(&standard.name) {
+            module_state.standards.push(standard.name);
+        }
+
+        for standard_fn in standard.fns.iter() {
+            // Find the implementation of the standard function
+            let item = context.item_impl.contents.inner.iter().find(|item| {
+                matches!(&item.value, ItemImplItem::Fn(item_fn) if item_fn.fn_signature.name.as_str() == standard_fn.name)
+            });
+
+            let Some(item) = item else {
+                project.report.borrow_mut().add_entry(
+                    context.path,
+                    project.span_to_line(context.path, &context.item_impl.span())?,
+                    Severity::Medium,
+                    format!(
+                        "The `{}` implementation for `Contract` is missing the required `{}` function.",
+                        standard.name,
+                        standard_fn.signatures[0],
+                    ),
+                );
+                continue;
+            };
+
+            let ItemImplItem::Fn(item_fn) = &item.value else { unreachable!() };
+
+            // Check the signature of the standard function
+            let signature = fn_signature_to_string(&item_fn.fn_signature);
+
+            if !standard_fn.signatures.iter().any(|x| signatures_match(x, signature.as_str())) {
+                project.report.borrow_mut().add_entry(
+                    context.path,
+                    project.span_to_line(context.path, &item_fn.fn_signature.span())?,
+                    Severity::Medium,
+                    format!(
+                        "The `Contract::{}` function does not match the `{}` standard: expected `{}`, found `{}`.",
+                        standard_fn.name,
+                        standard.name,
+                        standard_fn.signatures[0],
+                        signature,
+                    ),
+                );
+            }
+
+            // Check if the standard function is required to be payable
+            if standard_fn.payable && !utils::check_attribute_decls(item.attributes.as_slice(), "payable", &[]) {
+                project.report.borrow_mut().add_entry(
+                    context.path,
+                    project.span_to_line(context.path, &item_fn.fn_signature.span())?,
+                    Severity::Medium,
+                    format!(
+                        "The `Contract::{}` function is not marked `#[payable]`, which is required by the `{}` standard.",
+                        standard_fn.name,
+                        standard.name,
+                    ),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Create the function state
+        module_state.fn_states.entry(context.item_fn.fn_signature.span()).or_insert_with(|| FnState {
+            name: context.item_fn.fn_signature.name.as_str().to_string(),
+            impl_span: context.item_impl.map(|x| x.span()),
+            location: utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
+            span: context.item_fn.fn_signature.span(),
+            is_contract_fn: context.item_impl.map(|x| x.ty.span().as_str() == "Contract").unwrap_or(false),
+            changes_supply: false,
+            logged_events: vec![],
+            function_calls: vec![],
+        });
+
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        let Some(item_fn) = context.item_fn.as_ref() else { return Ok(()) };
+        let fn_name = item_fn.fn_signature.name.as_str();
+
+        match context.expr {
+            Expr::FuncApp { func, args } => {
+                let Expr::Path(path) = func.as_ref() else { return Ok(()) };
+                let idents = utils::fold_path_idents(path);
+                let Some(name) = idents.last().map(|x| x.as_str().to_string()) else { return Ok(()) };
+                let args = utils::fold_punctuated(&args.inner);
+
+                // Collect the values returned by the `decimals` function
+                if fn_name == "decimals" && name == "Some" && args.len() == 1 {
+                    if let Some(value) = module_state.expr_to_int_value(args[0]) {
+                        if !module_state.decimals_returned.contains(&value) {
+                            module_state.decimals_returned.push(value);
+                        }
+                    }
+                }
+
+                let fn_state = module_state.fn_states.get_mut(&item_fn.fn_signature.span()).unwrap();
+
+                match name.as_str() {
+                    // Check for logged standard events
+                    "log" if args.len() == 1 => {
+                        if let Some(event_name) = get_logged_struct_name(args[0]) {
+                            if EVENT_NAMES.contains(&event_name.as_str()) && !fn_state.logged_events.contains(&event_name) {
+                                fn_state.logged_events.push(event_name);
+                            }
+                        }
+                    }
+
+                    // Check for calls to `std::asset::{mint, mint_to, burn}`
+                    "mint" | "mint_to" | "burn" if idents.len() == 1 || idents.iter().any(|x| x.as_str() == "asset") => {
+                        fn_state.changes_supply = true;
+                    }
+
+                    _ => {}
+                }
+
+                // Check for calls to `sway_libs` helpers which log standard events internally
+                if let Some((_, event_name)) = LOGGING_HELPERS.iter().find(|(helper, _)| *helper == name) {
+                    if !fn_state.logged_events.iter().any(|x| x == event_name) {
+                        fn_state.logged_events.push(event_name.to_string());
+                    }
+                }
+
+                // Check for function calls
+                let func = func.span().as_str().to_string();
+                if !fn_state.function_calls.contains(&func) {
+                    fn_state.function_calls.push(func);
+                }
+            }
+
+            // Collect the decimals logged in `SetDecimalsEvent` structs
+            Expr::Struct { path, fields } => {
+                let Some(name) = utils::fold_path_idents(path).last().map(|x| x.as_str().to_string()) else { return Ok(()) };
+
+                if name != "SetDecimalsEvent" {
+                    return Ok(());
+                }
+
+                for field in utils::fold_punctuated(&fields.inner) {
+                    if field.field_name.as_str() != "decimals" {
+                        continue;
+                    }
+
+                    let Some((_, expr)) = field.expr_opt.as_ref() else { continue };
+                    let Some(value) = module_state.expr_to_int_value(expr.as_ref()) else { continue };
+
+                    module_state.decimals_logged.push(LoggedDecimals {
+                        location: utils::get_item_location(context.item, &context.item_impl, &context.item_fn),
+                        span: context.expr.span(),
+                        value,
+                    });
+                }
+            }
+
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn leave_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get(context.path).unwrap();
+
+        for standard in STANDARDS.iter().filter(|x| module_state.standards.contains(&x.name)) {
+            // Check if the setter functions of the standard log their required events
+            for (setter_name, event_name) in standard.events.iter() {
+                let Some((span, fn_state)) = module_state.fn_states.iter().find(|(_, x)| x.is_contract_fn && x.name == *setter_name) else { continue };
+
+                if module_state.fn_logs_event(span, event_name, &mut vec![]) {
+                    continue;
+                }
+
+                project.report.borrow_mut().add_entry(
+                    context.path,
+                    project.span_to_line(context.path, &fn_state.span)?,
+                    Severity::Medium,
+                    format!(
+                        "{} does not log a `{}`, which is required by the `{}` standard.",
+                        fn_state.location,
+                        event_name,
+                        standard.name,
+                    ),
+                );
+            }
+        }
+
+        if !module_state.standards.contains(&"SRC20") {
+            return Ok(());
+        }
+
+        // Check if contract functions that change the total supply of an asset log a `TotalSupplyEvent`
+        for (span, fn_state) in module_state.fn_states.iter() {
+            if !fn_state.is_contract_fn || !module_state.fn_changes_supply(span, &mut vec![]) {
+                continue;
+            }
+
+            if module_state.fn_logs_event(span, "TotalSupplyEvent", &mut vec![]) {
+                continue;
+            }
+
+            project.report.borrow_mut().add_entry(
+                context.path,
+                project.span_to_line(context.path, &fn_state.span)?,
+                Severity::Medium,
+                format!(
+                    "{} changes the total supply of an asset without logging a `TotalSupplyEvent`, which is required by the `SRC20` standard.",
+                    fn_state.location,
+                ),
+            );
+        }
+
+        // Check if the logged decimals are consistent with the decimals returned by the `decimals` function
+        let [returned] = module_state.decimals_returned.as_slice() else { return Ok(()) };
+
+        for logged in module_state.decimals_logged.iter().filter(|x| x.value != *returned) {
+            project.report.borrow_mut().add_entry(
+                context.path,
+                project.span_to_line(context.path, &logged.span)?,
+                Severity::Medium,
+                format!(
+                    "{} logs a `SetDecimalsEvent` with {} decimals, but the `decimals` function returns {}.",
+                    logged.location,
+                    logged.value,
+                    returned,
+                ),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_src_standard_conformance() {
+        crate::tests::test_detector("src_standard_conformance", 6);
+    }
+}
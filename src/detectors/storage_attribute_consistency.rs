@@ -0,0 +1,509 @@
+use crate::{
+    error::Error,
+    project::Project,
+    report::Severity,
+    scope::AstScope,
+    utils,
+    visitor::{AstVisitor, ExprContext, FnContext, ModuleContext, StatementContext, UseContext},
+};
+use std::{cell::RefCell, collections::HashMap, path::{Path, PathBuf}, rc::Rc};
+use sway_ast::{attribute::Annotated, Expr, FnArgs, ItemImplItem, ItemKind, ItemTraitItem};
+use sway_types::{Span, Spanned};
+
+/// The names of the primitive types, whose associated functions are called like functions in a module, e.g. `b256::zero()`.
+const PRIMITIVE_TYPE_NAMES: &[&str] = &["u8", "u16", "u32", "u64", "u256", "bool", "str", "b256", "raw_ptr", "raw_slice"];
+
+/// The paths of the `std` functions which read storage slots directly.
+const STORAGE_API_READ_FN_PATHS: &[&str] = &["std::storage::storage_api::read"];
+
+/// The paths of the `std` functions which write storage slots directly.
+const STORAGE_API_WRITE_FN_PATHS: &[&str] = &["std::storage::storage_api::write", "std::storage::storage_api::clear"];
+
+#[derive(Default)]
+pub struct StorageAttributeConsistencyVisitor {
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+struct ModuleState {
+    storage_read_fns: utils::FnCallMatcher,
+    storage_write_fns: utils::FnCallMatcher,
+    imports: utils::ImportState,
+    fn_states: Vec<FnState>,
+}
+
+impl Default for ModuleState {
+    fn default() -> Self {
+        Self {
+            storage_read_fns: utils::FnCallMatcher::new(STORAGE_API_READ_FN_PATHS),
+            storage_write_fns: utils::FnCallMatcher::new(STORAGE_API_WRITE_FN_PATHS),
+            imports: Default::default(),
+            fn_states: vec![],
+        }
+    }
+}
+
+struct FnState {
+    name: String,
+    location: String,
+    signature_span: Span,
+    abi_name: Option<String>,
+    impl_type: Option<String>,
+    declares_read: bool,
+    declares_write: bool,
+    reads_storage: bool,
+    writes_storage: bool,
+    has_unresolved_access: bool,
+    storage_bindings: utils::StorageBindingState,
+    fn_calls: Vec<FnCall>,
+}
+
+/// A call made by a function, which is resolved to the functions it calls once all functions of the module are known.
+#[derive(PartialEq)]
+enum FnCall {
+    /// A call through the supplied path segments, e.g. `helper()`, `Type::helper()` or `module::helper()`.
+    Path(Vec<String>),
+    /// A call to the method with the supplied name, e.g. `value.helper()`.
+    Method(String),
+}
+
+/// The storage accesses of a function, including the storage accesses of the functions it calls.
+#[derive(Clone, Copy)]
+struct StorageAccess {
+    reads: bool,
+    writes: bool,
+    /// Whether the function passes storage keys to other functions or calls functions which could not be resolved,
+    /// in which case its storage accesses are unknown.
+    is_unresolved: bool,
+}
+
+/// Formats the `#[storage(...)]` attribute with the supplied accesses.
+fn storage_attribute_to_string(read: bool, write: bool) -> String {
+    match (read, write) {
+        (true, true) => "#[storage(read, write)]".into(),
+        (true, false) => "#[storage(read)]".into(),
+        (false, true) => "#[storage(write)]".into(),
+        (false, false) => "no `#[storage(...)]` attribute".into(),
+    }
+}
+
+/// Gets the storage accesses declared by the `fn_name` function of the `abi_name` abi in any module of the project.
+fn get_abi_fn_storage_access(project: &Project, abi_name: &str, fn_name: &str) -> Option<(bool, bool)> {
+    let modules = project.modules();
+    let modules = modules.borrow();
+
+    for module in modules.values() {
+        for item in module.items.iter() {
+            let ItemKind::Abi(item_abi) = &item.value else { continue };
+
+            if item_abi.name.as_str() != abi_name {
+                continue;
+            }
+
+            for Annotated { attributes, value } in item_abi.abi_items.inner.iter() {
+                let ItemTraitItem::Fn(fn_signature, _) = value else { continue };
+
+                if fn_signature.name.as_str() != fn_name {
+                    continue;
+                }
+
+                return Some((
+                    utils::check_attribute_decls(attributes, "storage", &["read"]),
+                    utils::check_attribute_decls(attributes, "storage", &["write"]),
+                ));
+            }
+        }
+    }
+
+    None
+}
+
+/// Checks if any module other than the one at `path` declares an impl function named `fn_name`, in the package of the module or one of its dependencies.
+fn is_external_impl_fn_name(project: &Project, path: &Path, fn_name: &str) -> bool {
+    let modules = project.modules();
+    let modules = modules.borrow();
+
+    let packages = project.packages();
+    let package_roots = packages.package_for_path(path)
+        .map(|package| std::iter::once(package).chain(packages.get_dependencies(package)).map(|x| x.root.clone()).collect::<Vec<_>>());
+
+    let is_visible_module = |module_path: &Path| match package_roots.as_ref() {
+        Some(package_roots) => packages.package_for_path(module_path).map(|x| package_roots.contains(&x.root)).unwrap_or(false),
+        None => true,
+    };
+
+    modules.iter().filter(|(x, _)| x.as_path() != path && is_visible_module(x)).any(|(_, module)| {
+        module.items.iter().any(|item| {
+            let ItemKind::Impl(item_impl) = &item.value else { return false };
+
+            item_impl.contents.inner.iter().any(|item| {
+                matches!(&item.value, ItemImplItem::Fn(item_fn) if item_fn.fn_signature.name.as_str() == fn_name)
+            })
+        })
+    })
+}
+
+impl ModuleState {
+    /// Gets the indices of the functions of the module which the supplied `fn_call` resolves to.
+    fn resolve_local_fn_call(&self, fn_call: &FnCall) -> Vec<usize> {
+        self.fn_states.iter()
+            .enumerate()
+            .filter(|(_, x)| x.abi_name.is_none() && match fn_call {
+                FnCall::Path(path) => match path.as_slice() {
+                    [name] => x.impl_type.is_none() && x.name == *name,
+                    [impl_type, name] => x.impl_type.as_ref() == Some(impl_type) && x.name == *name,
+                    _ => false,
+                },
+
+                FnCall::Method(name) => x.impl_type.is_some() && x.name == *name,
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Gets the storage accesses declared by the function in another module which the supplied `fn_call` resolves to,
+    /// or `None` if the called function is not known to leave storage untouched, i.e. a `std` function or an enum variant.
+    fn resolve_external_fn_call(&self, project: &Project, path: &Path, fn_call: &FnCall) -> Option<StorageAccess> {
+        let unresolved = StorageAccess { reads: false, writes: false, is_unresolved: true };
+
+        let call_path = match fn_call {
+            FnCall::Path(call_path) => call_path,
+
+            // Methods declared in other modules can not be resolved without knowing the type of their receiver
+            FnCall::Method(name) => return is_external_impl_fn_name(project, path, name).then_some(unresolved),
+        };
+
+        let full_path = self.imports.resolve_path(call_path);
+
+        if utils::is_std_path(&full_path) {
+            return None;
+        }
+
+        let modules = project.modules();
+        let modules = modules.borrow();
+
+        if let Some((_, attributes, _)) = utils::resolve_module_fn(path, call_path, &self.imports, &modules, project.packages()) {
+            return Some(StorageAccess {
+                reads: utils::check_attribute_decls(attributes, "storage", &["read"]),
+                writes: utils::check_attribute_decls(attributes, "storage", &["write"]),
+                is_unresolved: false,
+            });
+        }
+
+        let first = call_path[0].as_str();
+        let is_imported = self.imports.imports.contains_key(first);
+
+        match call_path.as_slice() {
+            // Unqualified names which are not declared or imported are part of the prelude
+            [_] if !is_imported => None,
+
+            // Associated functions of primitive types and of types which are not imported, e.g. enum variants, do not access storage
+            [_, _] if PRIMITIVE_TYPE_NAMES.contains(&first) => None,
+            [_, _] if first.starts_with(|c: char| c.is_ascii_uppercase()) && !is_imported => None,
+
+            _ => Some(unresolved),
+        }
+    }
+
+    /// Computes the storage accesses of each function, including the storage accesses of the functions it calls.
+    fn resolve_storage_accesses(&self, project: &Project, path: &Path) -> Vec<StorageAccess> {
+        let mut accesses = self.fn_states.iter()
+            .map(|x| StorageAccess {
+                reads: x.reads_storage,
+                writes: x.writes_storage,
+                is_unresolved: x.has_unresolved_access,
+            })
+            .collect::<Vec<_>>();
+
+        // Resolve the functions called by each function, using the declared storage accesses of functions in other modules
+        let mut called_indices = vec![];
+
+        for (fn_state, access) in self.fn_states.iter().zip(accesses.iter_mut()) {
+            let mut indices = vec![];
+
+            for fn_call in fn_state.fn_calls.iter() {
+                let local_indices = self.resolve_local_fn_call(fn_call);
+
+                if !local_indices.is_empty() {
+                    indices.extend(local_indices);
+                    continue;
+                }
+
+                if let Some(called) = self.resolve_external_fn_call(project, path, fn_call) {
+                    access.reads |= called.reads;
+                    access.writes |= called.writes;
+                    access.is_unresolved |= called.is_unresolved;
+                }
+            }
+
+            called_indices.push(indices);
+        }
+
+        loop {
+            let mut changed = false;
+
+            for (index, indices) in called_indices.iter().enumerate() {
+                for &called_index in indices.iter() {
+                    let called = accesses[called_index];
+                    let access = &mut accesses[index];
+
+                    if (called.reads && !access.reads) || (called.writes && !access.writes) || (called.is_unresolved && !access.is_unresolved) {
+                        access.reads |= called.reads;
+                        access.writes |= called.writes;
+                        access.is_unresolved |= called.is_unresolved;
+                        changed = true;
+                    }
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        accesses
+    }
+}
+
+impl AstVisitor for StorageAttributeConsistencyVisitor {
+    fn is_cross_module(&self) -> bool {
+        true
+    }
+
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Create the module state
+        if !self.module_states.contains_key(context.path) {
+            self.module_states.insert(context.path.into(), ModuleState::default());
+        }
+
+        Ok(())
+    }
+
+    fn visit_use(&mut self, context: &UseContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Check the use tree for `std::storage::storage_api::{read, write, clear}`
+        module_state.storage_read_fns.visit_use(context.item_use);
+        module_state.storage_write_fns.visit_use(context.item_use);
+
+        // Store the imported names, since calls to them are resolved in other modules
+        module_state.imports.visit_use(context.item_use);
+
+        Ok(())
+    }
+
+    fn visit_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Get the name of the abi implemented by the function, if any
+        let abi_name = context.item_impl
+            .and_then(|item_impl| item_impl.trait_opt.as_ref())
+            .map(|(path_type, _)| {
+                let segment = path_type.suffix.last().map(|(_, x)| x).unwrap_or(&path_type.prefix);
+                segment.name.as_str().to_string()
+            });
+
+        // Get the name of the type the function is associated with, if any
+        let impl_type = context.item_impl
+            .filter(|item_impl| item_impl.trait_opt.is_none())
+            .map(|item_impl| item_impl.ty.span().as_str().to_string());
+
+        // Functions which take storage keys as parameters access storage through them on behalf of their callers
+        let args = match &context.item_fn.fn_signature.arguments.inner {
+            FnArgs::Static(args) => Some(args),
+            FnArgs::NonStatic { args_opt, .. } => args_opt.as_ref().map(|(_, args)| args),
+        };
+
+        let has_storage_key_param = args
+            .map(|args| utils::fold_punctuated(args).iter().any(|arg| utils::ty_to_string(&arg.ty).starts_with("StorageKey")))
+            .unwrap_or(false);
+
+        // Create the function state
+        module_state.fn_states.push(FnState {
+            name: context.item_fn.fn_signature.name.as_str().to_string(),
+            location: utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
+            signature_span: context.item_fn.fn_signature.span(),
+            abi_name,
+            impl_type,
+            declares_read: utils::check_attribute_decls(context.fn_attributes, "storage", &["read"]),
+            declares_write: utils::check_attribute_decls(context.fn_attributes, "storage", &["write"]),
+            reads_storage: false,
+            writes_storage: false,
+            has_unresolved_access: has_storage_key_param,
+            storage_bindings: Default::default(),
+            fn_calls: vec![],
+        });
+
+        Ok(())
+    }
+
+    fn visit_statement(&mut self, context: &StatementContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Get the function state
+        let fn_signature = context.item_fn.fn_signature.span();
+        let Some(fn_state) = module_state.fn_states.iter_mut().rev().find(|x| x.signature_span == fn_signature) else { return Ok(()) };
+
+        // Check for variables bound to storage keys, e.g. `let x = storage.y.get(z);`
+        fn_state.storage_bindings.visit_statement(&context.blocks, context.statement);
+
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Get the function state
+        let Some(item_fn) = context.item_fn.as_ref() else { return Ok(()) };
+        let fn_signature = item_fn.fn_signature.span();
+        let Some(fn_state) = module_state.fn_states.iter_mut().rev().find(|x| x.signature_span == fn_signature) else { return Ok(()) };
+
+        // Check for storage keys passed to other functions, which can read or write them on behalf of the function
+        let args = match context.expr {
+            Expr::FuncApp { args, .. } | Expr::MethodCall { args, .. } => utils::fold_punctuated(&args.inner),
+            _ => vec![],
+        };
+
+        if args.iter().any(|x| utils::get_storage_key_idents(x).is_some()) {
+            fn_state.has_unresolved_access = true;
+        }
+
+        // Check for calls to `std::storage::storage_api::{read, write, clear}`
+        if module_state.storage_read_fns.is_call(context.expr) {
+            fn_state.reads_storage = true;
+            return Ok(());
+        }
+
+        if module_state.storage_write_fns.is_call(context.expr) {
+            fn_state.writes_storage = true;
+            return Ok(());
+        }
+
+        // Store the called functions, since they can access storage on behalf of the function
+        let fn_call = match context.expr {
+            Expr::FuncApp { func, .. } => {
+                let Expr::Path(path) = func.as_ref() else { return Ok(()) };
+
+                // Calls through `Self` are calls to the functions associated with the type of the impl
+                let mut call_path = utils::fold_path_idents(path).iter().map(|x| x.as_str().to_string()).collect::<Vec<_>>();
+
+                if call_path.len() == 2 && call_path[0] == "Self" {
+                    if let Some(impl_type) = fn_state.impl_type.as_ref() {
+                        call_path[0] = impl_type.clone();
+                    }
+                }
+
+                Some(FnCall::Path(call_path))
+            }
+
+            // Method calls on storage keys are part of the storage api
+            Expr::MethodCall { path_seg, .. } if utils::fold_expr_idents(context.expr).first().map(|x| x.as_str() != "storage").unwrap_or(true) => {
+                Some(FnCall::Method(path_seg.name.as_str().to_string()))
+            }
+
+            _ => None,
+        };
+
+        if let Some(fn_call) = fn_call {
+            let is_fn_app = matches!(fn_call, FnCall::Path(_));
+
+            if !fn_state.fn_calls.contains(&fn_call) {
+                fn_state.fn_calls.push(fn_call);
+            }
+
+            if is_fn_app {
+                return Ok(());
+            }
+        }
+
+        // Check for storage writes, either directly or through variables bound to storage keys
+        if fn_state.storage_bindings.get_storage_write_idents(&context.blocks, context.expr).is_some() {
+            fn_state.writes_storage = true;
+            return Ok(());
+        }
+
+        // Check for storage reads
+        if let Some(expr) = utils::find_storage_access_in_expr(context.expr) {
+            if fn_state.storage_bindings.get_storage_write_idents(&context.blocks, expr).is_none() {
+                fn_state.reads_storage = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn leave_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get(context.path).unwrap();
+        let accesses = module_state.resolve_storage_accesses(project, context.path);
+
+        for (fn_state, access) in module_state.fn_states.iter().zip(accesses) {
+            let line = project.span_to_line(context.path, &fn_state.signature_span)?;
+            let declared = storage_attribute_to_string(fn_state.declares_read, fn_state.declares_write);
+
+            // Check for abi declarations whose storage attributes differ from the implementation
+            if let Some(abi_name) = fn_state.abi_name.as_ref() {
+                if let Some((abi_read, abi_write)) = get_abi_fn_storage_access(project, abi_name, &fn_state.name) {
+                    if (abi_read, abi_write) != (fn_state.declares_read, fn_state.declares_write) {
+                        project.report.borrow_mut().add_entry(
+                            context.path,
+                            line,
+                            Severity::Low,
+                            format!(
+                                "{} is declared with {declared}, but the `{abi_name}` abi declares it with {}. Consider keeping the storage attributes of the abi and its implementation in sync.",
+                                fn_state.location,
+                                storage_attribute_to_string(abi_read, abi_write),
+                            ),
+                        );
+                    }
+                }
+            }
+
+            // Skip functions whose storage accesses are unknown
+            if access.is_unresolved {
+                continue;
+            }
+
+            // Check for functions which could be pure
+            if (fn_state.declares_read || fn_state.declares_write) && !access.reads && !access.writes {
+                project.report.borrow_mut().add_entry(
+                    context.path,
+                    line,
+                    Severity::Low,
+                    format!(
+                        "{} is declared with {declared}, but never accesses storage. Consider removing the attribute to make the function pure.",
+                        fn_state.location,
+                    ),
+                );
+
+                continue;
+            }
+
+            // Check for functions which declare `write` without writing to storage
+            if fn_state.declares_write && !access.writes {
+                project.report.borrow_mut().add_entry(
+                    context.path,
+                    line,
+                    Severity::Low,
+                    format!(
+                        "{} is declared with {declared}, but never writes to storage. Consider removing `write` from the attribute to follow least privilege and reduce gas usage.",
+                        fn_state.location,
+                    ),
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_storage_attribute_consistency() {
+        crate::tests::test_detector("storage_attribute_consistency", 3);
+    }
+}
@@ -4,11 +4,10 @@ use crate::{
     report::Severity,
     scope::AstScope,
     utils,
-    visitor::{AstVisitor, BlockContext, ExprContext, FnContext, ModuleContext, StatementContext},
+    visitor::{AstVisitor, ExprContext, FnContext, ModuleContext, StatementContext},
 };
 use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
-use sway_ast::{Expr, Pattern, Statement, StatementLet};
-use sway_types::{BaseIdent, Span, Spanned};
+use sway_types::{Span, Spanned};
 
 #[derive(Default)]
 pub struct StorageFieldMutabilityVisitor {
@@ -28,17 +27,7 @@ struct StorageFieldState {
 
 #[derive(Default)]
 struct FnState {
-    block_states: HashMap<Span, BlockState>,
-}
-
-#[derive(Default)]
-struct BlockState {
-    storage_bindings: Vec<StorageBinding>,
-}
-
-struct StorageBinding {
-    variable_name: BaseIdent,
-    storage_name: BaseIdent,
+    storage_bindings: utils::StorageBindingState,
 }
 
 impl AstVisitor for StorageFieldMutabilityVisitor {
@@ -88,22 +77,6 @@ impl AstVisitor for StorageFieldMutabilityVisitor {
         Ok(())
     }
 
-    fn visit_block(&mut self, context: &BlockContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
-        // Get the module state
-        let module_state = self.module_states.get_mut(context.path).unwrap();
-
-        // Get the function state
-        let fn_signature = context.item_fn.fn_signature.span();
-        let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
-
-        // Create the block state
-        let block_span = context.block.span();
-
-        fn_state.block_states.entry(block_span).or_default();
-        
-        Ok(())
-    }
-
     fn visit_statement(&mut self, context: &StatementContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
         // Get the module state
         let module_state = self.module_states.get_mut(context.path).unwrap();
@@ -112,31 +85,8 @@ impl AstVisitor for StorageFieldMutabilityVisitor {
         let fn_signature = context.item_fn.fn_signature.span();
         let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
 
-        // Get the block state
-        let block_span = context.blocks.last().unwrap();
-        let block_state = fn_state.block_states.get_mut(block_span).unwrap();
-
         // Check for storage binding variable declarations
-        let Statement::Let(StatementLet {
-            pattern: Pattern::AmbiguousSingleIdent(variable_name),
-            expr,
-            ..
-        }) = context.statement else { return Ok(()) };
-
-        let idents = utils::fold_expr_idents(expr);
-
-        if idents.len() < 3 {
-            return Ok(());
-        }
-
-        let "storage" = idents[0].as_str() else { return Ok(()) };
-        let ("get" | "read") = idents.last().unwrap().as_str() else { return Ok(()) };
-
-        // Create the storage binding
-        block_state.storage_bindings.push(StorageBinding {
-            variable_name: variable_name.clone(),
-            storage_name: idents[1].clone(),
-        });
+        fn_state.storage_bindings.visit_statement(&context.blocks, context.statement);
 
         Ok(())
     }
@@ -148,36 +98,15 @@ impl AstVisitor for StorageFieldMutabilityVisitor {
         // Get the function state
         let Some(item_fn) = context.item_fn else { return Ok(()) };
         let fn_signature = item_fn.fn_signature.span();
-        let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
-
-        // Only check method call expressions
-        let Expr::MethodCall { .. } = context.expr else { return Ok(()) };
+        let fn_state = module_state.fn_states.get(&fn_signature).unwrap();
 
-        // Get the expression's identifiers
-        let idents = utils::fold_expr_idents(context.expr);
+        // Check for direct or indirect storage writes, e.g. `storage.x.write(y)` or `storage.x.insert(y)`
+        let Some(storage_idents) = fn_state.storage_bindings.get_storage_write_idents(&context.blocks, context.expr) else { return Ok(()) };
 
-        // Check for `storage.x.write(y)` or `storage.x.insert(y)`
-        let ("write" | "insert") = idents.last().unwrap().as_str() else { return Ok(()) };
-
-        // Check the expression for direct storage access
-        if idents[0].as_str() == "storage" {
-            // Mark the storage field as mutated
-            let Some((_, state)) = module_state.storage_field_states.iter_mut().find(|(k, _)| k.as_str() == idents[1].as_str()) else { return Ok(()) };
+        // Mark the storage field as mutated
+        if let Some((_, state)) = module_state.storage_field_states.iter_mut().find(|(k, _)| k.as_str() == storage_idents[0].as_str()) {
             state.mutated = true;
         }
-        // Check the expression for indirect storage access
-        else {
-            for block_span in context.blocks.iter().rev() {
-                let block_state = fn_state.block_states.get(block_span).unwrap();
-
-                if let Some(storage_binding) = block_state.storage_bindings.iter().rev().find(|x| x.variable_name.as_str() == idents[0].as_str()) {
-                    if let Some((_, storage_field_state)) = module_state.storage_field_states.iter_mut().find(|(k, _)| k.as_str() == storage_binding.storage_name.as_str()) {
-                        storage_field_state.mutated = true;
-                        return Ok(());
-                    }
-                }
-            }
-        }
 
         Ok(())
     }
@@ -0,0 +1,402 @@
+use crate::{
+    error::Error,
+    project::Project,
+    report::Severity,
+    scope::AstScope,
+    utils,
+    visitor::{AstVisitor, ExprContext, ModuleContext, StorageFieldContext, UseContext},
+};
+use sha2::{Digest, Sha256};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+use sway_ast::{Expr, ItemKind, Literal, Module, StorageEntry, StorageField};
+use sway_types::{Span, Spanned};
+
+#[derive(Default)]
+pub struct StorageSlotCollisionVisitor {
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+#[derive(Default)]
+struct ModuleState {
+    constants: HashMap<String, Expr>,
+    storage_api_names: Vec<String>,
+    storage_slots: Vec<StorageSlot>,
+    manual_slots: Vec<ManualSlot>,
+}
+
+impl ModuleState {
+    /// Collects the module-level constants of the supplied `module`, so they can be used to resolve storage slots.
+    fn collect_constants(&mut self, module: &Module) {
+        for item in module.items.iter() {
+            let ItemKind::Const(item_const) = &item.value else { continue };
+            let Some(expr) = item_const.expr_opt.as_ref() else { continue };
+            self.constants.insert(item_const.name.as_str().to_string(), expr.clone());
+        }
+    }
+
+    /// Gets the storage slot of the supplied storage `field` in the supplied `namespace`, either from its `in` key or the one assigned by the compiler.
+    fn storage_field_slot(&self, namespace: &[String], field: &StorageField) -> StorageSlot {
+        let namespace = namespace.join("::");
+
+        let name = if namespace.is_empty() {
+            field.name.as_str().to_string()
+        } else {
+            format!("{namespace}::{}", field.name.as_str())
+        };
+
+        let key = match field.key_expr.as_ref() {
+            Some(key_expr) => self.expr_to_slot_key(key_expr),
+
+            None if namespace.is_empty() => {
+                SlotKey::Value(Sha256::digest(format!("storage.{}", field.name.as_str()).as_bytes()).into())
+            }
+
+            None => {
+                SlotKey::Value(Sha256::digest(format!("storage::{namespace}.{}", field.name.as_str()).as_bytes()).into())
+            }
+        };
+
+        StorageSlot {
+            name,
+            span: field.name.span(),
+            key,
+        }
+    }
+
+    /// Gets the storage slots of the storage fields declared in the supplied `module`, including the fields of storage namespaces.
+    fn module_storage_slots(&self, module: &Module) -> Vec<StorageSlot> {
+        fn collect_entry_slots(module_state: &ModuleState, entry: &StorageEntry, namespace: &mut Vec<String>, slots: &mut Vec<StorageSlot>) {
+            if let Some(field) = entry.field.as_ref() {
+                slots.push(module_state.storage_field_slot(namespace, field));
+            }
+
+            if let Some(entries) = entry.namespace.as_ref() {
+                namespace.push(entry.name.as_str().to_string());
+
+                for entry in utils::fold_punctuated(&entries.inner) {
+                    collect_entry_slots(module_state, entry.value.as_ref(), namespace, slots);
+                }
+
+                namespace.pop();
+            }
+        }
+
+        let mut slots = vec![];
+
+        for item in module.items.iter() {
+            let ItemKind::Storage(item_storage) = &item.value else { continue };
+
+            for entry in utils::fold_punctuated(&item_storage.entries.inner) {
+                collect_entry_slots(self, &entry.value, &mut vec![], &mut slots);
+            }
+        }
+
+        slots
+    }
+
+    /// Attempts to evaluate the storage slot referred to by the supplied `expr`.
+    fn expr_to_slot_key(&self, expr: &Expr) -> SlotKey {
+        match expr {
+            Expr::Literal(Literal::Int(value)) => {
+                if let Some(slot) = parse_b256_literal(value.span.as_str()) {
+                    return SlotKey::Value(slot);
+                }
+            }
+
+            Expr::Parens(parens) => return self.expr_to_slot_key(parens.inner.as_ref()),
+
+            Expr::Path(path) if path.root_opt.is_none() && path.suffix.is_empty() => {
+                if let Some(expr) = self.constants.get(path.prefix.name.as_str()) {
+                    return self.expr_to_slot_key(expr);
+                }
+            }
+
+            Expr::FuncApp { func, args } => {
+                let Expr::Path(path) = func.as_ref() else { return SlotKey::Expr(expr.span().as_str().to_string()) };
+                let args = utils::fold_punctuated(&args.inner);
+
+                // Check for `sha256("...")`, which is how the compiler assigns storage slots
+                if utils::fold_path_idents(path).last().map(|x| x.as_str() == "sha256").unwrap_or(false) && args.len() == 1 {
+                    if let Expr::Literal(Literal::String(value)) = args[0] {
+                        return SlotKey::Value(Sha256::digest(value.parsed.as_bytes()).into());
+                    }
+                }
+            }
+
+            _ => {}
+        }
+
+        SlotKey::Expr(expr.span().as_str().to_string())
+    }
+
+    /// Checks if the supplied `expr` is a raw storage access, returning the expression used as its storage slot.
+    fn expr_to_manual_slot<'a>(&self, expr: &'a Expr) -> Option<&'a Expr> {
+        let Expr::FuncApp { func, args } = expr else { return None };
+        let Expr::Path(path) = func.as_ref() else { return None };
+
+        let idents = utils::fold_path_idents(path);
+        let idents = idents.iter().map(|x| x.as_str()).collect::<Vec<_>>();
+
+        let is_manual_access = match idents.as_slice() {
+            // Check for `StorageKey::new(slot, offset, field_id)`
+            [.., "StorageKey", "new"] => true,
+
+            // Check for `std::storage::storage_api::{read, write, clear}`
+            [.., "storage_api", "read" | "write" | "clear"] => true,
+
+            // Check for imported `read`, `write` and `clear` functions
+            [name] => self.storage_api_names.iter().any(|x| x == name),
+
+            _ => false,
+        };
+
+        if !is_manual_access {
+            return None;
+        }
+
+        utils::fold_punctuated(&args.inner).first().copied()
+    }
+}
+
+struct StorageSlot {
+    name: String,
+    span: Span,
+    key: SlotKey,
+}
+
+struct ManualSlot {
+    location: String,
+    span: Span,
+    key: SlotKey,
+}
+
+#[derive(PartialEq)]
+enum SlotKey {
+    Value([u8; 32]),
+    Expr(String),
+}
+
+fn parse_b256_literal(s: &str) -> Option<[u8; 32]> {
+    let digits = s.strip_prefix("0x")?.replace('_', "");
+
+    if digits.len() != 64 {
+        return None;
+    }
+
+    let mut result = [0u8; 32];
+
+    for (i, byte) in result.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&digits[i * 2..i * 2 + 2], 16).ok()?;
+    }
+
+    Some(result)
+}
+
+/// Gets the other modules whose storage fields share storage slots with the code of the module at the supplied `path`.
+///
+/// The code of a library module runs in the storage of the contracts of its linked packages, and the storage of an SRC-14 proxy
+/// contract is shared with the implementation contracts of its linked packages.
+fn get_shared_storage_modules<'a>(project: &Project, path: &Path, module: &Module, modules: &'a HashMap<PathBuf, Module>) -> Vec<(&'a PathBuf, &'a Module)> {
+    let packages = project.packages();
+    let Some(package) = packages.package_for_path(path) else { return vec![] };
+
+    let linked_roots = packages.get_linked_packages(package).into_iter().map(|x| x.root.clone()).collect::<Vec<_>>();
+    let is_proxy = utils::is_src14_proxy_module(module);
+
+    modules.iter()
+        .filter(|(other_path, other_module)| {
+            if other_path.as_path() == path || !utils::is_contract_module(other_module) {
+                return false;
+            }
+
+            let Some(other_package) = packages.package_for_path(other_path) else { return false };
+
+            if !linked_roots.contains(&other_package.root) {
+                return false;
+            }
+
+            // Library code is compiled into the contracts which use it
+            if !utils::is_contract_module(module) {
+                return true;
+            }
+
+            // Only a proxy and its implementation share storage across contracts
+            other_package.root != package.root && is_proxy != utils::is_src14_proxy_module(other_module)
+        })
+        .collect()
+}
+
+impl AstVisitor for StorageSlotCollisionVisitor {
+    // Slots are compared with the storage of libraries, proxies and implementations in linked packages
+    fn is_cross_module(&self) -> bool {
+        true
+    }
+
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Create the module state
+        let module_state = self.module_states.entry(context.path.into()).or_default();
+
+        // Collect module-level constants ahead of time so they can be used to resolve storage slots
+        module_state.collect_constants(context.module);
+
+        Ok(())
+    }
+
+    fn visit_use(&mut self, context: &UseContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Check the use tree for `std::storage::storage_api::{read, write, clear}`
+        for fn_name in ["read", "write", "clear"] {
+            if let Some(name) = utils::use_tree_to_name(&context.item_use.tree, format!("std::storage::storage_api::{fn_name}").as_str()) {
+                module_state.storage_api_names.push(name);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_storage_field(&mut self, context: &StorageFieldContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Get the storage slot of the field
+        let namespace = context.namespace.iter().map(|x| x.as_str().to_string()).collect::<Vec<_>>();
+        let storage_slot = module_state.storage_field_slot(&namespace, context.field);
+
+        // Check if the storage slot is already in use by a previous storage field
+        if let Some(previous_slot) = module_state.storage_slots.iter().find(|x| x.key == storage_slot.key) {
+            project.report.borrow_mut().add_entry(
+                context.path,
+                project.span_to_line(context.path, &context.field.name.span())?,
+                Severity::High,
+                format!(
+                    "The `{}` storage field can alias the same storage slot as the `{}` storage field.",
+                    storage_slot.name,
+                    previous_slot.name,
+                ),
+            );
+        }
+
+        module_state.storage_slots.push(storage_slot);
+
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Check for raw storage access using a manual storage slot
+        let Some(slot_expr) = module_state.expr_to_manual_slot(context.expr) else { return Ok(()) };
+        let key = module_state.expr_to_slot_key(slot_expr);
+
+        module_state.manual_slots.push(ManualSlot {
+            location: utils::get_item_location(context.item, &context.item_impl, &context.item_fn),
+            span: context.expr.span(),
+            key,
+        });
+
+        Ok(())
+    }
+
+    fn leave_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get(context.path).unwrap();
+
+        // Get the storage slots of the other modules whose storage is shared with the module
+        let shared_slots = {
+            let modules = project.modules();
+            let modules = modules.borrow();
+
+            get_shared_storage_modules(project, context.path, context.module, &modules).into_iter()
+                .flat_map(|(path, module)| {
+                    let mut other_state = ModuleState::default();
+                    other_state.collect_constants(module);
+                    other_state.module_storage_slots(module).into_iter().map(move |x| (path.clone(), x)).collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Check if any storage fields overlap with the slots of storage fields declared by a linked proxy or implementation contract
+        for storage_slot in module_state.storage_slots.iter() {
+            for (other_path, other_slot) in shared_slots.iter().filter(|(_, x)| x.key == storage_slot.key) {
+                project.report.borrow_mut().add_entry(
+                    context.path,
+                    project.span_to_line(context.path, &storage_slot.span)?,
+                    Severity::High,
+                    format!(
+                        "The `{}` storage field can alias the same storage slot as the `{}` storage field in `{}`.",
+                        storage_slot.name,
+                        other_slot.name,
+                        other_path.to_string_lossy(),
+                    ),
+                );
+            }
+        }
+
+        // Check if any manual storage slots overlap with the slots of declared storage fields
+        for manual_slot in module_state.manual_slots.iter() {
+            let storage_names = module_state.storage_slots.iter()
+                .filter(|x| x.key == manual_slot.key)
+                .map(|x| format!("`{}`", x.name))
+                .chain(
+                    shared_slots.iter()
+                        .filter(|(_, x)| x.key == manual_slot.key)
+                        .map(|(path, x)| format!("`{}` (in `{}`)", x.name, path.to_string_lossy()))
+                )
+                .collect::<Vec<_>>();
+
+            if storage_names.is_empty() {
+                continue;
+            }
+
+            project.report.borrow_mut().add_entry(
+                context.path,
+                project.span_to_line(context.path, &manual_slot.span)?,
+                Severity::High,
+                format!(
+                    "{} uses a manual storage slot that overlaps with the slot of the {} storage {}: `{}`",
+                    manual_slot.location,
+                    storage_names.join(", "),
+                    if storage_names.len() == 1 { "field" } else { "fields" },
+                    manual_slot.span.as_str(),
+                ),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{project::Project, Options};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_storage_slot_collision() {
+        crate::tests::test_detector("storage_slot_collision", 6);
+    }
+
+    #[test]
+    fn test_storage_slot_collision_proxy() {
+        // The storage of a proxy is shared with the implementation contracts of its linked packages
+        let options = Options {
+            directory: Some(PathBuf::from("test/storage_slot_collision_proxy")),
+            detectors: vec!["storage_slot_collision".to_string()],
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        let report = project.report.borrow();
+        assert_eq!(report.entries.iter().map(|(_, x)| x.len()).sum::<usize>(), 2);
+    }
+}
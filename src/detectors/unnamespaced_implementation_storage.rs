@@ -0,0 +1,71 @@
+use crate::{
+    error::Error,
+    project::Project,
+    report::Severity,
+    scope::AstScope,
+    utils,
+    visitor::{AstVisitor, ModuleContext, StorageFieldContext},
+};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
+use sway_types::Spanned;
+
+#[derive(Default)]
+pub struct UnnamespacedImplementationStorageVisitor {
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+#[derive(Default)]
+struct ModuleState {
+    is_implementation: bool,
+}
+
+impl AstVisitor for UnnamespacedImplementationStorageVisitor {
+    fn is_cross_module(&self) -> bool {
+        true
+    }
+
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Create the module state
+        let module_state = self.module_states.entry(context.path.into()).or_default();
+
+        // Check if the module is the implementation of a proxy contract in a linked package
+        let modules = project.modules();
+        module_state.is_implementation = utils::is_proxy_implementation_module(context.path, context.module, &modules.borrow(), project.packages());
+
+        Ok(())
+    }
+
+    fn visit_storage_field(&mut self, context: &StorageFieldContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get(context.path).unwrap();
+
+        if !module_state.is_implementation {
+            return Ok(());
+        }
+
+        // Only check storage fields that are not namespaced and do not supply an explicit `in` key
+        if !context.namespace.is_empty() || context.field.key_expr.is_some() {
+            return Ok(());
+        }
+
+        project.report.borrow_mut().add_entry(
+            context.path,
+            project.span_to_line(context.path, &context.field.name.span())?,
+            Severity::Medium,
+            format!(
+                "The `{}` storage field is declared without a namespace in a proxy implementation contract, which can collide with the storage of the proxy contract. Consider declaring it inside a storage namespace.",
+                context.field.name.as_str(),
+            ),
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_unnamespaced_implementation_storage() {
+        crate::tests::test_detector("unnamespaced_implementation_storage", 2);
+    }
+}
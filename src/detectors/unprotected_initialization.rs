@@ -4,7 +4,7 @@ use crate::{
     report::Severity,
     scope::AstScope,
     utils,
-    visitor::{AstVisitor, ExprContext, FnContext, ModuleContext},
+    visitor::{AstVisitor, ExprContext, FnContext, ModuleContext, UseContext},
 };
 use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
 use sway_types::{Span, Spanned};
@@ -16,103 +16,77 @@ pub struct UnprotectedInitializationVisitor {
 
 #[derive(Default)]
 struct ModuleState {
-    fn_states: HashMap<String, FnState>,
+    is_implementation: bool,
+    access_checks: utils::AccessCheckState,
+    init_fn_states: Vec<FnState>,
 }
 
-#[derive(Clone)]
 struct FnState {
-    is_init_fn: bool,
-    has_requirement: bool,
-    finalized: bool,
     location: String,
     span: Span,
-    function_calls: Vec<String>,
 }
 
 impl AstVisitor for UnprotectedInitializationVisitor {
-    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+    fn is_cross_module(&self) -> bool {
+        true
+    }
+
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
         // Create the module state
-        if !self.module_states.contains_key(context.path) {
-            self.module_states.insert(context.path.into(), ModuleState::default());
-        }
+        let module_state = self.module_states.entry(context.path.into()).or_default();
+
+        // Check if the module is the implementation of a proxy contract in a linked package
+        let modules = project.modules();
+        module_state.is_implementation = utils::is_proxy_implementation_module(context.path, context.module, &modules.borrow(), project.packages());
 
         Ok(())
     }
 
-    fn visit_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+    fn visit_use(&mut self, context: &UseContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
         // Get the module state
         let module_state = self.module_states.get_mut(context.path).unwrap();
 
-        // Create the function state
-        let fn_signature = context.item_fn.fn_signature.name.to_string();
-
-        module_state.fn_states.entry(fn_signature).or_insert_with(|| FnState {
-            is_init_fn: context.item_fn.fn_signature.name.as_str().contains("init"),
-            has_requirement: false,
-            finalized: false,
-            location: utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
-            span: context.item_fn.fn_signature.span(),
-            function_calls: vec![],
-        });
+        // Check the use tree for `std::auth::msg_sender`
+        module_state.access_checks.visit_use(context.item_use);
 
         Ok(())
     }
 
-    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+    fn visit_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
         // Get the module state
         let module_state = self.module_states.get_mut(context.path).unwrap();
 
-        // Get the function state
-        let Some(item_fn) = context.item_fn.as_ref() else { return Ok(()); };
-        let fn_signature = item_fn.fn_signature.name.to_string();
-        let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
-
-        // Check for `require` and update the function state
-        if utils::get_require_args(context.expr).is_some() || utils::get_if_revert_condition(context.expr).is_some() {
-            fn_state.has_requirement = true;
-        }
-
-        // Check for function calls
-        if let sway_ast::Expr::FuncApp { func, .. } = context.expr {
-            if let sway_ast::Expr::Path(_) = func.as_ref() {
-                let func = func.span().as_str().to_string();
-                if !fn_state.function_calls.contains(&func) {
-                    fn_state.function_calls.push(func);
-                }
-            }
+        // Create the function state
+        module_state.access_checks.visit_fn(context.item_impl, context.item_fn);
+
+        // Keep track of initializer functions
+        if context.item_fn.fn_signature.name.as_str().contains("init") {
+            module_state.init_fn_states.push(FnState {
+                location: utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
+                span: context.item_fn.fn_signature.span(),
+            });
         }
 
         Ok(())
     }
 
-    fn leave_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
         // Get the module state
         let module_state = self.module_states.get_mut(context.path).unwrap();
 
-        fn finalize_fn_state(mut fn_states: HashMap<String, FnState>, fn_state: &mut FnState) {
-            if fn_state.finalized {
-                return;
-            }
+        // Check for requirements, access restrictions and function calls
+        let Some(item_fn) = context.item_fn.as_ref() else { return Ok(()) };
+        module_state.access_checks.visit_expr(item_fn, context.expr);
 
-            for function_call in fn_state.function_calls.iter() {
-                let Some((_, mut called_fn_state)) = fn_states.iter_mut().find(|f| f.0.as_str() == function_call).map(|(a, b)| (a.clone(), b.clone())) else { continue };
-                if !called_fn_state.finalized {
-                    finalize_fn_state(fn_states.clone(), &mut called_fn_state);
-                }
-
-                if called_fn_state.has_requirement {
-                    fn_state.has_requirement = true;
-                    break;
-                }
-            }
-        }
+        Ok(())
+    }
 
-        let temp_fn_states = module_state.fn_states.clone();
+    fn leave_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get(context.path).unwrap();
 
-        for (_, fn_state) in module_state.fn_states.iter_mut() {
-            finalize_fn_state(temp_fn_states.clone(), fn_state);
-            
-            if fn_state.is_init_fn && !fn_state.has_requirement {
+        for fn_state in module_state.init_fn_states.iter() {
+            if !module_state.access_checks.fn_has_requirement(&fn_state.span) {
                 project.report.borrow_mut().add_entry(
                     context.path,
                     project.span_to_line(context.path, &fn_state.span)?,
@@ -122,8 +96,18 @@ impl AstVisitor for UnprotectedInitializationVisitor {
                         fn_state.location,
                     ),
                 );
+            } else if module_state.is_implementation && !module_state.access_checks.fn_has_access_check(&fn_state.span) {
+                project.report.borrow_mut().add_entry(
+                    context.path,
+                    project.span_to_line(context.path, &fn_state.span)?,
+                    Severity::High,
+                    format!(
+                        "{} is an initializer function in a proxy implementation contract that can be called on the implementation directly. Consider checking against `msg_sender()` in order to limit access.",
+                        fn_state.location,
+                    ),
+                );
             }
-        }        
+        }
 
         Ok(())
     }
@@ -133,6 +117,6 @@ impl AstVisitor for UnprotectedInitializationVisitor {
 mod tests {
     #[test]
     fn test_unprotected_initialization() {
-        crate::tests::test_detector("unprotected_initialization", 1);
+        crate::tests::test_detector("unprotected_initialization", 2);
     }
 }
@@ -0,0 +1,107 @@
+use crate::{
+    error::Error,
+    project::Project,
+    report::Severity,
+    scope::AstScope,
+    utils::{self, AccessCheckState},
+    visitor::{AstVisitor, ExprContext, FnContext, ModuleContext, UseContext},
+};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
+use sway_types::{Span, Spanned};
+
+#[derive(Default)]
+pub struct UnprotectedProxyTargetVisitor {
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+#[derive(Default)]
+struct ModuleState {
+    access_checks: AccessCheckState,
+    set_proxy_target_fns: Vec<FnState>,
+}
+
+struct FnState {
+    location: String,
+    span: Span,
+}
+
+impl AstVisitor for UnprotectedProxyTargetVisitor {
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Create the module state
+        if !self.module_states.contains_key(context.path) {
+            self.module_states.insert(context.path.into(), ModuleState::default());
+        }
+
+        Ok(())
+    }
+
+    fn visit_use(&mut self, context: &UseContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Check the use tree for `std::auth::msg_sender`
+        module_state.access_checks.visit_use(context.item_use);
+
+        Ok(())
+    }
+
+    fn visit_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Create the function state
+        module_state.access_checks.visit_fn(context.item_impl, context.item_fn);
+
+        // Only check the SRC-14 `set_proxy_target` function
+        if context.item_fn.fn_signature.name.as_str() == "set_proxy_target" {
+            module_state.set_proxy_target_fns.push(FnState {
+                location: utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
+                span: context.item_fn.fn_signature.span(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Check for access restriction and function calls
+        let Some(item_fn) = context.item_fn.as_ref() else { return Ok(()) };
+        module_state.access_checks.visit_expr(item_fn, context.expr);
+
+        Ok(())
+    }
+
+    fn leave_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get(context.path).unwrap();
+
+        for fn_state in module_state.set_proxy_target_fns.iter() {
+            if module_state.access_checks.fn_has_access_check(&fn_state.span) {
+                continue;
+            }
+
+            project.report.borrow_mut().add_entry(
+                context.path,
+                project.span_to_line(context.path, &fn_state.span)?,
+                Severity::High,
+                format!(
+                    "{} can change the proxy target without access restriction. Consider checking against `msg_sender()` in order to limit access.",
+                    fn_state.location,
+                ),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_unprotected_proxy_target() {
+        crate::tests::test_detector("unprotected_proxy_target", 1);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    config::{DetectorOption, OptionDefault},
     error::Error,
     project::Project,
     report::Severity,
@@ -18,21 +19,23 @@ pub struct UnprotectedStorageVariableVisitor {
     module_states: Rc<RefCell<HashMap<PathBuf, ModuleState>>>,
 }
 
+impl UnprotectedStorageVariableVisitor {
+    pub const OPTIONS: &'static [DetectorOption] = &[
+        DetectorOption {
+            name: "msg_sender_names",
+            description: "The names of the functions which return the sender of the call without being imported.",
+            // Since `std::auth::msg_sender` is part of the prelude, include it here
+            default: OptionDefault::StringList(&["msg_sender"]),
+        },
+    ];
+}
+
+#[derive(Default)]
 struct ModuleState {
     msg_sender_names: Vec<String>,
     fn_states: HashMap<Span, FnState>,
 }
 
-impl Default for ModuleState {
-    fn default() -> Self {
-        Self {
-            // Since `std::auth::msg_sender` is part of the prelude, include it here
-            msg_sender_names: vec!["msg_sender".into()],
-            fn_states: Default::default(),
-        }
-    }
-}
-
 impl ModuleState {
     fn expr_is_msg_sender_call(&mut self, expr: &Expr) -> bool {
         match expr {
@@ -142,12 +145,15 @@ pub struct VarState {
 }
 
 impl AstVisitor for UnprotectedStorageVariableVisitor {
-    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
         // Create the module state
         let mut module_states = self.module_states.borrow_mut();
         
         if !module_states.contains_key(context.path) {
-            module_states.insert(context.path.into(), ModuleState::default());
+            module_states.insert(context.path.into(), ModuleState {
+                msg_sender_names: project.detector_options().string_list("unprotected_storage_variable", "msg_sender_names"),
+                ..Default::default()
+            });
         }
 
         Ok(())
@@ -0,0 +1,332 @@
+use crate::{
+    error::Error,
+    project::Project,
+    report::Severity,
+    scope::AstScope,
+    utils,
+    visitor::{AstVisitor, ExprContext, FnContext, ModuleContext, StatementLetContext},
+};
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
+use sway_ast::{Expr, FnArgs, IfCondition};
+use sway_types::{Span, Spanned};
+
+#[derive(Default)]
+pub struct UnsafeMessageHandlingVisitor {
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+#[derive(Default)]
+struct ModuleState {
+    fn_states: HashMap<Span, FnState>,
+}
+
+#[derive(Default)]
+struct FnState {
+    message_read_span: Option<Span>,
+    sender_variables: Vec<String>,
+    nonce_variables: Vec<String>,
+    storage_read_variables: Vec<String>,
+    is_sender_validated: bool,
+    is_nonce_checked: bool,
+    arg_names: Vec<String>,
+    arg_aliases: Vec<(String, String)>, // (variable name, argument name)
+    validated_args: Vec<String>,
+    send_message_calls: Vec<SendMessageCallState>,
+}
+
+struct SendMessageCallState {
+    span: Span,
+    controlled_fields: Vec<(&'static str, String)>, // (field name, argument name)
+}
+
+/// Checks if the supplied `expr` calls the `fn_name` function or references any of the supplied `variables`.
+fn expr_references(expr: &Expr, fn_name: &str, variables: &[String]) -> bool {
+    let mut result = false;
+
+    utils::map_expr(expr, &mut |expr| {
+        if utils::get_fn_call_name(expr).map(|x| x == fn_name).unwrap_or(false) {
+            result = true;
+        }
+
+        if let Expr::Path(_) = expr {
+            if variables.iter().any(|x| x == expr.span().as_str()) {
+                result = true;
+            }
+        }
+    });
+
+    result
+}
+
+/// Checks if the supplied `expr` reads a value from storage, e.g. `storage.x.get(y).try_read()`, or references any of the supplied `variables` bound to one.
+fn expr_reads_storage(expr: &Expr, variables: &[String]) -> bool {
+    let mut result = false;
+
+    utils::map_expr(expr, &mut |expr| {
+        match expr {
+            Expr::MethodCall { path_seg, .. } => {
+                let idents = utils::fold_expr_idents(expr);
+
+                if idents.first().map(|x| x.as_str() == "storage").unwrap_or(false)
+                    && matches!(path_seg.name.as_str(), "read" | "try_read")
+                    && !idents.iter().any(|x| utils::is_storage_write_fn(x.as_str()))
+                {
+                    result = true;
+                }
+            }
+
+            Expr::Path(_) => {
+                if variables.iter().any(|x| x == expr.span().as_str()) {
+                    result = true;
+                }
+            }
+
+            _ => {}
+        }
+    });
+
+    result
+}
+
+impl FnState {
+    /// Gets the names of the function arguments referenced in the supplied `expr`, either directly or through variables bound to them.
+    fn get_referenced_args(&self, expr: &Expr) -> Vec<String> {
+        let mut result = vec![];
+
+        for span in utils::fold_expr_ident_spans(expr) {
+            if let Some(name) = self.arg_names.iter().find(|x| *x == span.as_str()) {
+                result.push(name.clone());
+            }
+
+            for (alias, name) in self.arg_aliases.iter() {
+                if alias == span.as_str() && !result.contains(name) {
+                    result.push(name.clone());
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl AstVisitor for UnsafeMessageHandlingVisitor {
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Only check contracts
+        if !utils::is_contract_module(context.module) {
+            return Ok(());
+        }
+
+        // Create the module state
+        if !self.module_states.contains_key(context.path) {
+            self.module_states.insert(context.path.into(), ModuleState::default());
+        }
+
+        Ok(())
+    }
+
+    fn visit_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Create the function state
+        let fn_signature = context.item_fn.fn_signature.span();
+        let fn_state = module_state.fn_states.entry(fn_signature).or_default();
+
+        // Store the names of the function's arguments, since they are controlled by the caller
+        let args = match &context.item_fn.fn_signature.arguments.inner {
+            FnArgs::Static(args) => args,
+            FnArgs::NonStatic { args_opt: Some(args), .. } => &args.1,
+            _ => return Ok(()),
+        };
+
+        for arg in args {
+            for ident in utils::fold_pattern_idents(&arg.pattern) {
+                fn_state.arg_names.push(ident.as_str().to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_statement_let(&mut self, context: &StatementLetContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Get the function state
+        let fn_signature = context.item_fn.fn_signature.span();
+        let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
+
+        let expr = &context.statement_let.expr;
+        let idents = utils::fold_pattern_idents(&context.statement_let.pattern);
+
+        // Check if the variable is bound to the sender or nonce of a message input
+        if expr_references(expr, "input_message_sender", &fn_state.sender_variables) {
+            fn_state.sender_variables.extend(idents.iter().map(|x| x.as_str().to_string()));
+        }
+
+        if expr_references(expr, "input_message_nonce", &fn_state.nonce_variables) {
+            fn_state.nonce_variables.extend(idents.iter().map(|x| x.as_str().to_string()));
+        }
+
+        // Check if the variable is bound to a value read from storage
+        if expr_reads_storage(expr, &fn_state.storage_read_variables) {
+            fn_state.storage_read_variables.extend(idents.iter().map(|x| x.as_str().to_string()));
+        }
+
+        // Check if the variable is bound to a function argument
+        for name in fn_state.get_referenced_args(expr) {
+            for ident in idents.iter() {
+                fn_state.arg_aliases.push((ident.as_str().to_string(), name.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Get the function state
+        let Some(item_fn) = context.item_fn.as_ref() else { return Ok(()) };
+        let fn_signature = item_fn.fn_signature.span();
+        let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
+
+        // Check for reads of message input data
+        let fn_name = utils::get_fn_call_name(context.expr);
+
+        if let Some("input_message_data" | "input_message_sender") = fn_name.as_deref() {
+            if fn_state.message_read_span.is_none() {
+                fn_state.message_read_span = Some(context.expr.span());
+            }
+        }
+
+        // Check for comparisons which validate the sender of a message input
+        if utils::is_comparison_expr(context.expr) && expr_references(context.expr, "input_message_sender", &fn_state.sender_variables) {
+            fn_state.is_sender_validated = true;
+        }
+
+        // Get the condition of `require`, `assert` or `if ... { revert(...) }` expressions
+        let condition_expr = match fn_name.as_deref() {
+            Some("require" | "assert") => Some(context.expr),
+            _ => match utils::get_if_revert_condition(context.expr) {
+                Some(IfCondition::Expr(condition)) => Some(condition.as_ref()),
+                _ => None,
+            },
+        };
+
+        // Check for comparisons or requirements which check the nonce of a message input against storage,
+        // e.g. `require(storage.processed.get(nonce).try_read().is_none(), ...)`
+        let nonce_check_expr = if utils::is_comparison_expr(context.expr) { Some(context.expr) } else { condition_expr };
+
+        if let Some(nonce_check_expr) = nonce_check_expr {
+            if expr_references(nonce_check_expr, "input_message_nonce", &fn_state.nonce_variables)
+                && expr_reads_storage(nonce_check_expr, &fn_state.storage_read_variables)
+            {
+                fn_state.is_nonce_checked = true;
+            }
+        }
+
+        // Check for `require`, `assert` or `if ... { revert(...) }` expressions that validate function arguments
+        if let Some(condition_expr) = condition_expr {
+            for name in fn_state.get_referenced_args(condition_expr) {
+                if !fn_state.validated_args.contains(&name) {
+                    fn_state.validated_args.push(name);
+                }
+            }
+        }
+
+        // Check for calls to `send_message` with a caller-controlled recipient or amount
+        let Some("send_message") = fn_name.as_deref() else { return Ok(()) };
+        let Expr::FuncApp { args, .. } = context.expr else { return Ok(()) };
+        let args = utils::fold_punctuated(&args.inner);
+
+        let mut controlled_fields = vec![];
+
+        for (index, field_name) in [(0, "recipient"), (2, "amount")] {
+            let Some(arg) = args.get(index) else { continue };
+
+            for name in fn_state.get_referenced_args(arg) {
+                controlled_fields.push((field_name, name));
+            }
+        }
+
+        if !controlled_fields.is_empty() {
+            fn_state.send_message_calls.push(SendMessageCallState {
+                span: context.expr.span(),
+                controlled_fields,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn leave_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get(context.path) else { return Ok(()) };
+
+        // Get the function state
+        let fn_signature = context.item_fn.fn_signature.span();
+        let fn_state = module_state.fn_states.get(&fn_signature).unwrap();
+
+        let item_location = utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn));
+
+        // Check for message inputs which are processed without validating their sender or nonce
+        if let Some(message_read_span) = fn_state.message_read_span.as_ref() {
+            let line = project.span_to_line(context.path, message_read_span)?;
+
+            if !fn_state.is_sender_validated {
+                project.report.borrow_mut().add_entry(
+                    context.path,
+                    line,
+                    Severity::High,
+                    format!(
+                        "{item_location} processes a message input without validating its sender: `{}`. Consider comparing `input_message_sender` against the trusted bridge address.",
+                        message_read_span.as_str(),
+                    ),
+                );
+            }
+
+            if !fn_state.is_nonce_checked {
+                project.report.borrow_mut().add_entry(
+                    context.path,
+                    line,
+                    Severity::High,
+                    format!(
+                        "{item_location} processes a message input without checking its nonce: `{}`. Consider recording processed nonces in storage, otherwise the message can be replayed.",
+                        message_read_span.as_str(),
+                    ),
+                );
+            }
+        }
+
+        // Check for calls to `send_message` whose recipient or amount is controlled by the caller
+        for call_state in fn_state.send_message_calls.iter() {
+            for (field_name, arg_name) in call_state.controlled_fields.iter() {
+                if fn_state.validated_args.contains(arg_name) {
+                    continue;
+                }
+
+                project.report.borrow_mut().add_entry(
+                    context.path,
+                    project.span_to_line(context.path, &call_state.span)?,
+                    Severity::Medium,
+                    format!(
+                        "{item_location} sends a message whose {field_name} is controlled by the unvalidated `{arg_name}` argument: `{}`. Consider validating `{arg_name}` before sending the message.",
+                        call_state.span.as_str(),
+                    ),
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_unsafe_message_handling() {
+        crate::tests::test_detector("unsafe_message_handling", 5);
+    }
+}
@@ -7,7 +7,6 @@ use crate::{
     visitor::{AstVisitor, ExprContext, ModuleContext, UseContext},
 };
 use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
-use sway_ast::Expr;
 use sway_types::Spanned;
 
 #[derive(Default)]
@@ -15,25 +14,14 @@ pub struct UnsafeTimestampUsageVisitor {
     module_states: HashMap<PathBuf, ModuleState>,
 }
 
-#[derive(Default)]
 struct ModuleState {
-    timestamp_names: Vec<String>,
-    timestamp_of_block_names: Vec<String>,
+    timestamp_fns: utils::FnCallMatcher,
 }
 
-impl ModuleState {
-    fn expr_is_timestamp_call(&self, expr: &Expr) -> bool {
-        // Destructure the expression into a function application
-        let Expr::FuncApp { func, .. } = expr else { return false };
-        let Expr::Path(path) = func.as_ref() else { return false };
-        
-        // Update the variable state
-        match path.span().as_str() {
-            "std::block::timestamp" => true,
-            "std::block::timestamp_of_block" => true,
-            s if self.timestamp_names.iter().any(|name| s == name) => true,
-            s if self.timestamp_of_block_names.iter().any(|name| s == name) => true,
-            _ => false,
+impl Default for ModuleState {
+    fn default() -> Self {
+        Self {
+            timestamp_fns: utils::FnCallMatcher::new(utils::TIMESTAMP_FN_PATHS),
         }
     }
 }
@@ -52,15 +40,8 @@ impl AstVisitor for UnsafeTimestampUsageVisitor {
         // Get the module state
         let module_state = self.module_states.get_mut(context.path).unwrap();
 
-        // Check the use tree for `std::block::timestamp`
-        if let Some(name) = utils::use_tree_to_name(&context.item_use.tree, "std::block::timestamp") {
-            module_state.timestamp_names.push(name);
-        }
-        
-        // Check the use tree for `std::block::timestamp_of_block`
-        if let Some(name) = utils::use_tree_to_name(&context.item_use.tree, "std::block::timestamp_of_block") {
-            module_state.timestamp_of_block_names.push(name);
-        }
+        // Check the use tree for `std::block::{timestamp, timestamp_of_block}`
+        module_state.timestamp_fns.visit_use(context.item_use);
 
         Ok(())
     }
@@ -70,7 +51,7 @@ impl AstVisitor for UnsafeTimestampUsageVisitor {
         let module_state = self.module_states.get_mut(context.path).unwrap();
 
         // Check if the expression is a timestamp call expression
-        if module_state.expr_is_timestamp_call(context.expr) {
+        if module_state.timestamp_fns.is_call(context.expr) {
             project.report.borrow_mut().add_entry(
                 context.path,
                 project.span_to_line(context.path, &context.expr.span())?,
@@ -12,10 +12,7 @@ use crate::{
     },
 };
 use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
-use sway_ast::{
-    ty::TyTupleDescriptor, Expr, FnArgs, PathExpr, PathExprSegment, PathType, Pattern, Traits, Ty,
-    UseTree,
-};
+use sway_ast::{Expr, FnArgs, PathType, Pattern, Traits, Ty, UseTree};
 use sway_types::{Span, Spanned};
 
 #[derive(Default)]
@@ -61,99 +58,19 @@ impl ModuleState {
     }
 
     fn check_expr_usage(&mut self, expr: &Expr) {
-        utils::map_expr(expr, &mut |expr| {
-            if let Expr::Path(path_expr) = expr {
-                self.check_path_expr_usage(path_expr)
-            }
-        });
+        utils::map_expr_usage(expr, &mut |span| self.check_span_usage(span));
     }
 
     fn check_pattern_usage(&mut self, pattern: &Pattern) {
-        utils::map_pattern(pattern, &mut |pattern| {
-            if let Pattern::Constructor { path, .. } | Pattern::Struct { path, .. } = pattern {
-                self.check_path_expr_usage(path);
-            }
-        });
-    }
-
-    fn check_path_expr_usage(&mut self, path: &PathExpr) {
-        let mut check_segment_usage = |segment: &PathExprSegment| {
-            self.check_span_usage(&segment.name.span());
-
-            if let Some((_, generics)) = segment.generics_opt.as_ref() {
-                for ty in &generics.parameters.inner {
-                    self.check_ty_usage(ty);
-                }
-            }
-        };
-
-        check_segment_usage(&path.prefix);
-
-        for (_, suffix) in path.suffix.iter() {
-            check_segment_usage(suffix);
-        }
+        utils::map_pattern_usage(pattern, &mut |span| self.check_span_usage(span));
     }
 
     fn check_path_type_usage(&mut self, path: &PathType) {
-        self.check_span_usage(&path.prefix.name.span());
-                
-        if let Some(generics) = path.prefix.generics_opt.as_ref() {
-            for ty in &generics.1.parameters.inner {
-                self.check_ty_usage(ty);
-            }
-        }
-
-        if let Some(root) = path.root_opt.as_ref() {
-            if let Some(root) = root.0.as_ref() {
-                self.check_ty_usage(root.inner.ty.as_ref());
-                
-                // if let Some(as_trait) = root.inner.as_trait.as_ref() {
-                    self.check_path_type_usage(root.inner.as_trait.1.as_ref());
-                // }
-            }
-        }
+        utils::map_path_type_usage(path, &mut |span| self.check_span_usage(span));
     }
 
     fn check_ty_usage(&mut self, ty: &Ty) {
-        match ty {
-            Ty::Path(path) => {
-                self.check_path_type_usage(path);
-            }
-
-            Ty::Tuple(tuple) => {
-                if let TyTupleDescriptor::Cons { head, tail, .. } = &tuple.inner {
-                    self.check_ty_usage(head.as_ref());
-                    
-                    for ty in tail {
-                        self.check_ty_usage(ty);
-                    }
-                }
-            }
-
-            Ty::Array(array) => {
-                self.check_ty_usage(&array.inner.ty);
-                self.check_expr_usage(array.inner.length.as_ref());
-            }
-            
-            Ty::StringSlice(_) => {}
-            Ty::StringArray { .. } => {}
-            Ty::Infer { .. } => {}
-            
-            Ty::Ptr { ty, .. } |
-            Ty::Slice { ty, .. } => {
-                self.check_ty_usage(ty.inner.as_ref());
-            }
-
-            Ty::Ref { ty, .. } => {
-                self.check_ty_usage(ty.as_ref());
-            }
-
-            Ty::Never { .. } => {}
-
-            Ty::Expr(expr) => {
-                self.check_expr_usage(expr);
-            }
-        }
+        utils::map_ty_usage(ty, &mut |span| self.check_span_usage(span));
     }
 }
 
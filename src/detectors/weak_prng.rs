@@ -1,7 +1,7 @@
 use crate::{
     error::Error,
     project::Project,
-    report::Severity,
+    report::{Confidence, Severity},
     scope::AstScope,
     utils,
     visitor::{
@@ -203,25 +203,30 @@ impl AstVisitor for WeakPrngVisitor {
         // Get the module state
         let module_state = self.module_states.get_mut(context.path).unwrap();
 
-        // Check if `lhs` is a timestamp call expression
-        let mut is_timestamp = module_state.expr_is_timestamp_call(lhs.as_ref());
+        // Check if `lhs` is a timestamp call expression, which is a syntactic match
+        let mut confidence = None;
+
+        if module_state.expr_is_timestamp_call(lhs.as_ref()) {
+            confidence = Some(Confidence::High);
+        }
 
         // Get the function state if available
         if let Some(item_fn) = context.item_fn.as_ref() {
             let fn_signature = item_fn.fn_signature.span();
             let fn_state = module_state.fn_states.get_mut(&fn_signature).unwrap();
 
-            // Check if `lhs` is a timestamp variable expression
-            if fn_state.expr_is_timestamp_variable(lhs.as_ref(), context.blocks.as_slice()) {
-                is_timestamp = true;
+            // Check if `lhs` is a timestamp variable expression, which may have been reassigned in a way we do not track
+            if confidence.is_none() && fn_state.expr_is_timestamp_variable(lhs.as_ref(), context.blocks.as_slice()) {
+                confidence = Some(Confidence::Medium);
             }
         }
 
-        if is_timestamp {
-            project.report.borrow_mut().add_entry(
+        if let Some(confidence) = confidence {
+            project.report.borrow_mut().add_entry_with_confidence(
                 context.path,
                 project.span_to_line(context.path, &context.expr.span())?,
                 Severity::Medium,
+                confidence,
                 format!(
                     "{} contains weak PRNG due to dependence on a block timestamp: `{}`",
                     utils::get_item_location(context.item, &context.item_impl, &context.item_fn),
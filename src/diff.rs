@@ -0,0 +1,139 @@
+use crate::{
+    error::Error,
+    formats,
+    project::DisplayFormat,
+    report::{DiffStatus, Report, Sorting},
+    schema::{JsonMetadata, JsonReport, SCHEMA_VERSION},
+};
+use std::{collections::HashMap, fmt::Display, path::Path};
+
+/// The comparison of two JSON reports.
+pub struct ReportDiff {
+    display_format: DisplayFormat,
+    metadata: JsonMetadata,
+    pub report: Report,
+}
+
+impl Display for ReportDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formats::write_report(f, self.display_format, &self.report, &HashMap::new(), || {
+            let mut json_report = JsonReport::new(&self.report, vec![], vec![], Default::default());
+            json_report.metadata = self.metadata.clone();
+            json_report
+        })
+    }
+}
+
+impl ReportDiff {
+    /// Compares the findings of the `old` and `new` reports, classifying each finding as new, fixed or unchanged.
+    ///
+    /// Findings are matched by their fingerprints, so findings that only moved to a different line are unchanged.
+    pub fn new(old: &JsonReport, new: &JsonReport, display_format: DisplayFormat, sorting: Sorting) -> Self {
+        let mut report = Report {
+            sorting,
+            ..Default::default()
+        };
+
+        // Count the fingerprints of the old findings, since identical findings can occur more than once
+        let mut old_fingerprints: HashMap<String, usize> = HashMap::new();
+
+        for (path, entry) in old.to_entries() {
+            *old_fingerprints.entry(entry.fingerprint(&path)).or_default() += 1;
+        }
+
+        // Classify the new findings as unchanged if a matching old finding is left, otherwise as new
+        let mut new_fingerprints: HashMap<String, usize> = HashMap::new();
+
+        for (path, mut entry) in new.to_entries() {
+            let fingerprint = entry.fingerprint(&path);
+
+            entry.status = match old_fingerprints.get_mut(&fingerprint) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    Some(DiffStatus::Unchanged)
+                }
+
+                _ => Some(DiffStatus::New),
+            };
+
+            *new_fingerprints.entry(fingerprint).or_default() += 1;
+            report.insert_entry(path, entry);
+        }
+
+        // Classify the old findings which are not matched by a new finding as fixed
+        for (path, mut entry) in old.to_entries() {
+            let fingerprint = entry.fingerprint(&path);
+
+            match new_fingerprints.get_mut(&fingerprint) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    continue;
+                }
+
+                _ => {}
+            }
+
+            entry.status = Some(DiffStatus::Fixed);
+            report.insert_entry(path, entry);
+        }
+
+        Self {
+            display_format,
+            metadata: new.metadata.clone(),
+            report,
+        }
+    }
+
+    /// Gets the number of entries with the supplied `status`.
+    pub fn status_count(&self, status: DiffStatus) -> usize {
+        self.report.entries.iter()
+            .flat_map(|(_, entries)| entries.iter())
+            .filter(|entry| entry.status == Some(status))
+            .count()
+    }
+}
+
+/// Loads a JSON report produced by the "Json" display format from the file at the supplied `path`.
+pub fn load_report<P: AsRef<Path>>(path: P) -> Result<JsonReport, Error> {
+    let path = path.as_ref();
+
+    let Ok(text) = std::fs::read_to_string(path) else {
+        return Err(Error::FileNotFound(path.into()));
+    };
+
+    let report: JsonReport = serde_json::from_str(&text).map_err(|e| {
+        Error::Wrapped(format!("Failed to load report \"{}\": {e}", path.to_string_lossy()).into())
+    })?;
+
+    // Reports with a different major schema version may use fields with a different meaning
+    let major_version = |version: &str| version.split('.').next().map(String::from);
+
+    if major_version(&report.schema_version) != major_version(SCHEMA_VERSION) {
+        return Err(Error::Wrapped(format!(
+            "Unsupported schema version {} in report \"{}\", expected {SCHEMA_VERSION}",
+            report.schema_version,
+            path.to_string_lossy(),
+        ).into()));
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_reports() {
+        let old = load_report("test/diff_reports/old.json").unwrap();
+        let new = load_report("test/diff_reports/new.json").unwrap();
+
+        let diff = ReportDiff::new(&old, &new, DisplayFormat::Text, Sorting::Line);
+
+        assert_eq!(diff.status_count(DiffStatus::New), 1);
+        assert_eq!(diff.status_count(DiffStatus::Fixed), 1);
+        assert_eq!(diff.status_count(DiffStatus::Unchanged), 2);
+
+        println!("{diff}");
+    }
+}
@@ -5,7 +5,14 @@ pub enum Error {
     Wrapped(Box<dyn std::error::Error>),
     InvalidDisplayFormat(String),
     InvalidSorting(String),
+    InvalidConfidence(String),
     ParseFailed(PathBuf),
+    ManifestParseFailed(PathBuf),
+    ConfigParseFailed(PathBuf),
+    InvalidDetectorOption(String),
+    InvalidCustomRule(String),
+    InvalidQueryPattern(String),
+    PluginLoadFailed(PathBuf, String),
     FileNotFound(PathBuf),
     LineNotFound(PathBuf, usize),
 }
@@ -16,7 +23,14 @@ impl std::fmt::Display for Error {
             Error::Wrapped(e) => write!(f, "{e}"),
             Error::InvalidDisplayFormat(format) => write!(f, "Invalid display format: {format}"),
             Error::InvalidSorting(sorting) => write!(f, "Invalid sorting: {sorting}"),
+            Error::InvalidConfidence(confidence) => write!(f, "Invalid confidence: {confidence}"),
             Error::ParseFailed(path) => write!(f, "Failed to parse file: \"{}\"", path.to_string_lossy()),
+            Error::ManifestParseFailed(path) => write!(f, "Failed to parse manifest: \"{}\"", path.to_string_lossy()),
+            Error::ConfigParseFailed(path) => write!(f, "Failed to parse config file: \"{}\"", path.to_string_lossy()),
+            Error::InvalidDetectorOption(message) => write!(f, "Invalid detector option: {message}"),
+            Error::InvalidCustomRule(message) => write!(f, "Invalid custom rule: {message}"),
+            Error::InvalidQueryPattern(message) => write!(f, "Invalid query pattern: {message}"),
+            Error::PluginLoadFailed(path, message) => write!(f, "Failed to load plugin \"{}\": {message}", path.to_string_lossy()),
             Error::FileNotFound(path) => write!(f, "File not found: \"{}\"", path.to_string_lossy()),
             Error::LineNotFound(path, offset) => write!(f, "Offset {offset} not found in file: \"{}\"", path.to_string_lossy()),
         }
@@ -0,0 +1,379 @@
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Component, Path, PathBuf},
+};
+use sway_ast::{Module, ModuleKind};
+
+#[derive(Deserialize)]
+struct ForcManifest {
+    project: Option<ForcManifestProject>,
+    workspace: Option<ForcManifestWorkspace>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, toml::Value>,
+    #[serde(default, rename = "contract-dependencies")]
+    contract_dependencies: BTreeMap<String, toml::Value>,
+}
+
+#[derive(Deserialize)]
+struct ForcManifestProject {
+    name: String,
+    #[serde(default = "default_entry")]
+    entry: String,
+}
+
+fn default_entry() -> String {
+    "main.sw".into()
+}
+
+#[derive(Deserialize)]
+struct ForcManifestWorkspace {
+    #[serde(default)]
+    members: Vec<PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct ForcLock {
+    #[serde(default)]
+    package: Vec<ForcLockPackage>,
+}
+
+#[derive(Deserialize)]
+struct ForcLockPackage {
+    name: String,
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+/// The kind of program a Forc package compiles to.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum ProgramKind {
+    Contract,
+    Script,
+    Predicate,
+    Library,
+}
+
+impl ProgramKind {
+    /// Gets the program kind declared by the supplied parsed `module`.
+    pub fn from_module(module: &Module) -> Self {
+        match module.kind {
+            ModuleKind::Contract { .. } => Self::Contract,
+            ModuleKind::Script { .. } => Self::Script,
+            ModuleKind::Predicate { .. } => Self::Predicate,
+            ModuleKind::Library { .. } => Self::Library,
+        }
+    }
+}
+
+/// A Forc package.
+#[derive(Clone, Debug)]
+pub struct Package {
+    /// The name of the package.
+    pub name: String,
+    /// The path to the directory containing the `Forc.toml` of the package.
+    pub root: PathBuf,
+    /// The path to the entry file of the package, which declares the kind of program the package compiles to.
+    pub entry_path: PathBuf,
+    /// The roots of the local packages the package depends on, including contract dependencies.
+    pub dependencies: Vec<PathBuf>,
+}
+
+impl Package {
+    /// Gets the path to the `src` directory of the package.
+    pub fn src_path(&self) -> PathBuf {
+        self.root.join("src")
+    }
+}
+
+/// The graph of the Forc packages of a project and their local dependencies.
+#[derive(Default)]
+pub struct PackageGraph {
+    pub packages: Vec<Package>,
+    /// The dependencies only named in lock files, as the root of the depending package and the name of the dependency.
+    lock_dependencies: Vec<(PathBuf, String)>,
+}
+
+/// The names of the directories which contain build output instead of sources.
+const BUILD_DIRECTORY_NAMES: &[&str] = &["out", "target"];
+
+/// Checks if the directory at the supplied `path` should not be searched for packages or sources, which are hidden and build directories.
+pub fn is_ignored_directory<P: AsRef<Path>>(path: P) -> bool {
+    let Some(name) = path.as_ref().file_name().map(|x| x.to_string_lossy()) else { return false };
+    name.starts_with('.') || BUILD_DIRECTORY_NAMES.contains(&name.as_ref())
+}
+
+/// Resolves `.` and `..` components of the supplied `path` without touching the file system.
+pub fn normalize_path_lexically<P: AsRef<Path>>(path: P) -> PathBuf {
+    let mut result = PathBuf::new();
+
+    for component in path.as_ref().components() {
+        match component {
+            Component::CurDir => {}
+
+            Component::ParentDir => {
+                if matches!(result.components().next_back(), None | Some(Component::ParentDir)) {
+                    result.push("..");
+                } else {
+                    result.pop();
+                }
+            }
+
+            component => result.push(component),
+        }
+    }
+
+    result
+}
+
+impl PackageGraph {
+    /// Finds the Forc manifests in the supplied `directory` and loads their packages, workspace members and local dependencies.
+    pub fn load_directory<P: AsRef<Path>>(&mut self, directory: P) -> Result<(), Error> {
+        let directory = directory.as_ref();
+
+        if directory.join("Forc.toml").is_file() {
+            self.load_manifest(directory)?;
+        }
+
+        for entry in directory.read_dir().map_err(|e| Error::Wrapped(Box::new(e)))? {
+            let Ok(entry) = entry else { continue };
+            let path = entry.path();
+
+            if path.is_dir() && !is_ignored_directory(&path) {
+                self.load_directory(path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Loads the package or workspace of the `Forc.toml` in the supplied `root` directory, along with its local dependencies.
+    pub fn load_manifest<P: AsRef<Path>>(&mut self, root: P) -> Result<(), Error> {
+        let root = normalize_path_lexically(root);
+
+        if self.packages.iter().any(|x| x.root == root) {
+            return Ok(());
+        }
+
+        let manifest_path = root.join("Forc.toml");
+        let text = std::fs::read_to_string(&manifest_path).map_err(|_| Error::FileNotFound(manifest_path.clone()))?;
+        let manifest: ForcManifest = toml::from_str(&text).map_err(|_| Error::ManifestParseFailed(manifest_path.clone()))?;
+
+        // Load the members of a workspace
+        if let Some(workspace) = manifest.workspace.as_ref() {
+            for member in workspace.members.iter() {
+                self.load_manifest(root.join(member))?;
+            }
+        }
+
+        let Some(project) = manifest.project.as_ref() else { return Ok(()) };
+
+        let mut package = Package {
+            name: project.name.clone(),
+            root: root.clone(),
+            entry_path: root.join("src").join(&project.entry),
+            dependencies: vec![],
+        };
+
+        // Collect the local dependencies of the package, which are the ones with a `path`
+        let mut dependency_names = vec![];
+
+        for (name, dependency) in manifest.dependencies.iter().chain(manifest.contract_dependencies.iter()) {
+            let Some(path) = dependency.get("path").and_then(|x| x.as_str()) else { continue };
+
+            // The dependency can be renamed with the `package` key
+            dependency_names.push(dependency.get("package").and_then(|x| x.as_str()).unwrap_or(name).to_string());
+            package.dependencies.push(normalize_path_lexically(root.join(path)));
+        }
+
+        // Include the dependencies that were resolved in the lock file, if any, which are resolved to their roots once they are loaded
+        if let Some(lock) = std::fs::read_to_string(root.join("Forc.lock")).ok().and_then(|text| toml::from_str::<ForcLock>(&text).ok()) {
+            if let Some(lock_package) = lock.package.iter().find(|x| x.name == project.name) {
+                for dependency in lock_package.dependencies.iter() {
+                    // Lock file dependencies are formatted as `name` or `name source`
+                    let Some(name) = dependency.split_whitespace().next() else { continue };
+
+                    if !dependency_names.iter().any(|x| x == name) && lock.package.iter().any(|x| x.name == name) {
+                        self.lock_dependencies.push((root.clone(), name.to_string()));
+                    }
+                }
+            }
+        }
+
+        let dependency_roots = package.dependencies.clone();
+        self.packages.push(package);
+
+        // Skip a local dependency which can't be loaded instead of aborting the analysis of the package
+        for dependency_root in dependency_roots {
+            if let Err(e) = self.load_manifest(dependency_root) {
+                eprintln!("Skipping dependency of package `{}`: {e}", project.name);
+            }
+        }
+
+        self.resolve_lock_dependencies();
+
+        Ok(())
+    }
+
+    /// Resolves the dependencies only named in lock files to the loaded package with the same name whose root is the closest
+    /// to the depending package, since packages of different workspaces can share a name.
+    fn resolve_lock_dependencies(&mut self) {
+        let mut unresolved = vec![];
+
+        for (root, name) in std::mem::take(&mut self.lock_dependencies) {
+            let closest_root = self.packages.iter()
+                .filter(|x| x.name == name && x.root != root)
+                .max_by_key(|x| x.root.components().zip(root.components()).take_while(|(a, b)| a == b).count())
+                .map(|x| x.root.clone());
+
+            let Some(dependency_root) = closest_root else {
+                unresolved.push((root, name));
+                continue;
+            };
+
+            if let Some(package) = self.packages.iter_mut().find(|x| x.root == root) {
+                if !package.dependencies.contains(&dependency_root) {
+                    package.dependencies.push(dependency_root);
+                }
+            }
+        }
+
+        self.lock_dependencies = unresolved;
+    }
+
+    /// Gets the package containing the file at the supplied `path`.
+    pub fn package_for_path<P: AsRef<Path>>(&self, path: P) -> Option<&Package> {
+        let path = normalize_path_lexically(path);
+
+        self.packages.iter()
+            .filter(|x| path.starts_with(&x.root))
+            .max_by_key(|x| x.root.components().count())
+    }
+
+    /// Gets the first package with the supplied `name`. Packages of different workspaces can share a name,
+    /// so dependencies are resolved with [`PackageGraph::package_by_root`] instead.
+    pub fn package_by_name(&self, name: &str) -> Option<&Package> {
+        self.packages.iter().find(|x| x.name == name)
+    }
+
+    /// Gets the package whose `Forc.toml` is in the supplied `root` directory.
+    pub fn package_by_root<P: AsRef<Path>>(&self, root: P) -> Option<&Package> {
+        self.packages.iter().find(|x| x.root == root.as_ref())
+    }
+
+    /// Gets the packages linked to the supplied `package` through dependencies in either direction, including the package itself.
+    pub fn get_linked_packages(&self, package: &Package) -> Vec<&Package> {
+        let mut result: Vec<&Package> = vec![];
+        let mut queue = vec![package];
+
+        while let Some(package) = queue.pop() {
+            if result.iter().any(|x| x.root == package.root) {
+                continue;
+            }
+
+            // Follow the dependencies of the package as well as the packages which depend on it
+            queue.extend(package.dependencies.iter().filter_map(|x| self.package_by_root(x)));
+            queue.extend(self.packages.iter().filter(|x| x.dependencies.contains(&package.root)));
+
+            result.push(package);
+        }
+
+        result
+    }
+
+    /// Gets the local dependencies of the supplied `package`, including transitive dependencies.
+    pub fn get_dependencies(&self, package: &Package) -> Vec<&Package> {
+        let mut result: Vec<&Package> = vec![];
+        let mut queue = package.dependencies.iter().collect::<Vec<_>>();
+
+        while let Some(root) = queue.pop() {
+            let Some(dependency) = self.package_by_root(root) else { continue };
+
+            if result.iter().any(|x| x.root == dependency.root) {
+                continue;
+            }
+
+            queue.extend(dependency.dependencies.iter());
+            result.push(dependency);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{project::Project, Options};
+
+    #[test]
+    fn test_forc_workspace() {
+        let options = Options {
+            directory: Some(PathBuf::from("test/forc_workspace/workspace/")),
+            ..Default::default()
+        };
+
+        let project = Project::try_from(&options).unwrap();
+        let packages = project.packages();
+
+        let token = packages.package_by_name("token").unwrap();
+        assert_eq!(token.dependencies, vec![PathBuf::from("test/forc_workspace/shared")]);
+
+        let shared = packages.package_by_name("shared").unwrap();
+        assert_eq!(shared.root, PathBuf::from("test/forc_workspace/shared"));
+
+        assert_eq!(packages.get_dependencies(token).len(), 1);
+
+        // Packages are linked through dependencies in either direction
+        assert_eq!(packages.get_linked_packages(shared).len(), 2);
+
+        // The local dependency outside of the workspace directory should be analyzed as well
+        assert!(project.modules().borrow().contains_key(&PathBuf::from("test/forc_workspace/shared/src/lib.sw")));
+        assert_eq!(project.program_kind("test/forc_workspace/workspace/token/src/main.sw"), Some(ProgramKind::Contract));
+        assert_eq!(project.program_kind("test/forc_workspace/shared/src/lib.sw"), Some(ProgramKind::Library));
+    }
+
+    #[test]
+    fn test_forc_name_collision() {
+        let options = Options {
+            directory: Some(PathBuf::from("test/forc_name_collision/")),
+            ..Default::default()
+        };
+
+        let project = Project::try_from(&options).unwrap();
+        let packages = project.packages();
+
+        // Packages of different workspaces which share a name should only be linked to their own dependencies
+        for workspace in ["first", "second"] {
+            let token = packages.package_by_root(format!("test/forc_name_collision/{workspace}/token")).unwrap();
+            assert_eq!(token.dependencies, vec![PathBuf::from(format!("test/forc_name_collision/{workspace}/shared"))]);
+
+            let linked_roots = packages.get_linked_packages(token).iter().map(|x| x.root.clone()).collect::<Vec<_>>();
+            assert_eq!(linked_roots.len(), 2);
+            assert!(linked_roots.iter().all(|x| x.starts_with(format!("test/forc_name_collision/{workspace}"))));
+        }
+    }
+
+    #[test]
+    fn test_forc_missing_dependency() {
+        let options = Options {
+            directory: Some(PathBuf::from("test/forc_missing_dependency/")),
+            ..Default::default()
+        };
+
+        // A local dependency which can't be loaded should be skipped instead of aborting the analysis
+        let project = Project::try_from(&options).unwrap();
+        assert!(project.packages().package_by_name("forc_missing_dependency").is_some());
+
+        // The program kind should come from the parsed entry file, ignoring the commented-out declaration
+        assert_eq!(project.program_kind("test/forc_missing_dependency/src/main.sw"), Some(ProgramKind::Contract));
+        assert!(project.packages().package_by_name("missing").is_none());
+
+        // Hidden and build directories should not be searched for packages or sources
+        assert!(is_ignored_directory("test/forc_missing_dependency/out"));
+        assert!(is_ignored_directory("test/forc_missing_dependency/target"));
+        assert!(is_ignored_directory("test/forc_missing_dependency/.git"));
+        assert!(!is_ignored_directory("test/forc_missing_dependency/src"));
+    }
+}
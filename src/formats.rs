@@ -0,0 +1,428 @@
+use crate::{
+    detectors::detector_url,
+    project::DisplayFormat,
+    report::{Entry, Report, Severity},
+    schema::JsonReport,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{Formatter, Result},
+    path::PathBuf,
+};
+
+/// The number of source lines to include before and after the line of a finding.
+const EXCERPT_CONTEXT_LINES: usize = 2;
+
+const SEVERITIES: [Severity; 3] = [Severity::High, Severity::Medium, Severity::Low];
+
+fn severity_icon(severity: Severity) -> &'static str {
+    match severity {
+        Severity::High => "🔴",
+        Severity::Medium => "🟡",
+        Severity::Low => "🟢",
+    }
+}
+
+fn severity_index(severity: Severity) -> usize {
+    match severity {
+        Severity::High => 0,
+        Severity::Medium => 1,
+        Severity::Low => 2,
+    }
+}
+
+/// Writes the supplied `report` in the supplied `display_format`.
+///
+/// The `json_report` function is only called when the report is displayed as JSON, since collecting its metadata is not free.
+pub fn write_report(
+    f: &mut Formatter<'_>,
+    display_format: DisplayFormat,
+    report: &Report,
+    sources: &HashMap<PathBuf, String>,
+    json_report: impl FnOnce() -> JsonReport,
+) -> Result {
+    match display_format {
+        DisplayFormat::Text => write!(f, "{report}"),
+        DisplayFormat::Json => write!(f, "{}", serde_json::to_value(json_report()).unwrap()),
+        DisplayFormat::Markdown => write_markdown(f, report, sources),
+        DisplayFormat::Html => write_html(f, report, sources),
+        DisplayFormat::JunitXml => write_junit_xml(f, report),
+        DisplayFormat::CodeQuality => write_code_quality(f, report),
+        DisplayFormat::Csv => write_csv(f, report),
+    }
+}
+
+/// Counts the entries of the supplied `report` per severity, and per detector and severity.
+fn count_entries(report: &Report) -> ([usize; 3], BTreeMap<String, [usize; 3]>) {
+    let mut severity_counts = [0usize; 3];
+    let mut detector_counts: BTreeMap<String, [usize; 3]> = BTreeMap::new();
+
+    for (_, entries) in report.entries.iter() {
+        for entry in entries.iter() {
+            let index = severity_index(entry.severity);
+            severity_counts[index] += 1;
+
+            let detector = entry.detector.clone().unwrap_or_else(|| "unknown".into());
+            detector_counts.entry(detector).or_default()[index] += 1;
+        }
+    }
+
+    (severity_counts, detector_counts)
+}
+
+/// Gets the source lines surrounding the line of the supplied `entry`, along with their line numbers.
+fn get_excerpt<'a>(source: Option<&'a String>, entry: &Entry) -> Vec<(usize, &'a str)> {
+    let (Some(source), Some(line)) = (source, entry.line) else { return vec![] };
+
+    let first_line = line.saturating_sub(EXCERPT_CONTEXT_LINES).max(1);
+    let last_line = line + EXCERPT_CONTEXT_LINES;
+
+    source.lines()
+        .enumerate()
+        .map(|(i, x)| (i + 1, x))
+        .filter(|(i, _)| *i >= first_line && *i <= last_line)
+        .collect()
+}
+
+/// Writes the supplied `report` as a self-contained Markdown audit report.
+pub fn write_markdown(f: &mut Formatter<'_>, report: &Report, sources: &HashMap<PathBuf, String>) -> Result {
+    let (severity_counts, detector_counts) = count_entries(report);
+
+    writeln!(f, "# Sway Analyzer Report")?;
+    writeln!(f)?;
+    writeln!(f, "## Summary")?;
+    writeln!(f)?;
+    writeln!(f, "| Severity | Findings |")?;
+    writeln!(f, "|----------|----------|")?;
+
+    for severity in SEVERITIES {
+        writeln!(f, "| {} {} | {} |", severity_icon(severity), severity, severity_counts[severity_index(severity)])?;
+    }
+
+    writeln!(f)?;
+    writeln!(f, "| Detector | High | Medium | Low |")?;
+    writeln!(f, "|----------|------|--------|-----|")?;
+
+    for (detector, counts) in detector_counts.iter() {
+        match detector_url(detector) {
+            Some(url) => write!(f, "| [`{detector}`]({url}) ")?,
+            None => write!(f, "| `{detector}` ")?,
+        }
+
+        writeln!(f, "| {} | {} | {} |", counts[0], counts[1], counts[2])?;
+    }
+
+    writeln!(f)?;
+    writeln!(f, "## Findings")?;
+
+    for (path, entries) in report.entries.iter() {
+        writeln!(f)?;
+        writeln!(f, "### `{}`", path.to_string_lossy())?;
+
+        for entry in entries.iter() {
+            writeln!(f)?;
+
+            write!(f, "#### {} {}", severity_icon(entry.severity), entry.severity)?;
+
+            if let Some(confidence) = entry.confidence {
+                write!(f, " ({confidence} confidence)")?;
+            }
+
+            if let Some(line) = entry.line {
+                write!(f, " (L{line})")?;
+            }
+
+            if let Some(detector) = entry.detector.as_ref() {
+                match detector_url(detector) {
+                    Some(url) => write!(f, ": [`{detector}`]({url})")?,
+                    None => write!(f, ": `{detector}`")?,
+                }
+            }
+
+            writeln!(f)?;
+            writeln!(f)?;
+            writeln!(f, "{}", entry.status_text())?;
+
+            let excerpt = get_excerpt(sources.get(path), entry);
+
+            if excerpt.is_empty() {
+                continue;
+            }
+
+            writeln!(f)?;
+            writeln!(f, "```sway")?;
+
+            for (line, text) in excerpt {
+                let marker = if Some(line) == entry.line { ">" } else { " " };
+                writeln!(f, "{marker} {line:>5} | {text}")?;
+            }
+
+            writeln!(f, "```")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Escapes the supplied `text` for use as a CSV field, quoting it if it contains a separator, a quote or a line break.
+pub fn escape_csv(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Gets the HTML markup of the supplied `detector` name, linking to its documentation if it is a built-in detector.
+fn detector_link_markup(detector: &str) -> String {
+    match detector_url(detector) {
+        Some(url) => format!("<a href=\"{}\"><code>{}</code></a>", escape_markup(&url), escape_markup(detector)),
+        None => format!("<code>{}</code>", escape_markup(detector)),
+    }
+}
+
+/// Escapes the supplied `text` for use in HTML or XML.
+fn escape_markup(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            c => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Writes the supplied `report` as a self-contained HTML audit report.
+pub fn write_html(f: &mut Formatter<'_>, report: &Report, sources: &HashMap<PathBuf, String>) -> Result {
+    let (severity_counts, detector_counts) = count_entries(report);
+
+    writeln!(f, "<!DOCTYPE html>")?;
+    writeln!(f, "<html lang=\"en\">")?;
+    writeln!(f, "<head>")?;
+    writeln!(f, "<meta charset=\"utf-8\">")?;
+    writeln!(f, "<title>Sway Analyzer Report</title>")?;
+    writeln!(f, "<style>")?;
+    writeln!(f, "body {{ font-family: sans-serif; margin: 2em auto; max-width: 960px; }}")?;
+    writeln!(f, "table {{ border-collapse: collapse; margin-bottom: 1em; }}")?;
+    writeln!(f, "th, td {{ border: 1px solid #ccc; padding: 4px 8px; text-align: left; }}")?;
+    writeln!(f, "pre {{ background: #f6f8fa; padding: 8px; overflow-x: auto; }}")?;
+    writeln!(f, ".finding {{ border-left: 4px solid #ccc; padding-left: 8px; margin-bottom: 1em; }}")?;
+    writeln!(f, ".high {{ border-color: #d73a49; }}")?;
+    writeln!(f, ".medium {{ border-color: #dbab09; }}")?;
+    writeln!(f, ".low {{ border-color: #28a745; }}")?;
+    writeln!(f, ".highlight {{ background: #fff5b1; }}")?;
+    writeln!(f, "</style>")?;
+    writeln!(f, "</head>")?;
+    writeln!(f, "<body>")?;
+    writeln!(f, "<h1>Sway Analyzer Report</h1>")?;
+    writeln!(f, "<h2>Summary</h2>")?;
+    writeln!(f, "<table>")?;
+    writeln!(f, "<tr><th>Severity</th><th>Findings</th></tr>")?;
+
+    for severity in SEVERITIES {
+        writeln!(f, "<tr><td>{} {}</td><td>{}</td></tr>", severity_icon(severity), severity, severity_counts[severity_index(severity)])?;
+    }
+
+    writeln!(f, "</table>")?;
+    writeln!(f, "<table>")?;
+    writeln!(f, "<tr><th>Detector</th><th>High</th><th>Medium</th><th>Low</th></tr>")?;
+
+    for (detector, counts) in detector_counts.iter() {
+        writeln!(
+            f,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            detector_link_markup(detector),
+            counts[0],
+            counts[1],
+            counts[2],
+        )?;
+    }
+
+    writeln!(f, "</table>")?;
+    writeln!(f, "<h2>Findings</h2>")?;
+
+    for (path, entries) in report.entries.iter() {
+        writeln!(f, "<h3><code>{}</code></h3>", escape_markup(&path.to_string_lossy()))?;
+
+        for entry in entries.iter() {
+            let class = entry.severity.to_string().to_ascii_lowercase();
+
+            writeln!(f, "<div class=\"finding {class}\">")?;
+            write!(f, "<h4>{} {}", severity_icon(entry.severity), entry.severity)?;
+
+            if let Some(confidence) = entry.confidence {
+                write!(f, " ({confidence} confidence)")?;
+            }
+
+            if let Some(line) = entry.line {
+                write!(f, " (L{line})")?;
+            }
+
+            if let Some(detector) = entry.detector.as_ref() {
+                write!(f, ": {}", detector_link_markup(detector))?;
+            }
+
+            writeln!(f, "</h4>")?;
+            writeln!(f, "<p>{}</p>", escape_markup(&entry.status_text()))?;
+
+            let excerpt = get_excerpt(sources.get(path), entry);
+
+            if !excerpt.is_empty() {
+                write!(f, "<pre><code>")?;
+
+                for (line, text) in excerpt {
+                    if Some(line) == entry.line {
+                        writeln!(f, "<span class=\"highlight\">{line:>5} | {}</span>", escape_markup(text))?;
+                    } else {
+                        writeln!(f, "{line:>5} | {}", escape_markup(text))?;
+                    }
+                }
+
+                writeln!(f, "</code></pre>")?;
+            }
+
+            writeln!(f, "</div>")?;
+        }
+    }
+
+    writeln!(f, "</body>")?;
+    writeln!(f, "</html>")?;
+
+    Ok(())
+}
+
+/// Groups the entries of the supplied `report` by the name of the detector that created them.
+fn group_entries_by_detector(report: &Report) -> BTreeMap<String, Vec<(&PathBuf, &Entry)>> {
+    let mut result: BTreeMap<String, Vec<(&PathBuf, &Entry)>> = BTreeMap::new();
+
+    for (path, entries) in report.entries.iter() {
+        for entry in entries.iter() {
+            let detector = entry.detector.clone().unwrap_or_else(|| "unknown".into());
+            result.entry(detector).or_default().push((path, entry));
+        }
+    }
+
+    result
+}
+
+/// Writes the supplied `report` as JUnit XML, where each detector is a test suite and each finding is a failing test case.
+pub fn write_junit_xml(f: &mut Formatter<'_>, report: &Report) -> Result {
+    let groups = group_entries_by_detector(report);
+    let total: usize = groups.values().map(|x| x.len()).sum();
+
+    writeln!(f, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(f, "<testsuites name=\"sway-analyzer\" tests=\"{total}\" failures=\"{total}\">")?;
+
+    for (detector, entries) in groups.iter() {
+        writeln!(
+            f,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">",
+            escape_markup(detector),
+            entries.len(),
+            entries.len(),
+        )?;
+
+        for (path, entry) in entries.iter() {
+            let name = match entry.line {
+                Some(line) => format!("{}:{line}", path.to_string_lossy()),
+                None => path.to_string_lossy().to_string(),
+            };
+
+            writeln!(
+                f,
+                "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\">",
+                escape_markup(&name),
+                escape_markup(detector),
+                escape_markup(&path.to_string_lossy()),
+            )?;
+
+            writeln!(
+                f,
+                "      <failure message=\"{}\" type=\"{}\">{}</failure>",
+                escape_markup(&entry.status_text()),
+                entry.severity,
+                escape_markup(&entry.status_text()),
+            )?;
+
+            writeln!(f, "    </testcase>")?;
+        }
+
+        writeln!(f, "  </testsuite>")?;
+    }
+
+    writeln!(f, "</testsuites>")?;
+
+    Ok(())
+}
+
+/// Writes the supplied `report` as a GitLab Code Quality JSON array.
+pub fn write_code_quality(f: &mut Formatter<'_>, report: &Report) -> Result {
+    let mut issues = vec![];
+
+    for (path, entries) in report.entries.iter() {
+        // GitLab requires unique fingerprints, so identical findings in the same file are told apart by their order of occurrence
+        let mut entries = entries.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|x| x.line);
+
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+
+        for entry in entries {
+            let detector = entry.detector.clone().unwrap_or_else(|| "unknown".into());
+
+            let occurrence = occurrences.entry(entry.fingerprint(path)).or_default();
+            let fingerprint = entry.fingerprint_with_occurrence(path, *occurrence);
+            *occurrence += 1;
+
+            let severity = match entry.severity {
+                Severity::High => "critical",
+                Severity::Medium => "major",
+                Severity::Low => "minor",
+            };
+
+            // The fingerprint needs to be stable across runs so GitLab can track findings between pipelines
+            issues.push(serde_json::json!({
+                "description": entry.status_text(),
+                "check_name": detector,
+                "fingerprint": fingerprint,
+                "severity": severity,
+                "location": {
+                    "path": path.to_string_lossy(),
+                    "lines": {
+                        "begin": entry.line.unwrap_or(1),
+                    },
+                },
+            }));
+        }
+    }
+
+    write!(f, "{}", serde_json::Value::Array(issues))
+}
+
+/// Writes the supplied `report` as CSV, with one row per finding.
+pub fn write_csv(f: &mut Formatter<'_>, report: &Report) -> Result {
+    writeln!(f, "file,line,severity,confidence,detector,message")?;
+
+    for (path, entries) in report.entries.iter() {
+        for entry in entries.iter() {
+            writeln!(
+                f,
+                "{},{},{},{},{},{}",
+                escape_csv(&path.to_string_lossy()),
+                entry.line.map(|x| x.to_string()).unwrap_or_default(),
+                entry.severity,
+                entry.confidence.map(|x| x.to_string()).unwrap_or_default(),
+                escape_csv(entry.detector.as_deref().unwrap_or("unknown")),
+                escape_csv(&entry.status_text()),
+            )?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,524 @@
+#![allow(clippy::type_complexity)]
+pub mod cache;
+pub mod config;
+pub mod detectors;
+pub mod diff;
+pub mod error;
+pub mod forc;
+pub mod formats;
+pub mod plugin;
+pub mod project;
+pub mod query;
+pub mod report;
+pub mod rules;
+pub mod schema;
+pub mod scope;
+pub mod storage_layout;
+pub mod surface;
+pub mod utils;
+pub mod visitor;
+pub mod watch;
+
+use project::DisplayFormat;
+use query::QueryPattern;
+use report::{Confidence, Sorting};
+use std::{collections::HashSet, path::PathBuf};
+use structopt::{clap::AppSettings, StructOpt};
+
+#[derive(Default, StructOpt)]
+#[structopt(global_settings = &[AppSettings::ColoredHelp, AppSettings::ArgRequiredElseHelp])]
+pub struct Options {
+    #[structopt(subcommand)]
+    pub command: Option<Command>,
+
+    /// The display format of the report. Can be "Text", "Json", "Markdown", "Html", "JunitXml", "CodeQuality" or "Csv". (Default = Text)
+    #[structopt(long, global = true)]
+    pub display_format: Option<DisplayFormat>,
+
+    /// The order to sort report entries by. Can be "Line" or "Severity". (Default = Line)
+    #[structopt(long, global = true)]
+    pub sorting: Option<Sorting>,
+
+    /// The path to the Forc project directory. (Optional)
+    #[structopt(long)]
+    pub directory: Option<PathBuf>,
+
+    /// The paths to the Sway source files. (Optional)
+    #[structopt(long)]
+    pub files: Vec<PathBuf>,
+
+    /// The specific detectors to utilize. (Optional; Leave unused for all)
+    #[structopt(long)]
+    pub detectors: Vec<String>,
+
+    /// The severity levels to `include` in the report. Default is all of the severities and options are `low` `medium` and `high` (Optional)
+    #[structopt(long)]
+    pub include: Vec<String>,
+
+    /// The severity levels to `exclude` from the report. Default is none of the severities and options are `low` `medium` and `high` (Optional)
+    #[structopt(long)]
+    pub exclude: Vec<String>,
+
+    /// The minimum confidence of the findings to include in the report. Can be "High", "Medium" or "Low". Findings of detectors which do not assess their confidence are always included. (Default = Low)
+    #[structopt(long)]
+    pub min_confidence: Option<Confidence>,
+
+    /// Prints the JSON Schema of the "Json" display format and exits.
+    #[structopt(long)]
+    pub json_schema: bool,
+
+    /// Re-analyzes the target whenever one of its Sway source files changes.
+    #[structopt(long)]
+    pub watch: bool,
+
+    /// The path to a directory to cache analysis results in, so unchanged files are not analyzed again. (Optional)
+    #[structopt(long)]
+    pub cache_dir: Option<PathBuf>,
+
+    /// The path to the config file containing the detector options. (Optional; Defaults to "sway-analyzer.toml" in the project directory if it exists)
+    #[structopt(long)]
+    pub config: Option<PathBuf>,
+
+    /// Sets a detector option, overriding the config file. Formatted as `<detector>.<option>=<value>`, e.g. `magic_number.allowed=0,1,10`. (Optional)
+    #[structopt(long = "detector-option")]
+    pub detector_options: Vec<String>,
+
+    /// The paths to shared libraries to load additional detectors from. (Optional)
+    #[structopt(long = "plugin", global = true)]
+    pub plugins: Vec<PathBuf>,
+}
+
+#[derive(StructOpt)]
+pub enum Command {
+    /// Compares two reports produced by the "Json" display format, classifying findings as new, fixed or unchanged.
+    DiffReports {
+        /// The path to the old report.
+        old: PathBuf,
+
+        /// The path to the new report.
+        new: PathBuf,
+    },
+
+    /// Lists the available detectors and the options they accept.
+    ListDetectors,
+
+    /// Searches the target for code matching a template with `$` metavariables, e.g. `transfer($to, $asset, $amt)` or `storage.$f.write($v)`.
+    Query {
+        /// The code template to search for.
+        pattern: QueryPattern,
+    },
+
+    /// Lists the storage fields of the target, along with their types, initial values and the functions which read or write each of them.
+    StorageLayout,
+
+    /// Lists the externally callable abi functions of the target, along with their attributes, access control checks, storage writes, external calls, asset transfers and logged events.
+    Surface,
+}
+
+/// Filter the entries based on the include or exclude options
+pub fn filter_entries(report: &crate::report::Report, options: &Options) -> Vec<(PathBuf, Vec<crate::report::Entry>)> {
+    let mut out = vec![];
+
+    let (input, should_include) = if !options.include.is_empty() {
+        (options.include.as_slice(), true)
+    } else if !options.exclude.is_empty() {
+        (options.exclude.as_slice(), false)
+    } else if options.min_confidence.is_some() {
+        (&[][..], false)
+    } else {
+        return report.entries.clone()
+    };
+
+    let filter_items: HashSet<_> = match input.first() {
+        Some(first_input) if first_input.contains(',') => first_input.split(',').map(str::to_ascii_lowercase).collect(),
+        _ => input.iter().map(|x| x.to_ascii_lowercase()).collect(),
+    };
+
+    // Findings which are less certain than the minimum confidence are always left out,
+    // while findings of detectors which do not assess their confidence are always kept
+    let min_confidence = options.min_confidence.unwrap_or(Confidence::Low);
+
+    for (path, entry) in &report.entries {
+        let filtered: Vec<_> = entry.iter()
+            .filter(|e| should_include == filter_items.contains(&e.severity.to_string().to_ascii_lowercase()))
+            .filter(|e| e.confidence.map(|x| x <= min_confidence).unwrap_or(true))
+            .cloned()
+            .collect();
+
+        if !filtered.is_empty() {
+            out.push((path.clone(), filtered));
+        }
+    }
+    
+    out
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use project::Project;
+
+    pub fn test_detector(name: &str, entry_count: usize) {
+        let options = Options {
+            directory: Some(format!("test/{name}").into()),
+            detectors: vec![name.to_string()],
+            ..Default::default()
+        };
+    
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+    
+        println!("{project}");
+    
+        let mut actual_entry_count = 0;
+    
+        for (_, entries) in project.report.borrow().entries.iter() {
+            actual_entry_count += entries.len();
+        }
+    
+        if entry_count != actual_entry_count {
+            panic!(
+                "Expected {entry_count} {}, found {actual_entry_count} {}",
+                if entry_count == 1 { "entry" } else { "entries" },
+                if actual_entry_count == 1 { "entry" } else { "entries" },
+            );
+        }
+    }
+
+    #[test]
+    fn test_detectors() {
+        let options = Options {
+            directory: Some(PathBuf::from("test/")),
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        println!("{project}");
+    }
+
+    #[test]
+    fn test_include_severities() {
+        // The arbitrary_asset_transfer directory contains 16 low severity entries,
+        // 10 medium severity entries and 10 high severity entries. We should see all 26 low and high severity entries
+        let options = Options {
+            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
+            include: vec!["low".to_string(), "high".to_string()],
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        // Filter the entries based on the include or exclude options
+        let entries  = filter_entries(&project.report.borrow(), &options);
+        project.report.borrow_mut().entries = entries.into_iter().collect();
+
+        assert_eq!(project.report.borrow().entries[0].1.len(), 26);
+
+        println!("{project}");
+    }
+
+    #[test]
+    fn test_include_single_severity() {
+        // The arbitrary_asset_transfer directory contains 16 low severity entries,
+        // 10 medium severity entries and 10 high severity entries. We should only see the low severity entries
+        let options = Options {
+            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
+            include: vec!["low".to_string()],
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        // Filter the entries based on the include or exclude options
+        let entries  = filter_entries(&project.report.borrow(), &options);
+        project.report.borrow_mut().entries = entries.into_iter().collect();
+
+        assert_eq!(project.report.borrow().entries[0].1.len(), 16);
+
+        println!("{project}");
+    }
+
+    #[test]
+    fn test_include_no_severities() {
+        // The arbitrary_asset_transfer directory contains 16 low severity entries,
+        // 10 medium severity entries and 10 high severity entries. We should see all 36 entries
+        let options = Options {
+            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
+            include: vec![],
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        // Filter the entries based on the include or exclude options
+        let entries  = filter_entries(&project.report.borrow(), &options);
+        project.report.borrow_mut().entries = entries.into_iter().collect();
+
+        assert_eq!(project.report.borrow().entries[0].1.len(), 36);
+
+        println!("{project}");
+    }
+
+    #[test]
+    fn test_exclude_severities() {
+        // The arbitrary_asset_transfer directory contains 16 low severity entries,
+        // 10 medium severity entries and 10 high severity entries. We should not see any entries
+        let options = Options {
+            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
+            exclude: vec!["low".to_string(), "medium".to_string(), "high".to_string()],
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        // Filter the entries based on the include or exclude options
+        let entries  = filter_entries(&project.report.borrow(), &options);
+        project.report.borrow_mut().entries = entries.into_iter().collect();
+
+        assert_eq!(project.report.borrow().entries.len(), 0);
+
+        println!("{project}");
+    }
+
+    #[test]
+    fn test_exclude_single_severity() {
+        // The arbitrary_asset_transfer directory contains 16 low severity entries,
+        // 10 medium severity entries and 10 high severity entries. We should only see the medium and high severity entries
+        let options = Options {
+            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
+            exclude: vec!["low".to_string()],
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        // Filter the entries based on the include or exclude options
+        let entries  = filter_entries(&project.report.borrow(), &options);
+        project.report.borrow_mut().entries = entries.into_iter().collect();
+
+        assert_eq!(project.report.borrow().entries[0].1.len(), 20);
+
+        println!("{project}");
+    }
+
+    #[test]
+    fn test_exclude_no_severities() {
+        // The arbitrary_asset_transfer directory contains 16 low severity entries,
+        // 10 medium severity entries and 10 high severity entries. We should see all 36 entries
+        let options = Options {
+            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
+            exclude: vec![],
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        // Filter the entries based on the include or exclude options
+        let entries  = filter_entries(&project.report.borrow(), &options);
+        project.report.borrow_mut().entries = entries;
+
+        assert_eq!(project.report.borrow().entries[0].1.len(), 36);
+
+        println!("{project}");
+    }
+
+    #[test]
+    fn test_min_confidence() {
+        // The weak_prng directory contains 6 high confidence entries on timestamp calls
+        // and 12 medium confidence entries on timestamp variables. We should only see the 6 high confidence entries
+        let options = Options {
+            directory: Some(PathBuf::from("test/weak_prng")),
+            detectors: vec!["weak_prng".to_string()],
+            min_confidence: Some(Confidence::High),
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        // Filter the entries based on the minimum confidence
+        let entries  = filter_entries(&project.report.borrow(), &options);
+        project.report.borrow_mut().entries = entries;
+
+        assert_eq!(project.report.borrow().entries[0].1.len(), 6);
+
+        println!("{project}");
+    }
+
+    #[test]
+    fn test_min_confidence_unassessed() {
+        // The arbitrary_asset_transfer detector does not assess the confidence of its 8 entries, so none of them should be filtered
+        let options = Options {
+            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
+            detectors: vec!["arbitrary_asset_transfer".to_string()],
+            min_confidence: Some(Confidence::High),
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        let entries = filter_entries(&project.report.borrow(), &options);
+        assert_eq!(entries.iter().map(|(_, x)| x.len()).sum::<usize>(), 8);
+    }
+
+    #[test]
+    fn test_markdown_display_format() {
+        let options = Options {
+            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
+            detectors: vec!["arbitrary_asset_transfer".to_string()],
+            display_format: Some(DisplayFormat::Markdown),
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        let output = project.to_string();
+
+        assert!(output.starts_with("# Sway Analyzer Report"));
+        assert!(output.contains("[`arbitrary_asset_transfer`](https://github.com/camden-smallwood/sway-analyzer/wiki/Detectors#arbitrary-asset-transfer)"));
+        assert!(output.contains("```sway"));
+
+        println!("{project}");
+    }
+
+    #[test]
+    fn test_detector_url() {
+        assert_eq!(
+            detectors::detector_url("arbitrary_asset_transfer").as_deref(),
+            Some("https://github.com/camden-smallwood/sway-analyzer/wiki/Detectors#arbitrary-asset-transfer"),
+        );
+
+        // Custom rules and plugin detectors are not documented on the wiki
+        assert_eq!(detectors::detector_url("custom_rule"), None);
+    }
+
+    #[test]
+    fn test_html_display_format() {
+        let options = Options {
+            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
+            detectors: vec!["arbitrary_asset_transfer".to_string()],
+            display_format: Some(DisplayFormat::Html),
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        let output = project.to_string();
+
+        assert!(output.starts_with("<!DOCTYPE html>"));
+        assert!(output.contains("href=\"https://github.com/camden-smallwood/sway-analyzer/wiki/Detectors#arbitrary-asset-transfer\""));
+        assert!(output.contains("<pre><code>"));
+
+        println!("{project}");
+    }
+
+    #[test]
+    fn test_junit_xml_display_format() {
+        let options = Options {
+            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
+            detectors: vec!["arbitrary_asset_transfer".to_string()],
+            display_format: Some(DisplayFormat::JunitXml),
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        let output = project.to_string();
+        let entry_count = project.report.borrow().entry_count("test/arbitrary_asset_transfer/src/main.sw");
+
+        assert!(output.contains("<testsuite name=\"arbitrary_asset_transfer\""));
+        assert_eq!(output.matches("<testcase ").count(), entry_count);
+        assert_eq!(output.matches("<failure ").count(), entry_count);
+
+        println!("{project}");
+    }
+
+    #[test]
+    fn test_code_quality_display_format() {
+        let options = Options {
+            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
+            detectors: vec!["arbitrary_asset_transfer".to_string()],
+            display_format: Some(DisplayFormat::CodeQuality),
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        let value: serde_json::Value = serde_json::from_str(&project.to_string()).unwrap();
+        let issues = value.as_array().unwrap();
+
+        assert_eq!(issues.len(), project.report.borrow().entry_count("test/arbitrary_asset_transfer/src/main.sw"));
+
+        // Every fingerprint should be unique, even for identical findings on different lines
+        let fingerprints = issues.iter().map(|x| x["fingerprint"].as_str().unwrap()).collect::<HashSet<_>>();
+        assert_eq!(fingerprints.len(), issues.len());
+
+        for issue in issues {
+            assert_eq!(issue["check_name"], "arbitrary_asset_transfer");
+            assert!(issue["fingerprint"].is_string());
+            assert!(issue["severity"].is_string());
+            assert!(issue["location"]["lines"]["begin"].is_number());
+        }
+    }
+
+    #[test]
+    fn test_csv_display_format() {
+        let options = Options {
+            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
+            detectors: vec!["arbitrary_asset_transfer".to_string()],
+            display_format: Some(DisplayFormat::Csv),
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        let output = project.to_string();
+        let entry_count = project.report.borrow().entry_count("test/arbitrary_asset_transfer/src/main.sw");
+
+        assert!(output.starts_with("file,line,severity,confidence,detector,message\n"));
+        assert_eq!(output.lines().count(), entry_count + 1);
+        assert_eq!(output.lines().filter(|x| x.contains(",arbitrary_asset_transfer,")).count(), entry_count);
+    }
+
+    #[test]
+    fn test_json_display_format() {
+        // The arbitrary_asset_transfer directory contains 16 low severity entries,
+        // 10 medium severity entries and 10 high severity entries. Excluding low should suppress 16 entries
+        let options = Options {
+            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
+            exclude: vec!["low".to_string()],
+            display_format: Some(DisplayFormat::Json),
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        // Filter the entries based on the include or exclude options
+        let entries  = filter_entries(&project.report.borrow(), &options);
+        project.report.borrow_mut().set_filtered_entries(entries);
+
+        let report: schema::JsonReport = serde_json::from_str(&project.to_string()).unwrap();
+
+        assert_eq!(report.schema_version, schema::SCHEMA_VERSION);
+        assert_eq!(report.metadata.tool_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(report.metadata.files_analyzed, vec!["test/arbitrary_asset_transfer/src/main.sw"]);
+        assert_eq!(report.metadata.suppressed.low, 16);
+        assert_eq!(report.metadata.suppressed.medium, 0);
+        assert_eq!(report.files[0].findings.len(), 20);
+    }
+}
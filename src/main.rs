@@ -1,52 +1,51 @@
-#![allow(clippy::type_complexity)]
-pub mod detectors;
-pub mod error;
-pub mod project;
-pub mod report;
-pub mod scope;
-pub mod utils;
-pub mod visitor;
+use std::{cell::RefCell, path::PathBuf, rc::Rc};
+use structopt::StructOpt;
+use sway_analyzer::{
+    config::OptionValue,
+    detectors::{detector_options, detector_url, DETECTOR_TYPES},
+    diff::{self, ReportDiff},
+    error::Error,
+    filter_entries,
+    plugin,
+    project::Project,
+    query::{self, QueryVisitor},
+    schema,
+    storage_layout::{self, StorageLayoutVisitor},
+    surface::{self, SurfaceVisitor},
+    watch,
+    Command,
+    Options,
+};
 
-use error::Error;
-use project::{DisplayFormat, Project};
-use report::Sorting;
-use std::{collections::HashSet, path::PathBuf};
-use structopt::{clap::AppSettings, StructOpt};
-
-#[derive(Default, StructOpt)]
-#[structopt(global_settings = &[AppSettings::ColoredHelp, AppSettings::ArgRequiredElseHelp])]
-struct Options {
-    /// The display format of the report. Can be "Text" or "Json". (Default = Text)
-    #[structopt(long)]
-    display_format: Option<DisplayFormat>,
-
-    /// The order to sort report entries by. Can be "Line" or "Severity". (Default = Line)
-    #[structopt(long)]
-    sorting: Option<Sorting>,
-
-    /// The path to the Forc project directory. (Optional)
-    #[structopt(long)]
-    directory: Option<PathBuf>,
+fn main() -> Result<(), Error> {
+    let mut options = Options::from_args();
 
-    /// The paths to the Sway source files. (Optional)
-    #[structopt(long)]
-    files: Vec<PathBuf>,
+    // Print the JSON Schema of the report if requested
+    if options.json_schema {
+        println!("{}", serde_json::to_string_pretty(&schema::json_schema()).unwrap());
+        return Ok(());
+    }
 
-    /// The specific detectors to utilize. (Optional; Leave unused for all)
-    #[structopt(long)]
-    detectors: Vec<String>,
+    // Compare two reports if requested
+    if let Some(Command::DiffReports { old, new }) = options.command.as_ref() {
+        let old = diff::load_report(old)?;
+        let new = diff::load_report(new)?;
 
-    /// The severity levels to `include` in the report. Default is all of the severities and options are `low` `medium` and `high` (Optional)
-    #[structopt(long)]
-    include: Vec<String>,
+        let diff = ReportDiff::new(
+            &old,
+            &new,
+            options.display_format.unwrap_or_default(),
+            options.sorting.unwrap_or_default(),
+        );
 
-    /// The severity levels to `exclude` from the report. Default is none of the severities and options are `low` `medium` and `high` (Optional)
-    #[structopt(long)]
-    exclude: Vec<String>,
-}
+        println!("{diff}");
+        return Ok(());
+    }
 
-fn main() -> Result<(), Error> {
-    let mut options = Options::from_args();
+    // List the available detectors if requested
+    if let Some(Command::ListDetectors) = options.command.as_ref() {
+        return list_detectors(&options);
+    }
 
     // Make sure that we have a valid include or exclude input. Both are not allowed.
     if !options.include.is_empty() && !options.exclude.is_empty() {
@@ -67,223 +66,87 @@ fn main() -> Result<(), Error> {
         return Err(Error::Wrapped("No directory or files provided as a target to analyze.".into()));
     }
 
-    // Construct the project and analyze the modules
-    let mut project = Project::try_from(&options)?;
-    project.analyze_modules()?;
-
-    // Filter the entries based on the include or exclude options
-    let entries  = filter_entries(&project.report.borrow(), &options);
-    project.report.borrow_mut().entries = entries.into_iter().collect();
-
-    println!("{project}");
-
-    Ok(())
-}
-
-/// Filter the entries based on the include or exclude options
-fn filter_entries(report: &crate::report::Report, options: &Options) -> Vec<(PathBuf, Vec<crate::report::Entry>)> {
-    let mut out = vec![];
-
-    let (input, should_include) = if !options.include.is_empty() {
-        (options.include.as_slice(), true)
-    } else if !options.exclude.is_empty() {
-        (options.exclude.as_slice(), false)
-    } else {
-        return report.entries.clone()
-    };
-
-    let first_input = &input[0];
-
-    let filter_items: HashSet<_> = if first_input.contains(',') {
-        first_input.split(',').map(str::to_ascii_lowercase).collect()
-    } else {
-        input.iter().map(|x| x.to_ascii_lowercase()).collect()
-    };
-
-    for (path, entry) in &report.entries {
-        let filtered: Vec<_> = entry.iter().filter(|e| should_include == filter_items.contains(&e.severity.to_string().to_ascii_lowercase())).cloned().collect();
-
-        if !filtered.is_empty() {
-            out.push((path.clone(), filtered));
-        }
-    }
-    
-    out
-}
-
-#[cfg(test)]
-pub mod tests {
-    use super::*;
+    // Search the target for the query pattern if requested, which always parses the files instead of using the cache
+    if let Some(Command::Query { pattern }) = options.command.as_ref() {
+        options.cache_dir = None;
 
-    pub fn test_detector(name: &str, entry_count: usize) {
-        let options = Options {
-            directory: Some(format!("test/{name}").into()),
-            detectors: vec![name.to_string()],
-            ..Default::default()
-        };
-    
-        let mut project = Project::try_from(&options).unwrap();
-        project.analyze_modules().unwrap();
-    
-        println!("{project}");
-    
-        let mut actual_entry_count = 0;
-    
-        for (_, entries) in project.report.borrow().entries.iter() {
-            actual_entry_count += entries.len();
-        }
-    
-        if entry_count != actual_entry_count {
-            panic!(
-                "Expected {entry_count} {}, found {actual_entry_count} {}",
-                if entry_count == 1 { "entry" } else { "entries" },
-                if actual_entry_count == 1 { "entry" } else { "entries" },
-            );
-        }
-    }
+        let matches = Rc::new(RefCell::new(vec![]));
 
-    #[test]
-    fn test_detectors() {
-        let options = Options {
-            directory: Some(PathBuf::from("test/")),
-            ..Default::default()
-        };
+        let mut project = Project::try_from(&options)?;
+        project.set_visitor("query", Box::new(QueryVisitor::new(pattern.clone(), matches.clone())));
+        project.analyze_modules()?;
 
-        let mut project = Project::try_from(&options).unwrap();
-        project.analyze_modules().unwrap();
-
-        println!("{project}");
+        println!("{}", query::format_matches(&matches.borrow(), options.display_format.unwrap_or_default()));
+        return Ok(());
     }
 
-    #[test]
-    fn test_include_severities() {
-        // The arbitrary_asset_transfer directory contains 15 low severity entries
-        // and 16 high severity entries. We should see all 31 entries
-        let options = Options {
-            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
-            include: vec!["low".to_string(), "high".to_string()],
-            ..Default::default()
-        };
-
-        let mut project = Project::try_from(&options).unwrap();
-        project.analyze_modules().unwrap();
+    // Summarize the entry points of the target if requested, which always parses the files instead of using the cache
+    if let Some(Command::Surface) = options.command.as_ref() {
+        options.cache_dir = None;
 
-        // Filter the entries based on the include or exclude options
-        let entries  = filter_entries(&project.report.borrow(), &options);
-        project.report.borrow_mut().entries = entries.into_iter().collect();
+        let entry_points = Rc::new(RefCell::new(vec![]));
 
-        assert_eq!(project.report.borrow().entries[0].1.len(), 26);
+        let mut project = Project::try_from(&options)?;
+        project.set_visitor("surface", Box::new(SurfaceVisitor::new(entry_points.clone())));
+        project.analyze_modules()?;
 
-        println!("{project}");
+        println!("{}", surface::format_entry_points(&entry_points.borrow(), options.display_format.unwrap_or_default()));
+        return Ok(());
     }
 
-    #[test]
-    fn test_include_single_severity() {
-        // The arbitrary_asset_transfer directory contains 15 low severity entries
-        // and 16 high severity entries. We should only see the low severity entries
-        let options = Options {
-            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
-            include: vec!["low".to_string()],
-            ..Default::default()
-        };
+    // Export the storage layout of the target if requested, which always parses the files instead of using the cache
+    if let Some(Command::StorageLayout) = options.command.as_ref() {
+        options.cache_dir = None;
 
-        let mut project = Project::try_from(&options).unwrap();
-        project.analyze_modules().unwrap();
+        let fields = Rc::new(RefCell::new(vec![]));
 
-        // Filter the entries based on the include or exclude options
-        let entries  = filter_entries(&project.report.borrow(), &options);
-        project.report.borrow_mut().entries = entries.into_iter().collect();
+        let mut project = Project::try_from(&options)?;
+        project.set_visitor("storage_layout", Box::new(StorageLayoutVisitor::new(fields.clone())));
+        project.analyze_modules()?;
 
-        assert_eq!(project.report.borrow().entries[0].1.len(), 16);
-
-        println!("{project}");
+        println!("{}", storage_layout::format_storage_layout(&fields.borrow(), options.display_format.unwrap_or_default()));
+        return Ok(());
     }
 
-    #[test]
-    fn test_include_no_severities() {
-        // The arbitrary_asset_transfer directory contains 15 low severity entries
-        // and 16 high severity entries. We should only see the low severity entries
-        let options = Options {
-            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
-            include: vec![],
-            ..Default::default()
-        };
-
-        let mut project = Project::try_from(&options).unwrap();
-        project.analyze_modules().unwrap();
+    // Construct the project and analyze the modules
+    let mut project = Project::try_from(&options)?;
+    project.analyze_modules()?;
 
-        // Filter the entries based on the include or exclude options
-        let entries  = filter_entries(&project.report.borrow(), &options);
-        project.report.borrow_mut().entries = entries.into_iter().collect();
+    // Filter the entries based on the include or exclude options
+    let entries  = filter_entries(&project.report.borrow(), &options);
+    project.report.borrow_mut().set_filtered_entries(entries);
 
-        assert_eq!(project.report.borrow().entries[0].1.len(), 26);
+    println!("{project}");
 
-        println!("{project}");
+    // Keep re-analyzing the project on changes if requested
+    if options.watch {
+        watch::watch(&options, project)?;
     }
 
-    #[test]
-    fn test_exclude_severities() {
-        // The arbitrary_asset_transfer directory contains 15 low severity entries
-        // and 16 high severity entries. We should not see any entries
-        let options = Options {
-            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
-            exclude: vec!["low".to_string(), "high".to_string()],
-            ..Default::default()
-        };
-
-        let mut project = Project::try_from(&options).unwrap();
-        project.analyze_modules().unwrap();
-
-        // Filter the entries based on the include or exclude options
-        let entries  = filter_entries(&project.report.borrow(), &options);
-        project.report.borrow_mut().entries = entries.into_iter().collect();
-
-        assert_eq!(project.report.borrow().entries.len(), 0);
+    Ok(())
+}
 
-        println!("{project}");
+/// Prints the available detectors along with the documentation and default values of their options, followed by the detectors of the supplied plugins
+fn list_detectors(options: &Options) -> Result<(), Error> {
+    for (name, _) in DETECTOR_TYPES {
+        println!("{name} ({})", detector_url(name).unwrap());
+
+        for option in detector_options(name) {
+            println!(
+                "    {name}.{} ({}, default = \"{}\"): {}",
+                option.name,
+                option.default.type_name(),
+                OptionValue::from(&option.default),
+                option.description,
+            );
+        }
     }
 
-    #[test]
-    fn test_exclude_single_severity() {
-        // The arbitrary_asset_transfer directory contains 15 low severity entries
-        // and 16 high severity entries. We should only see the high severity entries
-        let options = Options {
-            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
-            exclude: vec!["low".to_string()],
-            ..Default::default()
-        };
-
-        let mut project = Project::try_from(&options).unwrap();
-        project.analyze_modules().unwrap();
-
-        // Filter the entries based on the include or exclude options
-        let entries  = filter_entries(&project.report.borrow(), &options);
-        project.report.borrow_mut().entries = entries.into_iter().collect();
-
-        assert_eq!(project.report.borrow().entries[0].1.len(), 10);
-
-        println!("{project}");
+    for path in options.plugins.iter() {
+        for (name, _) in plugin::load_plugin(path)? {
+            println!("{name} (plugin: {})", path.to_string_lossy());
+        }
     }
 
-    #[test]
-    fn test_exclude_no_severities() {
-        // The arbitrary_asset_transfer directory contains 15 low severity entries
-        // and 16 high severity entries. We should not see any entries
-        let options = Options {
-            directory: Some(PathBuf::from("test/arbitrary_asset_transfer")),
-            exclude: vec![],
-            ..Default::default()
-        };
-
-        let mut project = Project::try_from(&options).unwrap();
-        project.analyze_modules().unwrap();
-
-        // Filter the entries based on the include or exclude options
-        let entries  = filter_entries(&project.report.borrow(), &options);
-        project.report.borrow_mut().entries = entries;
-
-        assert_eq!(project.report.borrow().entries[0].1.len(), 26);
-
-        println!("{project}");
-    }
+    Ok(())
 }
@@ -0,0 +1,219 @@
+//! Loading of detectors from plugins, which are shared libraries exporting a [`PluginDeclaration`].
+//!
+//! A plugin is a `cdylib` crate depending on `sway-analyzer`, which declares its detectors with [`declare_plugin!`](crate::declare_plugin):
+//!
+//! ```ignore
+//! sway_analyzer::declare_plugin!(
+//!     "my_detector" => MyDetectorVisitor,
+//! );
+//! ```
+//!
+//! Since the detectors are passed across the plugin boundary as `Box<dyn AstVisitor>`, a plugin has to be built
+//! with the same version of `sway-analyzer`, the same versions of `sway-ast` and `sway-types`, and the same Rust toolchain
+//! as the binary loading it. These versions are embedded in the declaration and checked before any detector is created.
+
+use crate::{detectors::DetectorEntry, error::Error};
+use std::{
+    ffi::{c_char, CStr},
+    path::Path,
+};
+
+/// The version of the plugin interface, which is incremented whenever the layout of [`PluginDeclaration`] changes.
+pub const PLUGIN_API_VERSION: u32 = 2;
+
+/// The NUL-terminated version of `sway-analyzer`, which plugins are built against.
+pub const ANALYZER_VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "\0");
+
+/// The NUL-terminated version of the Rust compiler, since trait objects have no stable layout across toolchains.
+pub const RUSTC_VERSION: &str = concat!(env!("SWAY_ANALYZER_RUSTC_VERSION"), "\0");
+
+/// The NUL-terminated hash of the locked versions of `sway-ast` and `sway-types`, since detectors receive their types.
+pub const SWAY_VERSIONS_HASH: &str = concat!(env!("SWAY_ANALYZER_SWAY_VERSIONS_HASH"), "\0");
+
+/// The version embedded by the build script when a version can not be determined, e.g. when no `Cargo.lock` is found.
+/// Plugins are never loaded if either side has an unknown version, since their compatibility can not be checked.
+const UNKNOWN_VERSION: &str = "unknown";
+
+/// The name of the symbol of the [`PluginDeclaration`] exported by plugins.
+pub const PLUGIN_DECLARATION_SYMBOL: &[u8] = b"SWAY_ANALYZER_PLUGIN\0";
+
+/// The declaration exported by plugins as the `SWAY_ANALYZER_PLUGIN` symbol.
+///
+/// The `api_version` field is always first, so the declaration of an incompatible plugin can be rejected before reading the rest of it.
+#[repr(C)]
+pub struct PluginDeclaration {
+    pub api_version: u32,
+    pub analyzer_version: *const c_char,
+    pub rustc_version: *const c_char,
+    pub sway_versions_hash: *const c_char,
+    pub detectors: extern "C" fn() -> PluginDetectors,
+}
+
+// SAFETY: The declaration only points to immutable static data of the plugin.
+unsafe impl Sync for PluginDeclaration {}
+
+/// The detectors of a plugin, in the same shape as `DETECTOR_TYPES`.
+#[repr(C)]
+pub struct PluginDetectors {
+    pub entries: *const DetectorEntry,
+    pub len: usize,
+}
+
+/// Declares the detectors of a plugin, exporting them as the `SWAY_ANALYZER_PLUGIN` symbol.
+#[macro_export]
+macro_rules! declare_plugin {
+    ($($name:literal => $visitor:ty),* $(,)?) => {
+        #[no_mangle]
+        pub static SWAY_ANALYZER_PLUGIN: $crate::plugin::PluginDeclaration = {
+            extern "C" fn detectors() -> $crate::plugin::PluginDetectors {
+                static ENTRIES: &[$crate::detectors::DetectorEntry] = &[
+                    $(($name, || Box::<$visitor>::default())),*
+                ];
+
+                $crate::plugin::PluginDetectors {
+                    entries: ENTRIES.as_ptr(),
+                    len: ENTRIES.len(),
+                }
+            }
+
+            $crate::plugin::PluginDeclaration {
+                api_version: $crate::plugin::PLUGIN_API_VERSION,
+                analyzer_version: $crate::plugin::ANALYZER_VERSION.as_ptr() as *const ::std::ffi::c_char,
+                rustc_version: $crate::plugin::RUSTC_VERSION.as_ptr() as *const ::std::ffi::c_char,
+                sway_versions_hash: $crate::plugin::SWAY_VERSIONS_HASH.as_ptr() as *const ::std::ffi::c_char,
+                detectors,
+            }
+        };
+    };
+}
+
+/// Loads the detectors of the plugin at the supplied `path`, rejecting plugins built for a different plugin interface,
+/// version of `sway-analyzer`, Rust toolchain or versions of the Sway crates.
+pub fn load_plugin<P: AsRef<Path>>(path: P) -> Result<Vec<DetectorEntry>, Error> {
+    let path = path.as_ref();
+    let error = |message: String| Error::PluginLoadFailed(path.into(), message);
+
+    // SAFETY: Loading a library runs its initialization code, which is trusted since the user explicitly supplied the plugin.
+    let library = unsafe { libloading::Library::new(path) }.map_err(|e| error(e.to_string()))?;
+
+    // SAFETY: The symbol is declared by `declare_plugin!`, and only its first field is read before the version handshake.
+    let declaration = unsafe {
+        let symbol = library.get::<*const PluginDeclaration>(PLUGIN_DECLARATION_SYMBOL).map_err(|e| error(e.to_string()))?;
+        &**symbol
+    };
+
+    check_declaration(declaration).map_err(error)?;
+
+    let detectors = (declaration.detectors)();
+
+    // SAFETY: The entries point to a static slice of the plugin, which stays loaded below.
+    let entries = unsafe { std::slice::from_raw_parts(detectors.entries, detectors.len) }.to_vec();
+
+    // Never unload the library, since the detectors reference its code and static data
+    std::mem::forget(library);
+
+    Ok(entries)
+}
+
+/// Checks that the supplied `declaration` was built for the same plugin interface, version of `sway-analyzer`,
+/// Rust toolchain and versions of the Sway crates as the binary loading it.
+fn check_declaration(declaration: &PluginDeclaration) -> Result<(), String> {
+    if declaration.api_version != PLUGIN_API_VERSION {
+        return Err(format!("Expected plugin API version {PLUGIN_API_VERSION}, found {}", declaration.api_version));
+    }
+
+    // SAFETY: The versions are NUL-terminated static strings of the plugin.
+    let (analyzer_version, rustc_version, sway_versions_hash) = unsafe {
+        (
+            CStr::from_ptr(declaration.analyzer_version).to_string_lossy(),
+            CStr::from_ptr(declaration.rustc_version).to_string_lossy(),
+            CStr::from_ptr(declaration.sway_versions_hash).to_string_lossy(),
+        )
+    };
+
+    let expected_version = ANALYZER_VERSION.trim_end_matches('\0');
+
+    if analyzer_version != expected_version {
+        return Err(format!("The plugin was built for sway-analyzer {analyzer_version}, expected {expected_version}"));
+    }
+
+    let expected_rustc_version = RUSTC_VERSION.trim_end_matches('\0');
+
+    if rustc_version == UNKNOWN_VERSION || expected_rustc_version == UNKNOWN_VERSION {
+        return Err("The Rust toolchain the plugin or sway-analyzer was built with is unknown".into());
+    }
+
+    if rustc_version != expected_rustc_version {
+        return Err(format!("The plugin was built with {rustc_version}, expected {expected_rustc_version}"));
+    }
+
+    let expected_sway_versions_hash = SWAY_VERSIONS_HASH.trim_end_matches('\0');
+
+    if sway_versions_hash == UNKNOWN_VERSION || expected_sway_versions_hash == UNKNOWN_VERSION {
+        return Err("The versions of sway-ast and sway-types the plugin or sway-analyzer was built against could not be determined from a Cargo.lock".into());
+    }
+
+    if sway_versions_hash != expected_sway_versions_hash {
+        return Err("The plugin was built against different versions of sway-ast and sway-types".into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern "C" fn no_detectors() -> PluginDetectors {
+        PluginDetectors {
+            entries: std::ptr::null(),
+            len: 0,
+        }
+    }
+
+    fn declaration(api_version: u32, analyzer_version: &'static str, rustc_version: &'static str, sway_versions_hash: &'static str) -> PluginDeclaration {
+        PluginDeclaration {
+            api_version,
+            analyzer_version: analyzer_version.as_ptr() as *const c_char,
+            rustc_version: rustc_version.as_ptr() as *const c_char,
+            sway_versions_hash: sway_versions_hash.as_ptr() as *const c_char,
+            detectors: no_detectors,
+        }
+    }
+
+    #[test]
+    fn test_check_declaration() {
+        let result = check_declaration(&declaration(PLUGIN_API_VERSION, ANALYZER_VERSION, RUSTC_VERSION, SWAY_VERSIONS_HASH));
+
+        // The declaration of the binary itself is only rejected if its versions are unknown
+        let is_unknown = [RUSTC_VERSION, SWAY_VERSIONS_HASH].iter().any(|x| x.trim_end_matches('\0') == UNKNOWN_VERSION);
+        assert_eq!(result.is_ok(), !is_unknown);
+    }
+
+    #[test]
+    fn test_check_declaration_api_version() {
+        let result = check_declaration(&declaration(PLUGIN_API_VERSION + 1, ANALYZER_VERSION, RUSTC_VERSION, SWAY_VERSIONS_HASH));
+        assert!(result.unwrap_err().contains("plugin API version"));
+    }
+
+    #[test]
+    fn test_check_declaration_versions() {
+        let result = check_declaration(&declaration(PLUGIN_API_VERSION, "0.0.0\0", RUSTC_VERSION, SWAY_VERSIONS_HASH));
+        assert!(result.unwrap_err().contains("sway-analyzer 0.0.0"));
+
+        let result = check_declaration(&declaration(PLUGIN_API_VERSION, ANALYZER_VERSION, "rustc 0.0.0\0", SWAY_VERSIONS_HASH));
+        assert!(result.is_err());
+
+        let result = check_declaration(&declaration(PLUGIN_API_VERSION, ANALYZER_VERSION, RUSTC_VERSION, "0000\0"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_check_declaration_unknown_versions() {
+        let result = check_declaration(&declaration(PLUGIN_API_VERSION, ANALYZER_VERSION, "unknown\0", SWAY_VERSIONS_HASH));
+        assert!(result.is_err());
+
+        let result = check_declaration(&declaration(PLUGIN_API_VERSION, ANALYZER_VERSION, RUSTC_VERSION, "unknown\0"));
+        assert!(result.is_err());
+    }
+}
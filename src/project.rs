@@ -1,11 +1,27 @@
-use crate::{detectors::*, error::Error, report::Report, scope::AstScope, visitor::*, Options};
+use crate::{
+    cache::AnalysisCache,
+    config::{Config, DetectorOptions},
+    detectors::*,
+    error::Error,
+    forc::{self, PackageGraph, ProgramKind},
+    formats,
+    plugin,
+    report::Report,
+    rules::{CustomRule, CustomRuleVisitor},
+    schema::JsonReport,
+    scope::AstScope,
+    utils,
+    visitor::*,
+    Options,
+};
 use std::{
     cell::RefCell,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt::Display,
     path::{Path, PathBuf},
     rc::Rc,
     str::FromStr,
+    time::{Duration, Instant},
 };
 use sway_ast::Module;
 use sway_ast_stubs::AstResolver;
@@ -16,6 +32,11 @@ pub enum DisplayFormat {
     #[default]
     Text,
     Json,
+    Markdown,
+    Html,
+    JunitXml,
+    CodeQuality,
+    Csv,
 }
 
 impl FromStr for DisplayFormat {
@@ -25,6 +46,11 @@ impl FromStr for DisplayFormat {
         match s.to_ascii_lowercase().as_str() {
             "text" => Ok(Self::Text),
             "json" => Ok(Self::Json),
+            "markdown" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            "junitxml" => Ok(Self::JunitXml),
+            "codequality" => Ok(Self::CodeQuality),
+            "csv" => Ok(Self::Csv),
             _ => Err(Error::InvalidDisplayFormat(s.to_string())),
         }
     }
@@ -34,26 +60,23 @@ impl FromStr for DisplayFormat {
 pub struct Project<'a> {
     display_format: DisplayFormat,
     line_ranges: HashMap<PathBuf, Vec<(usize, usize)>>,
+    sources: HashMap<PathBuf, String>,
     modules: Rc<RefCell<HashMap<PathBuf, Module>>>,
     detectors: Rc<RefCell<AstVisitorRecursive<'a>>>,
+    analysis_duration: Duration,
+    cache: Option<AnalysisCache>,
+    is_cached: bool,
+    detector_options: DetectorOptions,
+    custom_rules: Vec<CustomRule>,
+    plugin_detectors: Vec<DetectorEntry>,
+    packages: PackageGraph,
     pub report: Rc<RefCell<Report>>,
     pub resolver: Rc<RefCell<AstResolver>>,
 }
 
 impl Display for Project<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.display_format {
-            DisplayFormat::Text => {
-                write!(f, "{}", self.report.borrow())?;
-            }
-
-            DisplayFormat::Json => {
-                let value = serde_json::to_value(self.report.borrow().clone()).unwrap();
-                write!(f, "{}", value)?;
-            }
-        }
-
-        Ok(())
+        formats::write_report(f, self.display_format, &self.report.borrow(), &self.sources, || self.json_report())
     }
 }
 
@@ -61,88 +84,346 @@ impl TryFrom<&Options> for Project<'_> {
     type Error = Error;
 
     fn try_from(options: &Options) -> Result<Self, Self::Error> {
+        let config = Config::try_from(options)?;
+
         let mut project = Project {
             display_format: options.display_format.unwrap_or_default(),
+            detector_options: config.detector_options,
+            custom_rules: config.rules,
             report: Rc::new(RefCell::new(Report {
                 sorting: options.sorting.unwrap_or_default(),
                 ..Default::default()
             })),
             ..Default::default()
         };
-    
-        if let Some(path) = options.directory.as_ref() {
-            if !path.is_dir() || !path.exists() {
-                // TODO
+
+        // Load the detectors of the supplied plugins, which must not shadow any other detector or custom rule
+        for path in options.plugins.iter() {
+            for (detector_name, create_detector) in plugin::load_plugin(path)? {
+                let is_duplicate = DETECTOR_TYPES.iter().chain(project.plugin_detectors.iter()).any(|(name, _)| *name == detector_name)
+                    || project.custom_rules.iter().any(|x| x.name == detector_name);
+
+                if is_duplicate {
+                    return Err(Error::PluginLoadFailed(path.clone(), format!("Detector `{detector_name}` is already defined")));
+                }
+
+                project.plugin_detectors.push((detector_name, create_detector));
             }
+        }
     
-            fn parse_dir<P: AsRef<Path>>(project: &mut Project, path: P) -> Result<(), Error> {
-                for entry in path.as_ref().read_dir().map_err(|e| Error::Wrapped(Box::new(e)))? {
-                    let Ok(entry) = entry else { continue };
-                    let path = entry.path();
-    
-                    let forc_toml_path = PathBuf::from(format!("{}Forc.toml", path.to_string_lossy()));
-    
-                    if forc_toml_path.is_file() && forc_toml_path.exists() {
-                        let src_path = PathBuf::from(format!("{}src", path.to_string_lossy()));
-                
-                        if src_path.is_dir() && src_path.exists() {
-                            parse_dir(project, src_path)?;
-                            continue;
-                        }    
-                    }
+        fn collect_dir<P: AsRef<Path>>(paths: &mut Vec<PathBuf>, path: P) -> Result<(), Error> {
+            for entry in path.as_ref().read_dir().map_err(|e| Error::Wrapped(Box::new(e)))? {
+                let Ok(entry) = entry else { continue };
+                let path = entry.path();
+
+                let forc_toml_path = PathBuf::from(format!("{}Forc.toml", path.to_string_lossy()));
+
+                if forc_toml_path.is_file() && forc_toml_path.exists() {
+                    let src_path = PathBuf::from(format!("{}src", path.to_string_lossy()));
             
-                    if path.is_dir() {
-                        parse_dir(project, path)?;
-                    } else if path.is_file() && path.extension().map(|x| x == "sw").unwrap_or(false) {
-                        project.parse_file(path)?;
-                    }
+                    if src_path.is_dir() && src_path.exists() {
+                        collect_dir(paths, src_path)?;
+                        continue;
+                    }    
                 }
-    
-                Ok(())
+        
+                if path.is_dir() && !forc::is_ignored_directory(&path) {
+                    collect_dir(paths, path)?;
+                } else if path.is_file() && path.extension().map(|x| x == "sw").unwrap_or(false) {
+                    paths.push(path);
+                }
+            }
+
+            Ok(())
+        }
+
+        let mut paths = vec![];
+
+        if let Some(path) = options.directory.as_ref() {
+            if !path.is_dir() || !path.exists() {
+                // TODO
             }
     
-            parse_dir(&mut project, path)?;
+            collect_dir(&mut paths, path)?;
+            project.packages.load_directory(path)?;
         }
     
         for path in options.files.iter() {
-            project.parse_file(path)?;
+            if let Some(package_root) = utils::get_package_root(path) {
+                project.packages.load_manifest(package_root)?;
+            }
+
+            paths.push(path.clone());
+        }
+
+        // Include the sources of local dependencies which are outside of the analysis targets
+        let dependency_roots = project.packages.packages.iter().flat_map(|x| x.dependencies.clone()).collect::<Vec<_>>();
+
+        for package in project.packages.packages.iter() {
+            if !dependency_roots.contains(&package.root) || paths.iter().any(|x| forc::normalize_path_lexically(x).starts_with(&package.root)) {
+                continue;
+            }
+
+            let src_path = package.src_path();
+
+            if src_path.is_dir() {
+                collect_dir(&mut paths, src_path)?;
+            }
         }
 
         // Check if detectors are valid and if not, return an error with the detector name that is not valid.
         if !options.detectors.is_empty() {
             for detector in &options.detectors {
-                if !DETECTOR_TYPES.iter().any(|(name, _)| detector == *name) {
+                if !DETECTOR_TYPES.iter().chain(project.plugin_detectors.iter()).any(|(name, _)| detector == *name) && !project.custom_rules.iter().any(|x| x.name == *detector) {
                     return Err(Error::Wrapped(format!("Detector not found in detectors collection : {detector}").into()));
                 }
             }
         }
     
-        for &(detector_name, create_detector) in DETECTOR_TYPES {
+        for &(detector_name, create_detector) in DETECTOR_TYPES.iter().chain(project.plugin_detectors.iter()) {
             if options.detectors.is_empty() || options.detectors.iter().any(|v| v == detector_name) {
-                project.detectors.borrow_mut().visitors.push(create_detector());
+                project.detectors.borrow_mut().visitors.push((detector_name, create_detector()));
             }
         }
+
+        // Register the custom rules of the config file alongside the detectors
+        for rule in project.custom_rules.iter() {
+            if options.detectors.is_empty() || options.detectors.contains(&rule.name) {
+                let rule_name: &'static str = Box::leak(rule.name.clone().into_boxed_str());
+                project.detectors.borrow_mut().visitors.push((rule_name, Box::new(CustomRuleVisitor::new(rule.clone()))));
+            }
+        }
+
+        // Load the analysis cache if requested, which is not used in watch mode since everything is kept in memory there
+        if let Some(cache_dir) = options.cache_dir.as_ref().filter(|_| !options.watch) {
+            let (detector_names, cross_module_detectors) = {
+                let detectors = project.detectors.borrow();
+
+                let detector_names = detectors.visitors.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+
+                let cross_module_detectors = detectors.visitors.iter()
+                    .filter(|(name, visitor)| project.is_cross_module_visitor(name, visitor.as_ref()))
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>();
+
+                (detector_names, cross_module_detectors)
+            };
+
+            // The findings also depend on the Forc manifests and lock files of the packages and on the loaded plugins
+            let mut key_files = project.packages.packages.iter()
+                .flat_map(|x| [x.root.join("Forc.toml"), x.root.join("Forc.lock")])
+                .chain(options.directory.iter().flat_map(|x| [x.join("Forc.toml"), x.join("Forc.lock")]))
+                .chain(options.plugins.iter().cloned())
+                .collect::<Vec<_>>();
+
+            key_files.sort();
+            key_files.dedup();
+
+            project.cache = Some(AnalysisCache::load(
+                cache_dir,
+                &detector_names,
+                &cross_module_detectors,
+                &project.detector_options,
+                &project.custom_rules,
+                &key_files,
+            ));
+        }
+
+        project.parse_files(paths)?;
     
         Ok(project)
     }
 }
 
 impl Project<'_> {
+    /// Gets the modules that have been parsed for the project.
+    #[inline]
+    pub fn modules(&self) -> Rc<RefCell<HashMap<PathBuf, Module>>> {
+        self.modules.clone()
+    }
+
+    /// Replaces the detectors of the project with the supplied `visitor`, e.g. to run a query instead of the detectors.
+    pub fn set_visitor(&mut self, name: &'static str, visitor: Box<dyn AstVisitor>) {
+        self.detectors.borrow_mut().visitors = vec![(name, visitor)];
+    }
+
+    /// Gets the graph of the Forc packages of the project.
+    #[inline]
+    pub fn packages(&self) -> &PackageGraph {
+        &self.packages
+    }
+
+    /// Gets the values of the detector options, which detectors should read instead of hard-coding their parameters.
+    #[inline]
+    pub fn detector_options(&self) -> &DetectorOptions {
+        &self.detector_options
+    }
+
+    /// Gets the kind of program of the package containing the file at the supplied `path`, which is declared by the parsed entry file of the package.
+    pub fn program_kind<P: AsRef<Path>>(&self, path: P) -> Option<ProgramKind> {
+        let package = self.packages.package_for_path(path)?;
+        let modules = self.modules.borrow();
+
+        modules.iter()
+            .find(|(path, _)| forc::normalize_path_lexically(path) == package.entry_path)
+            .map(|(_, module)| ProgramKind::from_module(module))
+    }
+
+    /// Checks if the findings of the visitor with the supplied `name` can depend on the contents of other modules.
+    ///
+    /// Plugin detectors and custom rules are always treated as cross-module, since what they inspect is not known ahead of time.
+    fn is_cross_module_visitor(&self, name: &str, visitor: &dyn AstVisitor) -> bool {
+        visitor.is_cross_module()
+            || self.plugin_detectors.iter().any(|(x, _)| *x == name)
+            || self.custom_rules.iter().any(|x| x.name == name)
+    }
+
+    /// Gets the roots of the packages linked to the package containing the file at the supplied `path`, or `None` if the file is not part of a package.
+    fn linked_package_roots<P: AsRef<Path>>(&self, path: P) -> Vec<Option<PathBuf>> {
+        let Some(package) = self.packages.package_for_path(path) else { return vec![None] };
+
+        self.packages.get_linked_packages(package).into_iter()
+            .map(|x| Some(x.root.clone()))
+            .collect()
+    }
+
+    /// Normalizes the separators of the supplied `path`.
+    fn normalize_path<P: AsRef<Path>>(path: P) -> PathBuf {
+        PathBuf::from(path.as_ref().to_string_lossy().replace("\\\\", "\\").replace("//", "/"))
+    }
+
+    /// Attempts to parse the files from the supplied `paths`, using the cached findings instead of parsing the unchanged files
+    /// which are not part of a package linked to a changed file.
+    fn parse_files(&mut self, paths: Vec<PathBuf>) -> Result<(), Error> {
+        let Some(cache) = self.cache.as_ref() else {
+            for path in paths {
+                self.parse_file(path)?;
+            }
+
+            return Ok(());
+        };
+
+        let mut sources = HashMap::new();
+
+        for path in paths {
+            let path = Self::normalize_path(path);
+            let source = std::fs::read_to_string(path.clone()).map_err(|e| Error::Wrapped(Box::new(e)))?;
+            sources.insert(path, source);
+        }
+
+        if let Some(entries) = cache.get_all_entries(&sources) {
+            for (path, source) in sources {
+                self.load_line_ranges(path.clone(), source.as_str());
+                self.sources.insert(path, source);
+            }
+
+            let mut report = self.report.borrow_mut();
+
+            for (path, entry) in entries {
+                report.insert_entry(path, entry);
+            }
+
+            self.is_cached = true;
+            return Ok(());
+        }
+
+        // Only parse the unchanged files of the packages linked to a changed or removed file, since the findings of the
+        // cross-module detectors in those files can depend on the changed files
+        let unchanged_paths = cache.get_unchanged_paths(&sources);
+
+        let affected_package_roots = sources.keys()
+            .filter(|path| !unchanged_paths.contains(*path))
+            .cloned()
+            .chain(cache.get_removed_paths(&sources))
+            .flat_map(|path| self.linked_package_roots(path))
+            .collect::<HashSet<_>>();
+
+        let cached_paths = unchanged_paths.into_iter()
+            .filter(|path| !affected_package_roots.contains(&self.packages.package_for_path(path).map(|x| x.root.clone())))
+            .collect::<HashSet<_>>();
+
+        let cached_entries = cached_paths.iter()
+            .flat_map(|path| cache.get_entries(path).into_iter().map(|entry| (path.clone(), entry)))
+            .collect::<Vec<_>>();
+
+        for (path, source) in sources {
+            if cached_paths.contains(&path) {
+                self.load_line_ranges(path.clone(), source.as_str());
+                self.sources.insert(path, source);
+                continue;
+            }
+
+            self.parse_source(path, source)?;
+        }
+
+        let mut report = self.report.borrow_mut();
+
+        for (path, entry) in cached_entries {
+            report.insert_entry(path, entry);
+        }
+
+        Ok(())
+    }
+
     /// Attempts to parse the file from the supplied `path`.
     pub fn parse_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
-        let path = PathBuf::from(path.as_ref().to_string_lossy().replace("\\\\", "\\").replace("//", "/"));
+        let path = Self::normalize_path(path);
         let source = std::fs::read_to_string(path.clone()).map_err(|e| Error::Wrapped(Box::new(e)))?;
         
-        self.load_line_ranges(path.clone(), source.as_str());
+        self.parse_source(path, source)
+    }
 
+    /// Attempts to parse the supplied `source` of the file at the supplied `path`.
+    fn parse_source(&mut self, path: PathBuf, source: String) -> Result<(), Error> {
         let handler = sway_error::handler::Handler::default();
         let module = sway_parse::parse_file(&handler, source.as_str().into(), None, Default::default()).map_err(|_| Error::ParseFailed(path.clone()))?;
 
+        // Discard the line ranges of a previous parse of the file
+        self.line_ranges.remove(&path);
+        self.load_line_ranges(path.clone(), source.as_str());
+
+        self.sources.insert(path.clone(), source);
         self.modules.borrow_mut().insert(path, module.value);
 
         Ok(())
     }
 
+    /// Removes the previously-parsed file at the supplied `path` from the project.
+    pub fn remove_file<P: AsRef<Path>>(&mut self, path: P) {
+        let path = path.as_ref();
+
+        self.line_ranges.remove(path);
+        self.sources.remove(path);
+        self.modules.borrow_mut().remove(path);
+    }
+
+    /// Discards the report and the state of the detectors, so the modules can be analyzed again.
+    pub fn reset_analysis(&mut self) {
+        let mut detectors = self.detectors.borrow_mut();
+        let detector_names = detectors.visitors.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+
+        detectors.visitors.clear();
+
+        for &(detector_name, create_detector) in DETECTOR_TYPES.iter().chain(self.plugin_detectors.iter()) {
+            if detector_names.contains(&detector_name) {
+                detectors.visitors.push((detector_name, create_detector()));
+            }
+        }
+
+        for &detector_name in detector_names.iter() {
+            if let Some(rule) = self.custom_rules.iter().find(|x| x.name == detector_name) {
+                detectors.visitors.push((detector_name, Box::new(CustomRuleVisitor::new(rule.clone()))));
+            }
+        }
+
+        let sorting = self.report.borrow().sorting;
+
+        *self.report.borrow_mut() = Report {
+            sorting,
+            ..Default::default()
+        };
+    }
+
     /// Loads line ranges in a specfic file `path` from the provided `source` text.
     fn load_line_ranges(&mut self, path: PathBuf, source: &str) {
         let mut line_range = (0usize, 0usize);
@@ -178,21 +459,41 @@ impl Project<'_> {
         Err(Error::LineNotFound(path.into(), offset))
     }
 
+    /// Creates a versioned JSON report of the findings and analysis information of the project.
+    pub fn json_report(&self) -> JsonReport {
+        let detectors = self.detectors.borrow().visitors.iter().map(|(name, _)| name.to_string()).collect();
+
+        let mut files_analyzed = self.sources.keys().cloned().collect::<Vec<_>>();
+        files_analyzed.sort();
+
+        JsonReport::new(&self.report.borrow(), detectors, files_analyzed, self.analysis_duration)
+    }
+
     /// Attempts to analyze all of the parsed files.
     pub fn analyze_modules(&mut self) -> Result<(), Error> {
+        // The findings were already loaded from the cache if none of the files changed
+        if self.is_cached {
+            return Ok(());
+        }
+
+        let start_time = Instant::now();
+
         let modules = self.modules.clone();
         let detectors = self.detectors.clone();
 
         let mut module_paths = modules.borrow().keys().cloned().collect::<Vec<_>>();
         module_paths.sort();
 
+        let unchanged_paths = self.cache.as_ref().map(|x| x.get_unchanged_paths(&self.sources)).unwrap_or_default();
+
         // let core = AstScope::from_library(self, "core");
         
         // let std = AstScope::from_library(self, "std");
         
         for path in module_paths {
-            println!("{}", path.to_string_lossy());
-            
+            // Report progress on stderr, so stdout only contains the report
+            eprintln!("{}", path.to_string_lossy());
+
             let modules = modules.borrow();
             let module = modules.get(&path).unwrap();
 
@@ -203,8 +504,48 @@ impl Project<'_> {
 
             let scope = Rc::new(RefCell::new(AstScope::default()));
 
+            // Only run the cross-module detectors on unchanged files, since the findings of the other detectors are cached
+            let parked_visitors = if unchanged_paths.contains(&path) {
+                let visitors = std::mem::take(&mut detectors.borrow_mut().visitors);
+                let (active_visitors, parked_visitors): (Vec<_>, Vec<_>) = visitors.into_iter().partition(|(name, visitor)| self.is_cross_module_visitor(name, visitor.as_ref()));
+                detectors.borrow_mut().visitors = active_visitors;
+                parked_visitors
+            } else {
+                vec![]
+            };
+
             detectors.borrow_mut().visit_module(&context, scope.clone(), self)?;
             detectors.borrow_mut().leave_module(&context, scope.clone(), self)?;
+
+            if unchanged_paths.contains(&path) {
+                let mut detectors = detectors.borrow_mut();
+                detectors.visitors.extend(parked_visitors);
+                detectors.visitors.sort_by_key(|(name, _)| DETECTOR_TYPES.iter().chain(self.plugin_detectors.iter()).position(|(x, _)| x == name));
+
+                let entries = self.cache.as_ref().unwrap().get_module_local_entries(&path);
+                let mut report = self.report.borrow_mut();
+
+                for entry in entries {
+                    report.insert_entry(path.clone(), entry);
+                }
+            }
+        }
+
+        self.analysis_duration = start_time.elapsed();
+
+        // Attribute the entries to the packages containing their files
+        for (path, entries) in self.report.borrow_mut().entries.iter_mut() {
+            let package = self.packages.package_for_path(path).map(|x| x.name.clone());
+
+            for entry in entries.iter_mut() {
+                entry.package = package.clone();
+            }
+        }
+
+        // Update the cache with the findings of the analysis
+        if let Some(cache) = self.cache.as_mut() {
+            cache.update(&self.sources, &self.report.borrow().entries);
+            cache.save()?;
         }
 
         Ok(())
@@ -0,0 +1,488 @@
+use crate::{
+    error::Error,
+    project::{DisplayFormat, Project},
+    scope::AstScope,
+    visitor::{AstVisitor, ExprContext, ModuleContext, StatementContext, UseContext},
+};
+use serde::Serialize;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap, HashSet},
+    ops::Range,
+    path::PathBuf,
+    rc::Rc,
+};
+use sway_ast::UseTree;
+use sway_types::{Span, Spanned};
+
+/// The punctuation tokens which consist of more than one character.
+const MULTI_CHAR_PUNCTUATION: &[&str] = &[
+    "::", "==", "!=", "<=", ">=", "&&", "||", "=>", "->", "+=", "-=", "*=", "/=", "<<", ">>",
+];
+
+/// A token of Sway source code, along with its byte range in the source.
+#[derive(Clone, Debug)]
+struct Token {
+    text: String,
+    range: Range<usize>,
+}
+
+impl Token {
+    fn is_ident(&self) -> bool {
+        self.text.starts_with(|c: char| c.is_alphabetic() || c == '_')
+    }
+
+    fn metavariable_name(&self) -> Option<&str> {
+        self.text.strip_prefix('$')
+    }
+}
+
+/// Splits the supplied `source` into tokens, ignoring whitespace, comments and trailing commas.
+fn tokenize(source: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    while let Some(c) = source[i..].chars().next() {
+        let start = i;
+
+        if c.is_whitespace() {
+            i += c.len_utf8();
+            continue;
+        }
+
+        // Skip comments
+        if source[i..].starts_with("//") {
+            i = source[i..].find('\n').map(|x| i + x).unwrap_or(source.len());
+            continue;
+        }
+
+        if source[i..].starts_with("/*") {
+            i = source[i + 2..].find("*/").map(|x| i + x + 4).unwrap_or(source.len());
+            continue;
+        }
+
+        if c.is_alphanumeric() || c == '_' || c == '$' {
+            // Identifiers, metavariables and numbers
+            i += c.len_utf8();
+            i += source[i..].find(|c: char| !c.is_alphanumeric() && c != '_').unwrap_or(source.len() - i);
+        } else if c == '"' {
+            // String literals, stepping over escaped characters by their full width
+            let mut chars = source[i + 1..].char_indices();
+            i = source.len();
+
+            while let Some((offset, c)) = chars.next() {
+                match c {
+                    '\\' => {
+                        chars.next();
+                    }
+
+                    '"' => {
+                        i = start + 1 + offset + 1;
+                        break;
+                    }
+
+                    _ => {}
+                }
+            }
+        } else if let Some(punctuation) = MULTI_CHAR_PUNCTUATION.iter().find(|x| source[i..].starts_with(*x)) {
+            i += punctuation.len();
+        } else {
+            i += c.len_utf8();
+        }
+
+        tokens.push(Token {
+            text: source[start..i].to_string(),
+            range: start..i,
+        });
+    }
+
+    // Remove trailing commas, so multi-line argument lists match their single-line form
+    let mut result: Vec<Token> = vec![];
+
+    for token in tokens {
+        if matches!(token.text.as_str(), ")" | "]" | "}") && result.last().map(|x| x.text == ",").unwrap_or(false) {
+            result.pop();
+        }
+
+        result.push(token);
+    }
+
+    result
+}
+
+/// Removes the trailing semicolons of the supplied `tokens`, since statements and expressions are matched alike.
+fn trim_semicolons(mut tokens: &[Token]) -> &[Token] {
+    while let Some((last, rest)) = tokens.split_last() {
+        if last.text != ";" {
+            break;
+        }
+
+        tokens = rest;
+    }
+
+    tokens
+}
+
+/// Gets the number of tokens of the path at the start of the supplied `tokens`, e.g. `std::asset::transfer`.
+fn leading_path_len(tokens: &[Token]) -> usize {
+    let mut len = 0;
+
+    while tokens.get(len).map(|x| x.is_ident()).unwrap_or(false) {
+        len += 1;
+
+        if tokens.get(len).map(|x| x.text != "::").unwrap_or(true) || !tokens.get(len + 1).map(|x| x.is_ident()).unwrap_or(false) {
+            break;
+        }
+
+        len += 1;
+    }
+
+    len
+}
+
+/// Checks if the brackets of the supplied `tokens` are balanced and the tokens do not contain a top-level separator.
+fn is_single_expr(tokens: &[Token]) -> bool {
+    let mut depth = 0i32;
+
+    for token in tokens {
+        match token.text.as_str() {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => depth -= 1,
+            "," | ";" if depth == 0 => return false,
+            _ => {}
+        }
+
+        if depth < 0 {
+            return false;
+        }
+    }
+
+    depth == 0
+}
+
+/// A Sway code template containing metavariables, e.g. `transfer($to, $asset, $amt)` or `storage.$f.write($v)`.
+#[derive(Clone, Debug)]
+pub struct QueryPattern {
+    tokens: Vec<Token>,
+}
+
+impl std::str::FromStr for QueryPattern {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = trim_semicolons(&tokenize(s)).to_vec();
+
+        if tokens.is_empty() {
+            return Err(Error::InvalidQueryPattern("The pattern is empty".into()));
+        }
+
+        if let Some(token) = tokens.iter().find(|x| x.text == "$") {
+            return Err(Error::InvalidQueryPattern(format!("Expected a metavariable name after `$` at offset {}", token.range.start)));
+        }
+
+        Ok(Self { tokens })
+    }
+}
+
+impl QueryPattern {
+    /// Attempts to match the pattern against the supplied `tokens`, returning the token ranges bound to the metavariables.
+    fn match_tokens(&self, tokens: &[Token], callee_path: Option<&[String]>) -> Option<Vec<(String, Range<usize>)>> {
+        let mut pattern = self.tokens.as_slice();
+        let mut tokens = tokens;
+
+        // Match the callee of calls by its canonical path, so calls through import aliases or qualified paths are matched as well
+        if let Some(callee_path) = callee_path {
+            let pattern_path_len = leading_path_len(pattern);
+
+            if pattern_path_len > 0 && pattern.get(pattern_path_len).map(|x| x.text == "(").unwrap_or(false) {
+                let pattern_path = pattern[..pattern_path_len].iter().filter(|x| x.text != "::").map(|x| x.text.clone()).collect::<Vec<_>>();
+
+                if !callee_path.ends_with(&pattern_path) {
+                    return None;
+                }
+
+                pattern = &pattern[pattern_path_len..];
+                tokens = &tokens[leading_path_len(tokens)..];
+            }
+        }
+
+        let mut bindings = vec![];
+
+        if !match_tokens_recursive(pattern, tokens, &mut bindings) {
+            return None;
+        }
+
+        Some(bindings.into_iter().map(|(name, range, _)| (name, range)).collect())
+    }
+}
+
+/// Matches the supplied `pattern` tokens against all of the supplied `tokens`, backtracking over the tokens bound to metavariables.
+fn match_tokens_recursive(pattern: &[Token], tokens: &[Token], bindings: &mut Vec<(String, Range<usize>, Vec<String>)>) -> bool {
+    let Some((first, pattern_rest)) = pattern.split_first() else { return tokens.is_empty() };
+
+    let Some(name) = first.metavariable_name() else {
+        return tokens.first().map(|x| x.text == first.text).unwrap_or(false) && match_tokens_recursive(pattern_rest, &tokens[1..], bindings);
+    };
+
+    // Metavariables which occur more than once have to be bound to the same tokens
+    if let Some((_, _, bound)) = bindings.iter().find(|(x, _, _)| x == name) {
+        let bound = bound.clone();
+
+        return tokens.len() >= bound.len()
+            && tokens.iter().zip(bound.iter()).all(|(x, y)| x.text == *y)
+            && match_tokens_recursive(pattern_rest, &tokens[bound.len()..], bindings);
+    }
+
+    for end in 1..=tokens.len() {
+        let candidate = &tokens[..end];
+
+        if !is_single_expr(candidate) {
+            continue;
+        }
+
+        bindings.push((
+            name.to_string(),
+            candidate[0].range.start..candidate[end - 1].range.end,
+            candidate.iter().map(|x| x.text.clone()).collect(),
+        ));
+
+        if match_tokens_recursive(pattern_rest, &tokens[end..], bindings) {
+            return true;
+        }
+
+        bindings.pop();
+    }
+
+    false
+}
+
+/// A location matching a query pattern.
+#[derive(Clone, Debug, Serialize)]
+pub struct QueryMatch {
+    pub file: PathBuf,
+    pub line: Option<usize>,
+    pub text: String,
+    pub bindings: BTreeMap<String, String>,
+}
+
+#[derive(Default)]
+struct ModuleState {
+    imports: HashMap<String, Vec<String>>, // (local name, full path)
+    visited_ranges: HashSet<Range<usize>>,
+}
+
+impl ModuleState {
+    fn import_use_tree(&mut self, prefix: &[String], use_tree: &UseTree) {
+        match use_tree {
+            UseTree::Group { imports } => {
+                for use_tree in &imports.inner {
+                    self.import_use_tree(prefix, use_tree);
+                }
+            }
+
+            UseTree::Name { name } if name.as_str() == "self" => {
+                if let Some(last) = prefix.last() {
+                    self.imports.insert(last.clone(), prefix.to_vec());
+                }
+            }
+
+            UseTree::Name { name } => {
+                let mut path = prefix.to_vec();
+                path.push(name.as_str().to_string());
+                self.imports.insert(name.as_str().to_string(), path);
+            }
+
+            UseTree::Rename { name, alias, .. } => {
+                let mut path = prefix.to_vec();
+                path.push(name.as_str().to_string());
+                self.imports.insert(alias.as_str().to_string(), path);
+            }
+
+            UseTree::Path { prefix: inner_prefix, suffix, .. } => {
+                let mut prefix = prefix.to_vec();
+                prefix.push(inner_prefix.as_str().to_string());
+                self.import_use_tree(&prefix, suffix.as_ref());
+            }
+
+            UseTree::Glob { .. } | UseTree::Error { .. } => {}
+        }
+    }
+
+    /// Gets the canonical path of the callee of the call at the start of the supplied `tokens`, resolving imported names.
+    fn callee_path(&self, tokens: &[Token]) -> Option<Vec<String>> {
+        let path_len = leading_path_len(tokens);
+
+        if path_len == 0 || tokens.get(path_len).map(|x| x.text != "(").unwrap_or(true) {
+            return None;
+        }
+
+        let mut path = tokens[..path_len].iter().filter(|x| x.text != "::").map(|x| x.text.clone()).collect::<Vec<_>>();
+
+        if let Some(import_path) = self.imports.get(&path[0]) {
+            path.splice(..1, import_path.iter().cloned());
+        }
+
+        Some(path)
+    }
+}
+
+/// Matches a query pattern against every expression and statement of the visited modules.
+pub struct QueryVisitor {
+    pattern: QueryPattern,
+    matches: Rc<RefCell<Vec<QueryMatch>>>,
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+impl QueryVisitor {
+    pub fn new(pattern: QueryPattern, matches: Rc<RefCell<Vec<QueryMatch>>>) -> Self {
+        Self {
+            pattern,
+            matches,
+            module_states: HashMap::new(),
+        }
+    }
+
+    /// Attempts to match the pattern against the code of the supplied `span`.
+    fn check_span(&mut self, path: &std::path::Path, span: &Span, project: &Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(path).unwrap();
+
+        let source = span.as_str();
+        let tokens = tokenize(source);
+        let tokens = trim_semicolons(&tokens);
+
+        let Some((first, last)) = tokens.first().zip(tokens.last()) else { return Ok(()) };
+
+        // Only report each piece of code once, since expression statements are visited as both statements and expressions
+        let range = span.start() + first.range.start..span.start() + last.range.end;
+
+        if module_state.visited_ranges.contains(&range) {
+            return Ok(());
+        }
+
+        let callee_path = module_state.callee_path(tokens);
+        let Some(bindings) = self.pattern.match_tokens(tokens, callee_path.as_deref()) else { return Ok(()) };
+
+        module_state.visited_ranges.insert(range.clone());
+
+        self.matches.borrow_mut().push(QueryMatch {
+            file: path.into(),
+            line: project.span_to_line(path, span)?,
+            text: source[first.range.start..last.range.end].to_string(),
+            bindings: bindings.into_iter().map(|(name, range)| (format!("${name}"), source[range].to_string())).collect(),
+        });
+
+        Ok(())
+    }
+}
+
+impl AstVisitor for QueryVisitor {
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Create the module state
+        if !self.module_states.contains_key(context.path) {
+            self.module_states.insert(context.path.into(), ModuleState::default());
+        }
+
+        Ok(())
+    }
+
+    fn visit_use(&mut self, context: &UseContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Keep track of imported names, so calls through them can be matched by their full path
+        module_state.import_use_tree(&[], &context.item_use.tree);
+
+        Ok(())
+    }
+
+    fn visit_statement(&mut self, context: &StatementContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        self.check_span(context.path, &context.statement.span(), project)
+    }
+
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        self.check_span(context.path, &context.expr.span(), project)
+    }
+}
+
+/// Formats the supplied query `matches` in the supplied `display_format`, which is either JSON or plain text.
+pub fn format_matches(matches: &[QueryMatch], display_format: DisplayFormat) -> String {
+    let mut matches = matches.to_vec();
+    matches.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+
+    if let DisplayFormat::Json = display_format {
+        return serde_json::to_string_pretty(&matches).unwrap();
+    }
+
+    let mut result = String::new();
+
+    for query_match in matches.iter() {
+        let line = query_match.line.map(|x| format!(":{x}")).unwrap_or_default();
+        result.push_str(&format!("{}{line}: {}\n", query_match.file.to_string_lossy(), query_match.text));
+
+        for (name, value) in query_match.bindings.iter() {
+            result.push_str(&format!("    {name} = {value}\n"));
+        }
+    }
+
+    result.push_str(&format!("{} {}", matches.len(), if matches.len() == 1 { "match" } else { "matches" }));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tokenize, QueryMatch, QueryPattern, QueryVisitor};
+    use crate::{project::Project, Options};
+    use std::{cell::RefCell, path::PathBuf, rc::Rc};
+
+    fn query(pattern: &str) -> Vec<QueryMatch> {
+        let options = Options {
+            directory: Some(PathBuf::from("test/query/")),
+            ..Default::default()
+        };
+
+        let matches = Rc::new(RefCell::new(vec![]));
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.set_visitor("query", Box::new(QueryVisitor::new(pattern.parse::<QueryPattern>().unwrap(), matches.clone())));
+        project.analyze_modules().unwrap();
+
+        let mut matches = matches.borrow().clone();
+        matches.sort_by_key(|x| x.line);
+        matches
+    }
+
+    #[test]
+    fn test_query_call() {
+        // Calls are matched through imports, aliases and qualified paths, even when they span multiple lines
+        let matches = query("std::asset::transfer($to, $asset, $amt)");
+        assert_eq!(matches.iter().map(|x| x.line).collect::<Vec<_>>(), vec![Some(22), Some(24), Some(30)]);
+        assert_eq!(matches[0].bindings["$amt"], "amount");
+        assert_eq!(matches[2].bindings["$amt"], "1");
+    }
+
+    #[test]
+    fn test_query_metavariables() {
+        let matches = query("storage.$f.write($v)");
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].bindings["$f"], "balance");
+        assert_eq!(matches[0].bindings["$v"], "storage.balance.read() - amount");
+
+        // Metavariables which occur more than once have to be bound to the same code
+        let matches = query("storage.$f.write(storage.$f.read() - $v)");
+        assert_eq!(matches.len(), 1);
+    }
+
+    #[test]
+    fn test_tokenize_unicode() {
+        // Multi-byte characters in identifiers and escaped in string literals must not split a character
+        let tokens = tokenize("log(\"\\é\"); let été = \"ü\";");
+        let texts = tokens.iter().map(|x| x.text.as_str()).collect::<Vec<_>>();
+        assert_eq!(texts, vec!["log", "(", "\"\\é\"", ")", ";", "let", "été", "=", "\"ü\"", ";"]);
+
+        // Unterminated string literals end at the end of the source
+        let tokens = tokenize("\"é\\");
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].text, "\"é\\");
+    }
+}
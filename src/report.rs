@@ -1,7 +1,9 @@
 use crate::error::Error;
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::{
+    collections::BTreeMap,
     fmt::Display,
     path::{Path, PathBuf},
     str::FromStr,
@@ -39,23 +41,116 @@ impl Display for Severity {
     }
 }
 
+/// How likely an entry is to be a true positive, which is separate from how severe the issue would be.
+///
+/// Entries of detectors which do not assess their confidence are treated as `Medium` when filtering by confidence.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, PartialOrd, Eq, Ord)]
+pub enum Confidence {
+    High,
+    #[default]
+    Medium,
+    Low,
+}
+
+impl Display for Confidence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl FromStr for Confidence {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "high" => Ok(Self::High),
+            "medium" => Ok(Self::Medium),
+            "low" => Ok(Self::Low),
+            _ => Err(Error::InvalidConfidence(s.to_string())),
+        }
+    }
+}
+
+/// The classification of an entry when comparing two reports.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum DiffStatus {
+    New,
+    Fixed,
+    Unchanged,
+}
+
+impl Display for DiffStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Entry {
     pub line: Option<usize>,
     pub severity: Severity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<Confidence>,
     pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detector: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<DiffStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+}
+
+impl Entry {
+    /// Gets the text of the entry, prefixed with its diff status if it has one.
+    pub fn status_text(&self) -> String {
+        match self.status {
+            Some(status) => format!("[{status}] {}", self.text),
+            None => self.text.clone(),
+        }
+    }
+
+    /// Computes a fingerprint of the entry in the file at the supplied `path`.
+    ///
+    /// The line number is deliberately left out so the fingerprint stays stable when unrelated code moves the finding.
+    pub fn fingerprint<P: AsRef<Path>>(&self, path: P) -> String {
+        self.fingerprint_with_occurrence(path, 0)
+    }
+
+    /// Computes a fingerprint of the entry in the file at the supplied `path`, which is the zero-based `occurrence` of the
+    /// identical entries in the file, so identical findings on different lines still get unique fingerprints.
+    pub fn fingerprint_with_occurrence<P: AsRef<Path>>(&self, path: P, occurrence: usize) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(path.as_ref().to_string_lossy().as_bytes());
+        hasher.update(b"\0");
+        hasher.update(self.detector.as_deref().unwrap_or("unknown").as_bytes());
+        hasher.update(b"\0");
+        hasher.update(self.text.as_bytes());
+
+        // The first occurrence keeps the fingerprint of the entry itself
+        if occurrence > 0 {
+            hasher.update(b"\0");
+            hasher.update(occurrence.to_string().as_bytes());
+        }
+
+        hasher.finalize().iter().map(|x| format!("{x:02x}")).collect()
+    }
 }
 
 impl Display for Entry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let line = format!(
-            "{}{}",
+            "{}{}{}",
             if let Some(line) = self.line.as_ref() {
                 format!("L{}: ", line)
             } else {
                 String::new()
             },
-            self.text,
+            self.status_text(),
+            if let Some(confidence) = self.confidence.as_ref() {
+                format!(" ({} confidence)", confidence)
+            } else {
+                String::new()
+            },
         );
 
         let output = match self.severity {
@@ -72,9 +167,19 @@ impl Display for Entry {
 pub struct Report {
     pub sorting: Sorting,
     pub entries: Vec<(PathBuf, Vec<Entry>)>,
+    #[serde(skip)]
+    pub suppressed: BTreeMap<Severity, usize>,
+    #[serde(skip)]
+    current_detector: Option<&'static str>,
 }
 
 impl Report {
+    /// Sets the name of the detector that subsequently added entries are attributed to.
+    #[inline]
+    pub fn set_detector(&mut self, detector_name: &'static str) {
+        self.current_detector = Some(detector_name);
+    }
+
     pub fn add_entry<P: Into<PathBuf>, S: Into<String>>(
         &mut self,
         file: P,
@@ -82,6 +187,45 @@ impl Report {
         severity: Severity,
         text: S,
     ) {
+        self.insert_detector_entry(file, line, severity, None, text);
+    }
+
+    /// Adds an entry with an explicit `confidence`, e.g. when a detector knows a finding is an exact match or a likely false positive.
+    pub fn add_entry_with_confidence<P: Into<PathBuf>, S: Into<String>>(
+        &mut self,
+        file: P,
+        line: Option<usize>,
+        severity: Severity,
+        confidence: Confidence,
+        text: S,
+    ) {
+        self.insert_detector_entry(file, line, severity, Some(confidence), text);
+    }
+
+    /// Inserts an entry attributed to the current detector, with an optional `confidence`.
+    fn insert_detector_entry<P: Into<PathBuf>, S: Into<String>>(
+        &mut self,
+        file: P,
+        line: Option<usize>,
+        severity: Severity,
+        confidence: Option<Confidence>,
+        text: S,
+    ) {
+        let entry = Entry {
+            line,
+            severity,
+            confidence,
+            text: text.into(),
+            detector: self.current_detector.map(String::from),
+            status: None,
+            package: None,
+        };
+
+        self.insert_entry(file, entry);
+    }
+
+    /// Inserts the supplied `entry` into the entries of the file at the supplied path, keeping the entries sorted.
+    pub fn insert_entry<P: Into<PathBuf>>(&mut self, file: P, entry: Entry) {
         let file: PathBuf = file.into();
 
         if !self.entries.iter().any(|(path, _)| file.eq(path)) {
@@ -95,11 +239,7 @@ impl Report {
             .find(|(path, _)| file.eq(path))
             .unwrap();
 
-        file_entry.1.push(Entry {
-            line,
-            severity,
-            text: text.into(),
-        });
+        file_entry.1.push(entry);
 
         match self.sorting {
             Sorting::Line => file_entry.1.sort_unstable_by_key(|x| (x.line, x.severity)),
@@ -107,6 +247,24 @@ impl Report {
         }
     }
 
+    /// Replaces the entries of the report with the supplied `entries`, counting the removed entries as suppressed.
+    pub fn set_filtered_entries(&mut self, entries: Vec<(PathBuf, Vec<Entry>)>) {
+        for (_, entries) in self.entries.iter() {
+            for entry in entries.iter() {
+                *self.suppressed.entry(entry.severity).or_default() += 1;
+            }
+        }
+
+        for (_, entries) in entries.iter() {
+            for entry in entries.iter() {
+                *self.suppressed.get_mut(&entry.severity).unwrap() -= 1;
+            }
+        }
+
+        self.suppressed.retain(|_, count| *count > 0);
+        self.entries = entries;
+    }
+
     pub fn entry_count<P: AsRef<Path>>(&self, path: P) -> usize {
         self.entries.iter()
             .find(|(entry_path, _)| entry_path == path.as_ref())
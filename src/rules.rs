@@ -0,0 +1,545 @@
+use crate::{
+    error::Error,
+    project::Project,
+    report::Severity,
+    scope::AstScope,
+    utils,
+    visitor::{AstVisitor, ExprContext, FnContext, ModuleContext, StatementContext, UseContext},
+};
+use serde::Deserialize;
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+use sway_ast::{Expr, FnArgs, IfCondition, ItemFn, ItemKind, Pattern, Statement, StatementLet};
+use sway_types::{Span, Spanned};
+
+/// A project-specific rule declared in the `[[rules]]` tables of the config file.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub struct CustomRule {
+    /// The name of the rule, which is reported as the detector of its findings.
+    pub name: String,
+
+    /// The severity of the findings of the rule. (Default = Medium)
+    #[serde(default = "default_severity")]
+    pub severity: Severity,
+
+    /// An additional message to append to the findings of the rule, e.g. the rationale of the rule.
+    #[serde(default)]
+    pub message: Option<String>,
+
+    /// The paths of the files the rule applies to. (Default = all files)
+    #[serde(default)]
+    pub modules: Vec<PathBuf>,
+
+    #[serde(flatten)]
+    pub kind: CustomRuleKind,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CustomRuleKind {
+    /// Calls to `function` are not allowed.
+    ForbiddenCall {
+        function: String,
+    },
+
+    /// Calls to `function` are only allowed inside of functions declared with the `attribute` attribute, e.g. `storage(write)`.
+    CallRequiresAttribute {
+        function: String,
+        attribute: String,
+    },
+
+    /// The `field` storage field must be read in a `require` or reverting `if` condition by every abi function,
+    /// either directly or through the functions it calls.
+    StorageFieldChecked {
+        field: String,
+    },
+}
+
+fn default_severity() -> Severity {
+    Severity::Medium
+}
+
+impl CustomRule {
+    /// Checks if the rule applies to the file at the supplied `path`.
+    fn applies_to(&self, path: &Path) -> bool {
+        self.modules.is_empty() || self.modules.iter().any(|x| path.ends_with(x))
+    }
+
+    /// Gets the path of the function whose calls are checked by the rule, if any.
+    fn function(&self) -> Option<&str> {
+        match &self.kind {
+            CustomRuleKind::ForbiddenCall { function } => Some(function),
+            CustomRuleKind::CallRequiresAttribute { function, .. } => Some(function),
+            CustomRuleKind::StorageFieldChecked { .. } => None,
+        }
+    }
+
+    /// Formats the description of a finding, followed by the message of the rule.
+    fn format_text(&self, description: String) -> String {
+        match self.message.as_ref() {
+            Some(message) => format!("{description} {message}"),
+            None => description,
+        }
+    }
+}
+
+/// Validates the names of the supplied custom `rules`, which must be unique and must not shadow the name of a detector.
+pub fn validate_rules(rules: &[CustomRule], detector_names: &[&str]) -> Result<(), Error> {
+    for (i, rule) in rules.iter().enumerate() {
+        if detector_names.contains(&rule.name.as_str()) {
+            return Err(Error::InvalidCustomRule(format!("Rule `{}` has the same name as a detector", rule.name)));
+        }
+
+        if rules[..i].iter().any(|x| x.name == rule.name) {
+            return Err(Error::InvalidCustomRule(format!("Rule `{}` is declared more than once", rule.name)));
+        }
+    }
+
+    Ok(())
+}
+
+pub struct CustomRuleVisitor {
+    rule: CustomRule,
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+#[derive(Default)]
+struct ModuleState {
+    fn_call_names: Vec<String>,
+    fn_states: Vec<FnState>,
+}
+
+struct FnState {
+    name: String,
+    location: String,
+    signature_span: Span,
+    is_abi_fn: bool,
+    reads_field: bool,
+    read_variables: Vec<String>,
+    called_fns: Vec<(String, Vec<usize>)>,
+}
+
+/// The methods which read the value of a storage key.
+const STORAGE_READ_FN_NAMES: &[&str] = &["read", "try_read", "len", "is_empty"];
+
+/// Checks if the supplied `expr` reads the `field` storage field with a read method, either directly, e.g. `storage.paused.read()`,
+/// or through one of the storage keys named `key_names`, e.g. `paused.read()`.
+fn is_storage_field_read(expr: &Expr, field: &str, key_names: &[String]) -> bool {
+    let Expr::MethodCall { .. } = expr else { return false };
+    let idents = utils::fold_expr_idents(expr);
+
+    let method_idents = if idents.len() >= 3 && idents[0].as_str() == "storage" && idents[1].as_str() == field {
+        &idents[2..]
+    } else if idents.len() >= 2 && key_names.iter().any(|x| x == idents[0].as_str()) {
+        &idents[1..]
+    } else {
+        return false;
+    };
+
+    !method_idents.iter().any(|x| utils::is_storage_write_fn(x.as_str()))
+        && STORAGE_READ_FN_NAMES.contains(&method_idents.last().unwrap().as_str())
+}
+
+/// Checks if the supplied `expr` passes the storage key of the `field` storage field, either directly or through one of the storage keys named `key_names`.
+fn is_storage_field_key(expr: &Expr, field: &str, key_names: &[String]) -> bool {
+    if let Some(storage_idents) = utils::get_storage_key_idents(expr) {
+        return storage_idents[0].as_str() == field;
+    }
+
+    let Expr::Path(path) = expr else { return false };
+    key_names.iter().any(|x| x == path.span().as_str())
+}
+
+/// Gets the indices of the arguments of the supplied function call `expr` which pass the storage key of the `field` storage field.
+fn get_storage_field_key_arg_indices(expr: &Expr, field: &str, key_names: &[String]) -> Vec<usize> {
+    let Expr::FuncApp { args, .. } = expr else { return vec![] };
+
+    utils::fold_punctuated(&args.inner).iter()
+        .enumerate()
+        .filter(|(_, arg)| is_storage_field_key(arg, field, key_names))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Gets the name of the variable bound to a read of the `field` storage field by the supplied `statement`, e.g. `let paused = storage.paused.read();`.
+fn get_storage_field_read_binding(statement: &Statement, field: &str, key_names: &[String]) -> Option<String> {
+    let Statement::Let(StatementLet { pattern: Pattern::Var { name, .. }, expr, .. }) = statement else { return None };
+
+    if !is_storage_field_read(expr, field, key_names) {
+        return None;
+    }
+
+    Some(name.as_str().to_string())
+}
+
+/// Gets the condition checked by the supplied `expr`, if it is a `require` call or an `if` expression which reverts.
+fn get_checked_condition(expr: &Expr) -> Option<&Expr> {
+    if let Some(require_args) = utils::get_require_args(expr) {
+        return require_args.first().copied();
+    }
+
+    match utils::get_if_revert_condition(expr)? {
+        IfCondition::Expr(condition) => Some(condition.as_ref()),
+        _ => None,
+    }
+}
+
+/// Checks if the supplied `condition` reads the `field` storage field, either directly or through one of the `read_variables` bound to its value.
+fn condition_checks_field(condition: &Expr, field: &str, key_names: &[String], read_variables: &[String]) -> bool {
+    let mut result = false;
+
+    utils::map_expr(condition, &mut |expr| {
+        if is_storage_field_read(expr, field, key_names) {
+            result = true;
+        }
+
+        if let Expr::Path(path) = expr {
+            if read_variables.iter().any(|x| x == path.span().as_str()) {
+                result = true;
+            }
+        }
+    });
+
+    result
+}
+
+/// Checks if the supplied `item_fn` checks the `field` storage field, either directly or through the storage keys passed as its
+/// arguments at `key_arg_indices`, including the checks of the functions it calls in any module of the `project`.
+fn fn_checks_field(project: &Project, item_fn: &ItemFn, field: &str, key_arg_indices: &[usize], visited: &mut Vec<String>) -> bool {
+    let args = match &item_fn.fn_signature.arguments.inner {
+        FnArgs::Static(args) => Some(args),
+        FnArgs::NonStatic { args_opt, .. } => args_opt.as_ref().map(|(_, args)| args),
+    };
+
+    let key_names = args
+        .map(|args| {
+            utils::fold_punctuated(args).iter()
+                .enumerate()
+                .filter(|(index, _)| key_arg_indices.contains(index))
+                .flat_map(|(_, arg)| utils::fold_pattern_idents(&arg.pattern))
+                .map(|ident| ident.as_str().to_string())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let read_variables = item_fn.body.inner.statements.iter()
+        .filter_map(|statement| get_storage_field_read_binding(statement, field, &key_names))
+        .collect::<Vec<_>>();
+
+    let mut called_fns = vec![];
+    let mut result = false;
+
+    utils::map_block_exprs(&item_fn.body.inner, &mut |expr| {
+        if let Some(condition) = get_checked_condition(expr) {
+            if condition_checks_field(condition, field, &key_names, &read_variables) {
+                result = true;
+            }
+        }
+
+        if let Some(fn_name) = utils::get_fn_call_name(expr) {
+            called_fns.push((fn_name, get_storage_field_key_arg_indices(expr, field, &key_names)));
+        }
+    });
+
+    result || called_fns.iter().any(|(fn_name, key_arg_indices)| module_fn_checks_field(project, fn_name, field, key_arg_indices, visited))
+}
+
+/// Checks if the free function named `fn_name` in any module of the `project` checks the `field` storage field.
+fn module_fn_checks_field(project: &Project, fn_name: &str, field: &str, key_arg_indices: &[usize], visited: &mut Vec<String>) -> bool {
+    // Skip functions which are already being checked, since they are recursive
+    if visited.iter().any(|x| x == fn_name) {
+        return false;
+    }
+
+    visited.push(fn_name.to_string());
+
+    let modules = project.modules();
+    let modules = modules.borrow();
+
+    for module in modules.values() {
+        for item in module.items.iter() {
+            let ItemKind::Fn(item_fn) = &item.value else { continue };
+
+            if item_fn.fn_signature.name.as_str() == fn_name {
+                return fn_checks_field(project, item_fn, field, key_arg_indices, visited);
+            }
+        }
+    }
+
+    false
+}
+
+impl CustomRuleVisitor {
+    pub fn new(rule: CustomRule) -> Self {
+        Self {
+            rule,
+            module_states: HashMap::new(),
+        }
+    }
+}
+
+impl ModuleState {
+    /// Checks if the supplied `expr` calls the function of the rule with the supplied `function` path.
+    fn is_rule_fn_call(&self, expr: &Expr, function: &str) -> bool {
+        let Expr::FuncApp { func, .. } = expr else { return false };
+        let Expr::Path(path) = func.as_ref() else { return false };
+
+        let call_path = utils::fold_path_idents(path).iter().map(|x| x.as_str()).collect::<Vec<_>>().join("::");
+
+        // Check for fully-qualified calls, calls through an imported module and calls to an imported name
+        call_path == function
+            || (call_path.contains("::") && function.ends_with(format!("::{call_path}").as_str()))
+            || self.fn_call_names.contains(&call_path)
+    }
+
+    /// Computes whether each function checks the `field` storage field, including the checks of the functions it calls in any module.
+    fn resolve_field_reads(&self, project: &Project, field: &str) -> Vec<bool> {
+        let mut reads = self.fn_states.iter().map(|x| x.reads_field).collect::<Vec<_>>();
+
+        // Follow calls to functions in other modules and calls which pass the storage key of the field, since they are not tracked by the module
+        for (fn_state, reads_field) in self.fn_states.iter().zip(reads.iter_mut()) {
+            *reads_field |= fn_state.called_fns.iter().any(|(name, key_arg_indices)| {
+                let is_module_fn = self.fn_states.iter().any(|x| !x.is_abi_fn && x.name == *name);
+                (!is_module_fn || !key_arg_indices.is_empty()) && module_fn_checks_field(project, name, field, key_arg_indices, &mut vec![])
+            });
+        }
+
+        loop {
+            let mut changed = false;
+
+            for (index, fn_state) in self.fn_states.iter().enumerate() {
+                if reads[index] {
+                    continue;
+                }
+
+                let reads_through_call = fn_state.called_fns.iter().any(|(name, _)| {
+                    self.fn_states.iter().position(|x| !x.is_abi_fn && x.name == *name).map(|x| reads[x]).unwrap_or(false)
+                });
+
+                if reads_through_call {
+                    reads[index] = true;
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        reads
+    }
+}
+
+impl AstVisitor for CustomRuleVisitor {
+    // Rules can match code anywhere in the project, so their findings are never treated as module-local
+    fn is_cross_module(&self) -> bool {
+        true
+    }
+
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Only check the modules the rule applies to
+        if !self.rule.applies_to(context.path) {
+            return Ok(());
+        }
+
+        // Create the module state
+        if !self.module_states.contains_key(context.path) {
+            self.module_states.insert(context.path.into(), ModuleState::default());
+        }
+
+        Ok(())
+    }
+
+    fn visit_use(&mut self, context: &UseContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Check the use tree for imports of the function of the rule
+        let Some(function) = self.rule.function() else { return Ok(()) };
+
+        if let Some(name) = utils::use_tree_to_name(&context.item_use.tree, function) {
+            module_state.fn_call_names.push(name);
+        }
+
+        Ok(())
+    }
+
+    fn visit_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Only keep track of functions for storage field checks
+        let CustomRuleKind::StorageFieldChecked { .. } = &self.rule.kind else { return Ok(()) };
+
+        // Create the function state
+        let is_abi_fn = context.item_impl
+            .map(|item_impl| item_impl.trait_opt.is_some() && item_impl.ty.span().as_str() == "Contract")
+            .unwrap_or(false);
+
+        module_state.fn_states.push(FnState {
+            name: context.item_fn.fn_signature.name.as_str().to_string(),
+            location: utils::get_item_location(context.item, &context.item_impl, &Some(context.item_fn)),
+            signature_span: context.item_fn.fn_signature.span(),
+            is_abi_fn,
+            reads_field: false,
+            read_variables: vec![],
+            called_fns: vec![],
+        });
+
+        Ok(())
+    }
+
+    fn visit_statement(&mut self, context: &StatementContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Only keep track of variables for storage field checks
+        let CustomRuleKind::StorageFieldChecked { field } = &self.rule.kind else { return Ok(()) };
+
+        // Get the function state
+        let fn_signature = context.item_fn.fn_signature.span();
+        let Some(fn_state) = module_state.fn_states.iter_mut().rev().find(|x| x.signature_span == fn_signature) else { return Ok(()) };
+
+        // Store the variables bound to reads of the storage field, since they can be checked instead of the field
+        if let Some(variable_name) = get_storage_field_read_binding(context.statement, field, &[]) {
+            fn_state.read_variables.push(variable_name);
+        }
+
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        match &self.rule.kind {
+            CustomRuleKind::ForbiddenCall { function } => {
+                if !module_state.is_rule_fn_call(context.expr, function) {
+                    return Ok(());
+                }
+
+                project.report.borrow_mut().add_entry(
+                    context.path,
+                    project.span_to_line(context.path, &context.expr.span())?,
+                    self.rule.severity,
+                    self.rule.format_text(format!(
+                        "{} calls the forbidden `{function}` function: `{}`.",
+                        utils::get_item_location(context.item, &context.item_impl, &context.item_fn),
+                        context.expr.span().as_str(),
+                    )),
+                );
+            }
+
+            CustomRuleKind::CallRequiresAttribute { function, attribute } => {
+                if !module_state.is_rule_fn_call(context.expr, function) {
+                    return Ok(());
+                }
+
+                // Split the attribute into its name and arguments, e.g. `storage(write)`
+                let (attribute_name, attribute_args) = match attribute.split_once('(') {
+                    Some((name, args)) => (name.trim(), args.trim_end_matches(')').split(',').map(str::trim).filter(|x| !x.is_empty()).collect()),
+                    None => (attribute.trim(), vec![]),
+                };
+
+                if context.fn_attributes.map(|x| utils::check_attribute_decls(x, attribute_name, &attribute_args)).unwrap_or(false) {
+                    return Ok(());
+                }
+
+                project.report.borrow_mut().add_entry(
+                    context.path,
+                    project.span_to_line(context.path, &context.expr.span())?,
+                    self.rule.severity,
+                    self.rule.format_text(format!(
+                        "{} calls `{function}` without being declared with `#[{attribute}]`: `{}`.",
+                        utils::get_item_location(context.item, &context.item_impl, &context.item_fn),
+                        context.expr.span().as_str(),
+                    )),
+                );
+            }
+
+            CustomRuleKind::StorageFieldChecked { field } => {
+                // Get the function state
+                let Some(item_fn) = context.item_fn.as_ref() else { return Ok(()) };
+                let fn_signature = item_fn.fn_signature.span();
+                let Some(fn_state) = module_state.fn_states.iter_mut().rev().find(|x| x.signature_span == fn_signature) else { return Ok(()) };
+
+                // Store the called functions and the arguments which pass the storage key of the field, since they can check it on behalf of the function
+                if let Some(fn_name) = utils::get_fn_call_name(context.expr) {
+                    let called_fn = (fn_name, get_storage_field_key_arg_indices(context.expr, field, &[]));
+
+                    if !fn_state.called_fns.contains(&called_fn) {
+                        fn_state.called_fns.push(called_fn);
+                    }
+                }
+
+                // Check for reads of the storage field in `require` or reverting `if` conditions, since writes and unchecked reads do not enforce it
+                let Some(condition) = get_checked_condition(context.expr) else { return Ok(()) };
+
+                if condition_checks_field(condition, field, &[], &fn_state.read_variables) {
+                    fn_state.reads_field = true;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn leave_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get(context.path) else { return Ok(()) };
+
+        // Check for abi functions which do not read the storage field
+        let CustomRuleKind::StorageFieldChecked { field } = &self.rule.kind else { return Ok(()) };
+
+        for (fn_state, reads_field) in module_state.fn_states.iter().zip(module_state.resolve_field_reads(project, field)) {
+            if !fn_state.is_abi_fn || reads_field {
+                continue;
+            }
+
+            project.report.borrow_mut().add_entry(
+                context.path,
+                project.span_to_line(context.path, &fn_state.signature_span)?,
+                self.rule.severity,
+                self.rule.format_text(format!(
+                    "{} does not check the `storage.{field}` field.",
+                    fn_state.location,
+                )),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{project::Project, Options};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_custom_rules() {
+        // The rules are loaded from the `sway-analyzer.toml` file of the project directory
+        let options = Options {
+            directory: Some(PathBuf::from("test/custom_rules/")),
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        println!("{project}");
+
+        let report = project.report.borrow();
+        let entries = report.entries.iter().flat_map(|(_, x)| x.iter()).collect::<Vec<_>>();
+        let count_entries = |name: &str| entries.iter().filter(|x| x.detector.as_deref() == Some(name)).count();
+
+        assert_eq!(count_entries("unsafe_op_requires_write"), 1);
+        assert_eq!(count_entries("paused_checked"), 2);
+        assert_eq!(count_entries("no_oracle_timestamp"), 1);
+    }
+}
@@ -0,0 +1,236 @@
+use crate::report::{Confidence, DiffStatus, Entry, Report, Severity};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, time::Duration};
+
+/// The version of the JSON report schema.
+///
+/// The major version is incremented whenever a field is removed or changes meaning,
+/// the minor version is incremented whenever a field is added.
+pub const SCHEMA_VERSION: &str = "1.3.0";
+
+/// A versioned report of the findings of sway-analyzer.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct JsonReport {
+    /// The version of the report schema.
+    pub schema_version: String,
+    /// Information about the analysis that produced the report.
+    pub metadata: JsonMetadata,
+    /// The findings of the analysis, grouped by file.
+    pub files: Vec<JsonFile>,
+}
+
+/// Information about the analysis that produced a report.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct JsonMetadata {
+    /// The name of the tool.
+    pub tool_name: String,
+    /// The version of the tool.
+    pub tool_version: String,
+    /// The names of the detectors that were run.
+    pub detectors: Vec<String>,
+    /// The paths of the files that were analyzed.
+    pub files_analyzed: Vec<String>,
+    /// The duration of the analysis in milliseconds.
+    pub duration_ms: u64,
+    /// The number of findings that were suppressed from the report.
+    pub suppressed: JsonSeverityCounts,
+}
+
+/// A number of findings per severity.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
+pub struct JsonSeverityCounts {
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+}
+
+/// The findings of a single file.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct JsonFile {
+    /// The path of the file.
+    pub path: String,
+    /// The findings in the file.
+    pub findings: Vec<JsonFinding>,
+}
+
+/// A single finding.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct JsonFinding {
+    /// The name of the detector that produced the finding.
+    pub detector: Option<String>,
+    /// The name of the Forc package containing the file of the finding.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+    /// The severity of the finding.
+    pub severity: JsonSeverity,
+    /// The confidence that the finding is a true positive, if the detector assessed it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<JsonConfidence>,
+    /// The 1-based line number of the finding.
+    pub line: Option<usize>,
+    /// The description of the finding.
+    pub message: String,
+    /// The classification of the finding when comparing two reports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<JsonDiffStatus>,
+}
+
+/// The severity of a finding.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonSeverity {
+    High,
+    Medium,
+    Low,
+}
+
+impl From<Severity> for JsonSeverity {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::High => Self::High,
+            Severity::Medium => Self::Medium,
+            Severity::Low => Self::Low,
+        }
+    }
+}
+
+impl From<JsonSeverity> for Severity {
+    fn from(severity: JsonSeverity) -> Self {
+        match severity {
+            JsonSeverity::High => Self::High,
+            JsonSeverity::Medium => Self::Medium,
+            JsonSeverity::Low => Self::Low,
+        }
+    }
+}
+
+/// The confidence that a finding is a true positive.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonConfidence {
+    High,
+    Medium,
+    Low,
+}
+
+impl From<Confidence> for JsonConfidence {
+    fn from(confidence: Confidence) -> Self {
+        match confidence {
+            Confidence::High => Self::High,
+            Confidence::Medium => Self::Medium,
+            Confidence::Low => Self::Low,
+        }
+    }
+}
+
+impl From<JsonConfidence> for Confidence {
+    fn from(confidence: JsonConfidence) -> Self {
+        match confidence {
+            JsonConfidence::High => Self::High,
+            JsonConfidence::Medium => Self::Medium,
+            JsonConfidence::Low => Self::Low,
+        }
+    }
+}
+
+/// The classification of a finding when comparing two reports.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonDiffStatus {
+    New,
+    Fixed,
+    Unchanged,
+}
+
+impl From<DiffStatus> for JsonDiffStatus {
+    fn from(status: DiffStatus) -> Self {
+        match status {
+            DiffStatus::New => Self::New,
+            DiffStatus::Fixed => Self::Fixed,
+            DiffStatus::Unchanged => Self::Unchanged,
+        }
+    }
+}
+
+impl JsonReport {
+    /// Creates a versioned JSON report from the supplied internal `report` and analysis information.
+    pub fn new(report: &Report, detectors: Vec<String>, files_analyzed: Vec<PathBuf>, duration: Duration) -> Self {
+        let mut suppressed = JsonSeverityCounts::default();
+
+        for (severity, count) in report.suppressed.iter() {
+            match severity {
+                Severity::High => suppressed.high += count,
+                Severity::Medium => suppressed.medium += count,
+                Severity::Low => suppressed.low += count,
+            }
+        }
+
+        Self {
+            schema_version: SCHEMA_VERSION.into(),
+            metadata: JsonMetadata {
+                tool_name: env!("CARGO_PKG_NAME").into(),
+                tool_version: env!("CARGO_PKG_VERSION").into(),
+                detectors,
+                files_analyzed: files_analyzed.iter().map(|x| x.to_string_lossy().to_string()).collect(),
+                duration_ms: duration.as_millis() as u64,
+                suppressed,
+            },
+            files: report.entries.iter()
+                .map(|(path, entries)| JsonFile {
+                    path: path.to_string_lossy().to_string(),
+                    findings: entries.iter()
+                        .map(|entry| JsonFinding {
+                            detector: entry.detector.clone(),
+                            package: entry.package.clone(),
+                            severity: entry.severity.into(),
+                            confidence: entry.confidence.map(JsonConfidence::from),
+                            line: entry.line,
+                            message: entry.text.clone(),
+                            status: entry.status.map(JsonDiffStatus::from),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Converts the findings of the report back into report entries, along with the paths of their files.
+    pub fn to_entries(&self) -> Vec<(PathBuf, Entry)> {
+        let mut result = vec![];
+
+        for file in self.files.iter() {
+            for finding in file.findings.iter() {
+                result.push((
+                    PathBuf::from(&file.path),
+                    Entry {
+                        line: finding.line,
+                        severity: finding.severity.into(),
+                        confidence: finding.confidence.map(Confidence::from),
+                        text: finding.message.clone(),
+                        detector: finding.detector.clone(),
+                        status: None,
+                        package: finding.package.clone(),
+                    },
+                ));
+            }
+        }
+
+        result
+    }
+}
+
+/// Generates the JSON Schema of the versioned JSON report.
+pub fn json_schema() -> serde_json::Value {
+    serde_json::to_value(schemars::schema_for!(JsonReport)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_shipped_json_schema() {
+        // If this fails, regenerate the shipped schema with `sway-analyzer --json-schema > schema/report.schema.json`
+        let shipped: serde_json::Value = serde_json::from_str(include_str!("../schema/report.schema.json")).unwrap();
+        assert_eq!(shipped, super::json_schema());
+    }
+}
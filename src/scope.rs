@@ -78,7 +78,7 @@ impl AstScope {
                 );
             }
 
-            println!("Module: {}", utils::path_expr_to_string(&module_path));
+            eprintln!("Module: {}", utils::path_expr_to_string(&module_path));
 
             let scope = Rc::new(RefCell::new(AstScope::default()));
 
@@ -1507,7 +1507,7 @@ impl AstScope {
                         let ItemKind::Use(item_use) = &item.value else { continue };
 
                         for path_expr in utils::flatten_use_tree(None, &item_use.tree) {
-                            println!("Checking {}", utils::path_expr_to_string(&path_expr));
+                            eprintln!("Checking {}", utils::path_expr_to_string(&path_expr));
 
                             let segment = if let Some((_, segment)) = path_expr.suffix.last() {
                                 segment
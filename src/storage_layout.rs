@@ -0,0 +1,482 @@
+use crate::{
+    error::Error,
+    formats::escape_csv,
+    project::{DisplayFormat, Project},
+    scope::AstScope,
+    utils,
+    visitor::{AstVisitor, ExprContext, FnContext, ModuleContext, StatementContext, StorageFieldContext, UseContext},
+};
+use serde::Serialize;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+use sway_ast::{Expr, FnArgs, ItemFn, ItemKind};
+use sway_types::{Span, Spanned};
+
+/// A storage field of a contract, along with the functions which read or write it.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct StorageFieldAccess {
+    pub file: PathBuf,
+    pub line: Option<usize>,
+    /// The name of the field, prefixed with its namespaces, e.g. `stats::total`.
+    pub name: String,
+    pub ty: String,
+    pub initial_value: String,
+    pub readers: Vec<String>,
+    pub writers: Vec<String>,
+    /// Whether the field is written by at least one function, but never read.
+    pub write_only: bool,
+    /// Whether the field is never written, so it could be a constant or a configurable field instead.
+    pub never_written: bool,
+}
+
+#[derive(Default)]
+struct ModuleState {
+    imports: utils::ImportState,
+    fields: Vec<(Vec<String>, StorageFieldAccess)>,
+    accesses: Vec<Access>,
+    visited_spans: HashSet<Span>,
+    fn_states: HashMap<Span, FnState>,
+}
+
+#[derive(Default)]
+struct FnState {
+    storage_bindings: utils::StorageBindingState,
+}
+
+struct Access {
+    idents: Vec<String>,
+    fn_name: String,
+    is_write: bool,
+}
+
+impl ModuleState {
+    /// Gets the index of the field accessed through the supplied `idents` following `storage`, preferring the field with the longest namespace path.
+    fn find_field(&self, idents: &[String]) -> Option<usize> {
+        self.fields.iter()
+            .enumerate()
+            .filter(|(_, (path, _))| idents.starts_with(path))
+            .max_by_key(|(_, (path, _))| path.len())
+            .map(|(index, _)| index)
+    }
+
+    /// Gets whether the function called through the supplied `func` from the module at `path` reads and writes the storage key passed as its argument at `key_arg_index`.
+    ///
+    /// Storage keys passed to methods or to functions which can not be resolved can be both read and written.
+    fn get_key_arg_access(&self, project: &Project, path: &Path, func: Option<&Expr>, key_arg_index: usize) -> (bool, bool) {
+        let Some(Expr::Path(func)) = func else { return (true, true) };
+        let call_path = utils::fold_path_idents(func).iter().map(|x| x.as_str().to_string()).collect::<Vec<_>>();
+
+        let modules = project.modules();
+        let modules = modules.borrow();
+
+        match utils::resolve_module_fn(path, &call_path, &self.imports, &modules, project.packages()) {
+            Some((fn_path, _, item_fn)) => fn_key_arg_access(project, &fn_path, item_fn, key_arg_index, &mut vec![]),
+            None => (true, true),
+        }
+    }
+}
+
+/// Gets whether the supplied `item_fn` declared in the module at `path` reads and writes the storage key passed as its argument at `key_arg_index`,
+/// including the accesses of the functions it passes the key to in any module of the `project`.
+fn fn_key_arg_access(project: &Project, path: &Path, item_fn: &ItemFn, key_arg_index: usize, visited: &mut Vec<(PathBuf, String, usize)>) -> (bool, bool) {
+    // Skip functions which are already being checked, since they are recursive
+    let key = (path.to_path_buf(), item_fn.fn_signature.name.as_str().to_string(), key_arg_index);
+
+    if visited.contains(&key) {
+        return (false, false);
+    }
+
+    visited.push(key);
+
+    let args = match &item_fn.fn_signature.arguments.inner {
+        FnArgs::Static(args) => Some(args),
+        FnArgs::NonStatic { args_opt, .. } => args_opt.as_ref().map(|(_, args)| args),
+    };
+
+    let Some(arg) = args.and_then(|args| utils::fold_punctuated(args).get(key_arg_index).copied()) else { return (false, false) };
+
+    let key_names = utils::fold_pattern_idents(&arg.pattern).iter().map(|x| x.as_str().to_string()).collect::<Vec<_>>();
+
+    // Resolve the imports of the module declaring the function
+    let modules = project.modules();
+    let modules = modules.borrow();
+
+    let mut imports = utils::ImportState::default();
+
+    if let Some(module) = modules.get(path) {
+        for item in module.items.iter() {
+            if let ItemKind::Use(item_use) = &item.value {
+                imports.visit_use(item_use);
+            }
+        }
+    }
+
+    let mut visited_spans = HashSet::new();
+    let mut key_calls = vec![];
+    let (mut reads, mut writes) = (false, false);
+
+    utils::map_block_exprs(&item_fn.body.inner, &mut |expr| {
+        match expr {
+            // Check for accesses through the storage key, only checking the outermost method call of a chain, e.g. `key.get(i).unwrap().write(y)`
+            Expr::MethodCall { .. } if !visited_spans.contains(&expr.span()) => {
+                let mut target = expr;
+
+                while let Expr::MethodCall { target: inner_target, .. } = target {
+                    visited_spans.insert(inner_target.span());
+                    target = inner_target.as_ref();
+                }
+
+                let idents = utils::fold_expr_idents(expr).iter().map(|x| x.as_str().to_string()).collect::<Vec<_>>();
+
+                if idents.len() < 2 || !key_names.contains(&idents[0]) {
+                    return;
+                }
+
+                let method_name = idents.last().unwrap();
+
+                if utils::is_storage_write_fn(method_name) {
+                    writes = true;
+                } else if method_name != "get" {
+                    reads = true;
+                }
+            }
+
+            // Store the calls which pass the storage key on to other functions
+            Expr::FuncApp { func, args } => {
+                let Expr::Path(func) = func.as_ref() else { return };
+                let call_path = utils::fold_path_idents(func).iter().map(|x| x.as_str().to_string()).collect::<Vec<_>>();
+
+                for (index, arg) in utils::fold_punctuated(&args.inner).into_iter().enumerate() {
+                    if let Expr::Path(arg_path) = arg {
+                        if key_names.iter().any(|x| x == arg_path.span().as_str()) {
+                            key_calls.push((call_path.clone(), index));
+                        }
+                    }
+                }
+            }
+
+            _ => {}
+        }
+    });
+
+    for (call_path, index) in key_calls {
+        let (call_reads, call_writes) = match utils::resolve_module_fn(path, &call_path, &imports, &modules, project.packages()) {
+            Some((fn_path, _, item_fn)) => fn_key_arg_access(project, &fn_path, item_fn, index, visited),
+            None => (true, true),
+        };
+
+        reads |= call_reads;
+        writes |= call_writes;
+    }
+
+    (reads, writes)
+}
+
+/// Collects the storage fields of the visited modules, along with the functions which read or write each of them.
+pub struct StorageLayoutVisitor {
+    fields: Rc<RefCell<Vec<StorageFieldAccess>>>,
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+impl StorageLayoutVisitor {
+    pub fn new(fields: Rc<RefCell<Vec<StorageFieldAccess>>>) -> Self {
+        Self {
+            fields,
+            module_states: HashMap::new(),
+        }
+    }
+}
+
+impl AstVisitor for StorageLayoutVisitor {
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Create the module state
+        if !self.module_states.contains_key(context.path) {
+            self.module_states.insert(context.path.into(), ModuleState::default());
+        }
+
+        Ok(())
+    }
+
+    fn visit_use(&mut self, context: &UseContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Keep track of the imported names, so called helper functions can be resolved
+        module_state.imports.visit_use(context.item_use);
+
+        Ok(())
+    }
+
+    fn visit_storage_field(&mut self, context: &StorageFieldContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Create the storage field state
+        let mut path = context.namespace.iter().map(|x| x.as_str().to_string()).collect::<Vec<_>>();
+        path.push(context.field.name.as_str().to_string());
+
+        let field = StorageFieldAccess {
+            file: context.path.into(),
+            line: project.span_to_line(context.path, &context.field.name.span())?,
+            name: path.join("::"),
+            ty: utils::ty_to_string(&context.field.ty),
+            initial_value: context.field.initializer.span().as_str().split_whitespace().collect::<Vec<_>>().join(" "),
+            ..Default::default()
+        };
+
+        module_state.fields.push((path, field));
+
+        Ok(())
+    }
+
+    fn visit_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Create the function state
+        module_state.fn_states.entry(context.item_fn.fn_signature.span()).or_default();
+
+        Ok(())
+    }
+
+    fn visit_statement(&mut self, context: &StatementContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Get the function state
+        let fn_state = module_state.fn_states.get_mut(&context.item_fn.fn_signature.span()).unwrap();
+
+        // Check for storage binding variable declarations, so writes through them are attributed to their field
+        fn_state.storage_bindings.visit_statement(&context.blocks, context.statement);
+
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Only check expressions inside of functions
+        let Some(item_fn) = context.item_fn.as_ref() else { return Ok(()) };
+
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        let fn_name = match context.item_impl.as_ref() {
+            Some(item_impl) => format!("{}::{}", item_impl.ty.span().as_str(), item_fn.fn_signature.name.as_str()),
+            None => item_fn.fn_signature.name.as_str().to_string(),
+        };
+
+        // Storage keys passed to helper functions are accessed by the helper on behalf of the function, e.g. `increment(storage.x)`
+        let (func, args) = match context.expr {
+            Expr::FuncApp { func, args } => (Some(func.as_ref()), utils::fold_punctuated(&args.inner)),
+            Expr::MethodCall { args, .. } => (None, utils::fold_punctuated(&args.inner)),
+            _ => (None, vec![]),
+        };
+
+        for (index, arg) in args.into_iter().enumerate() {
+            let Some(storage_idents) = utils::get_storage_key_idents(arg) else { continue };
+            let idents = storage_idents.iter().map(|x| x.as_str().to_string()).collect::<Vec<_>>();
+
+            let (reads, writes) = module_state.get_key_arg_access(project, context.path, func, index);
+
+            for (is_write, is_accessed) in [(false, reads), (true, writes)] {
+                if is_accessed {
+                    module_state.accesses.push(Access {
+                        idents: idents.clone(),
+                        fn_name: fn_name.clone(),
+                        is_write,
+                    });
+                }
+            }
+        }
+
+        // Only check the outermost method call of a chain, e.g. `storage.x.get(i).unwrap().write(y)`
+        let Expr::MethodCall { .. } = context.expr else { return Ok(()) };
+
+        if module_state.visited_spans.contains(&context.expr.span()) {
+            return Ok(());
+        }
+
+        let mut target = context.expr;
+
+        while let Expr::MethodCall { target: inner_target, .. } = target {
+            module_state.visited_spans.insert(inner_target.span());
+            target = inner_target.as_ref();
+        }
+
+        // Get the function state
+        let fn_state = module_state.fn_states.get(&item_fn.fn_signature.span()).unwrap();
+
+        // Check for direct or indirect storage writes, e.g. `storage.x.write(y)` or `let x = storage.y.get(z); x.write(w);`
+        if let Some(storage_idents) = fn_state.storage_bindings.get_storage_write_idents(&context.blocks, context.expr) {
+            module_state.accesses.push(Access {
+                idents: storage_idents.iter().map(|x| x.as_str().to_string()).collect(),
+                fn_name,
+                is_write: true,
+            });
+
+            return Ok(());
+        }
+
+        // Check for storage reads, skipping chains which only derive a storage key, e.g. `storage.x.get(y)`
+        let idents = utils::fold_expr_idents(context.expr).iter().map(|x| x.as_str().to_string()).collect::<Vec<_>>();
+
+        if idents.len() < 3 || idents[0] != "storage" || idents.last().unwrap() == "get" {
+            return Ok(());
+        }
+
+        module_state.accesses.push(Access {
+            idents: idents[1..].to_vec(),
+            fn_name,
+            is_write: false,
+        });
+
+        Ok(())
+    }
+
+    fn leave_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let module_state = self.module_states.get_mut(context.path).unwrap();
+
+        // Attribute each access to the storage field it accesses
+        for access in module_state.accesses.iter() {
+            let Some(index) = module_state.find_field(&access.idents) else { continue };
+            let field = &mut module_state.fields[index].1;
+
+            let fn_names = if access.is_write { &mut field.writers } else { &mut field.readers };
+
+            if !fn_names.contains(&access.fn_name) {
+                fn_names.push(access.fn_name.clone());
+            }
+        }
+
+        for (_, field) in module_state.fields.iter() {
+            let mut field = field.clone();
+            field.write_only = !field.writers.is_empty() && field.readers.is_empty();
+            field.never_written = field.writers.is_empty();
+
+            self.fields.borrow_mut().push(field);
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats the supplied storage `fields` in the supplied `display_format`, which is either JSON, CSV or plain text.
+pub fn format_storage_layout(fields: &[StorageFieldAccess], display_format: DisplayFormat) -> String {
+    let mut fields = fields.to_vec();
+    fields.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+
+    match display_format {
+        DisplayFormat::Json => serde_json::to_string_pretty(&fields).unwrap(),
+
+        DisplayFormat::Csv => {
+            let mut result = String::from("file,line,field,type,initial_value,readers,writers,write_only,never_written\n");
+
+            for field in fields.iter() {
+                result.push_str(&format!(
+                    "{},{},{},{},{},{},{},{},{}\n",
+                    escape_csv(&field.file.to_string_lossy()),
+                    field.line.map(|x| x.to_string()).unwrap_or_default(),
+                    escape_csv(&field.name),
+                    escape_csv(&field.ty),
+                    escape_csv(&field.initial_value),
+                    escape_csv(&field.readers.join(";")),
+                    escape_csv(&field.writers.join(";")),
+                    field.write_only,
+                    field.never_written,
+                ));
+            }
+
+            result
+        }
+
+        _ => {
+            let join_or_none = |values: &[String]| if values.is_empty() { "none".to_string() } else { values.join(", ") };
+
+            let mut result = String::new();
+
+            for field in fields.iter() {
+                let line = field.line.map(|x| format!(":{x}")).unwrap_or_default();
+
+                let flag = if field.write_only {
+                    " [written but never read]"
+                } else if field.never_written {
+                    " [never written]"
+                } else {
+                    ""
+                };
+
+                result.push_str(&format!("{}{line}: {}: {} = {}{flag}\n", field.file.to_string_lossy(), field.name, field.ty, field.initial_value));
+                result.push_str(&format!("    read by: {}\n", join_or_none(&field.readers)));
+                result.push_str(&format!("    written by: {}\n", join_or_none(&field.writers)));
+            }
+
+            result.push_str(&format!("{} {}", fields.len(), if fields.len() == 1 { "storage field" } else { "storage fields" }));
+            result
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StorageLayoutVisitor;
+    use crate::{project::Project, Options};
+    use std::{cell::RefCell, path::PathBuf, rc::Rc};
+
+    #[test]
+    fn test_storage_layout() {
+        let options = Options {
+            directory: Some(PathBuf::from("test/storage_layout/")),
+            ..Default::default()
+        };
+
+        let fields = Rc::new(RefCell::new(vec![]));
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.set_visitor("storage_layout", Box::new(StorageLayoutVisitor::new(fields.clone())));
+        project.analyze_modules().unwrap();
+
+        let mut fields = fields.borrow().clone();
+        fields.sort_by_key(|x| x.line);
+
+        let names = fields.iter().map(|x| x.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["owners", "count", "last_touched", "admin", "items", "bumps", "nonces", "stats::total"]);
+
+        // owners
+        assert_eq!(fields[0].ty, "StorageMap<u64, Identity>");
+        assert_eq!(fields[0].readers, vec!["Contract::owner_of"]);
+        assert_eq!(fields[0].writers, vec!["Contract::register"]);
+
+        // count, which is also read through a helper function which only reads it
+        assert_eq!(fields[1].initial_value, "0");
+        assert_eq!(fields[1].readers, vec!["Contract::register", "Contract::current_count"]);
+        assert_eq!(fields[1].writers, vec!["Contract::register"]);
+        assert!(!fields[1].write_only);
+
+        // last_touched
+        assert!(fields[2].write_only);
+
+        // admin
+        assert!(fields[3].never_written);
+        assert!(!fields[3].write_only);
+
+        // items, which is only written through method chains
+        assert_eq!(fields[4].writers, vec!["Contract::push_item"]);
+        assert!(fields[4].write_only);
+
+        // bumps, which is only accessed through a helper function
+        assert_eq!(fields[5].readers, vec!["Contract::bump"]);
+        assert_eq!(fields[5].writers, vec!["Contract::bump"]);
+
+        // nonces, which is only written through a storage key variable
+        assert_eq!(fields[6].writers, vec!["Contract::reset_nonce"]);
+        assert!(fields[6].write_only);
+
+        // stats::total
+        assert_eq!(fields[7].readers, vec!["Contract::register"]);
+        assert_eq!(fields[7].writers, vec!["Contract::register"]);
+    }
+}
@@ -0,0 +1,593 @@
+use crate::{
+    error::Error,
+    project::{DisplayFormat, Project},
+    scope::AstScope,
+    utils,
+    visitor::{AstVisitor, ExprContext, FnContext, ModuleContext, StatementContext, UseContext},
+};
+use serde::Serialize;
+use std::{cell::RefCell, collections::HashMap, path::{Path, PathBuf}, rc::Rc};
+use sway_ast::{Expr, ItemKind, Statement};
+use sway_types::{Span, Spanned};
+
+/// The functions which transfer assets out of the contract.
+const TRANSFER_FUNCTIONS: &[&str] = &[
+    "std::asset::transfer",
+    "std::asset::transfer_to_address",
+    "std::asset::force_transfer_to_contract",
+    "std::asset::mint_to",
+];
+
+/// The functions which call other contracts without going through an abi cast.
+const LOW_LEVEL_CALL_FUNCTIONS: &[&str] = &[
+    "std::low_level_call::call_with_function_selector",
+];
+
+/// An externally callable function of a contract, along with a summary of what it can do.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EntryPoint {
+    pub file: PathBuf,
+    pub line: Option<usize>,
+    /// The name of the abi the function implements, or `None` for the `#[fallback]` function.
+    pub abi: Option<String>,
+    pub name: String,
+    pub payable: bool,
+    /// The storage access modes of the `#[storage]` attribute, e.g. `read` and `write`.
+    pub storage: Vec<String>,
+    pub access_checks: Vec<String>,
+    pub storage_writes: Vec<String>,
+    pub external_calls: Vec<String>,
+    pub asset_transfers: Vec<String>,
+    pub events: Vec<String>,
+}
+
+impl EntryPoint {
+    /// Gets the location of the function, e.g. `Vault::withdraw`.
+    fn location(&self) -> String {
+        match self.abi.as_ref() {
+            Some(abi) => format!("{abi}::{}", self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    /// Gets the attributes of the function which affect its attack surface, e.g. `#[payable]` and `#[storage(read, write)]`.
+    fn attributes(&self) -> Vec<String> {
+        let mut result = vec![];
+
+        if self.payable {
+            result.push("#[payable]".to_string());
+        }
+
+        if !self.storage.is_empty() {
+            result.push(format!("#[storage({})]", self.storage.join(", ")));
+        }
+
+        result
+    }
+}
+
+fn push_unique(values: &mut Vec<String>, value: String) {
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
+
+fn extend_unique(values: &mut Vec<String>, other: &[String]) {
+    for value in other {
+        push_unique(values, value.clone());
+    }
+}
+
+/// Checks if the supplied `expr` calls one of the functions with the supplied `paths`, either directly or through one of the imported `names`.
+fn is_fn_call(expr: &Expr, paths: &[&str], names: &[String]) -> bool {
+    let Expr::FuncApp { func, .. } = expr else { return false };
+    let name = func.span().as_str().to_string();
+
+    paths.contains(&name.as_str()) || names.contains(&name)
+}
+
+/// Gets the access checks of the free function which the supplied function `call` from the module at `path` resolves to,
+/// following the `imports` of the module, including the access checks of the functions it calls in any module of the `project`.
+fn module_fn_access_checks(
+    project: &Project,
+    path: &Path,
+    imports: &utils::ImportState,
+    call: &str,
+    visited: &mut Vec<(PathBuf, String)>,
+) -> Vec<String> {
+    let call_path = call.split("::").map(|x| x.trim().to_string()).collect::<Vec<_>>();
+
+    let modules = project.modules();
+    let modules = modules.borrow();
+
+    let Some((fn_path, _, item_fn)) = utils::resolve_module_fn(path, &call_path, imports, &modules, project.packages()) else { return vec![] };
+
+    // Skip functions which are already being checked, since they are recursive
+    let key = (fn_path.clone(), item_fn.fn_signature.name.as_str().to_string());
+
+    if visited.contains(&key) {
+        return vec![];
+    }
+
+    visited.push(key);
+
+    // Resolve the imports of the module declaring the function
+    let mut fn_imports = utils::ImportState::default();
+    let mut msg_sender_names = vec!["msg_sender".to_string()];
+
+    for item in modules[&fn_path].items.iter() {
+        let ItemKind::Use(item_use) = &item.value else { continue };
+
+        fn_imports.visit_use(item_use);
+
+        if let Some(name) = utils::use_tree_to_name(&item_use.tree, "std::auth::msg_sender") {
+            msg_sender_names.push(name);
+        }
+    }
+
+    let mut result = vec![];
+    let mut calls = vec![];
+
+    utils::map_block_exprs(&item_fn.body.inner, &mut |expr| {
+        if utils::is_access_restriction_expr(expr, msg_sender_names.as_slice()) {
+            push_unique(&mut result, compact_text(expr.span().as_str()));
+        }
+
+        if let Expr::FuncApp { func, .. } = expr {
+            if let Expr::Path(_) = func.as_ref() {
+                calls.push(func.span().as_str().to_string());
+            }
+        }
+    });
+
+    for call in calls {
+        extend_unique(&mut result, &module_fn_access_checks(project, &fn_path, &fn_imports, &call, visited));
+    }
+
+    result
+}
+
+/// Collapses the whitespace of the supplied source `text`, so multi-line code fits on a single line.
+fn compact_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[derive(Clone, Default)]
+struct Effects {
+    storage_writes: Vec<String>,
+    external_calls: Vec<String>,
+    asset_transfers: Vec<String>,
+    events: Vec<String>,
+}
+
+impl Effects {
+    fn len(&self) -> usize {
+        self.storage_writes.len() + self.external_calls.len() + self.asset_transfers.len() + self.events.len()
+    }
+
+    /// Adds the supplied `other` effects, returning whether any of them were new.
+    fn extend(&mut self, other: &Effects) -> bool {
+        let previous_len = self.len();
+
+        extend_unique(&mut self.storage_writes, &other.storage_writes);
+        extend_unique(&mut self.external_calls, &other.external_calls);
+        extend_unique(&mut self.asset_transfers, &other.asset_transfers);
+        extend_unique(&mut self.events, &other.events);
+
+        self.len() != previous_len
+    }
+}
+
+#[derive(Default)]
+struct ModuleState {
+    imports: utils::ImportState,
+    access_checks: utils::AccessCheckState,
+    declares_log_fn: bool,
+    transfer_fn_names: Vec<String>,
+    low_level_call_fn_names: Vec<String>,
+    fn_states: Vec<FnState>,
+}
+
+struct FnState {
+    name: String,
+    signature_span: Span,
+    entry_point: Option<EntryPoint>,
+    effects: Effects,
+    abi_variables: Vec<String>,
+    called_fn_names: Vec<String>,
+}
+
+impl ModuleState {
+    /// Checks if the supplied `expr` calls `std::logging::log`, either by its full path, through an import or through the prelude.
+    fn is_log_fn_call(&self, expr: &Expr) -> bool {
+        let Expr::FuncApp { func, .. } = expr else { return false };
+        let Expr::Path(path) = func.as_ref() else { return false };
+
+        let call_path = utils::fold_path_idents(path).iter().map(|x| x.as_str().to_string()).collect::<Vec<_>>();
+
+        if self.imports.resolve_path(&call_path) == ["std", "logging", "log"] {
+            return true;
+        }
+
+        // The prelude `log` function is shadowed by functions of the module and other imports with the same name
+        call_path == ["log"] && !self.declares_log_fn && !self.imports.imports.contains_key("log")
+    }
+
+    /// Computes the effects of each function, including the effects of the functions it calls.
+    fn resolve_effects(&self) -> Vec<Effects> {
+        let mut effects = self.fn_states.iter().map(|x| x.effects.clone()).collect::<Vec<_>>();
+
+        loop {
+            let mut changed = false;
+
+            for (index, fn_state) in self.fn_states.iter().enumerate() {
+                for fn_name in fn_state.called_fn_names.iter() {
+                    // Only follow calls to functions which are not entry points themselves
+                    let Some(called_index) = self.fn_states.iter().position(|x| x.entry_point.is_none() && x.name == *fn_name) else { continue };
+
+                    if called_index == index {
+                        continue;
+                    }
+
+                    let called_effects = effects[called_index].clone();
+
+                    if effects[index].extend(&called_effects) {
+                        changed = true;
+                    }
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        effects
+    }
+}
+
+/// Collects the externally callable functions of the visited contract modules, along with a summary of what each of them can do.
+pub struct SurfaceVisitor {
+    entry_points: Rc<RefCell<Vec<EntryPoint>>>,
+    module_states: HashMap<PathBuf, ModuleState>,
+}
+
+impl SurfaceVisitor {
+    pub fn new(entry_points: Rc<RefCell<Vec<EntryPoint>>>) -> Self {
+        Self {
+            entry_points,
+            module_states: HashMap::new(),
+        }
+    }
+}
+
+impl AstVisitor for SurfaceVisitor {
+    fn visit_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Only contracts have externally callable functions
+        if !utils::is_contract_module(context.module) {
+            return Ok(());
+        }
+
+        // Create the module state
+        if !self.module_states.contains_key(context.path) {
+            self.module_states.insert(context.path.into(), ModuleState {
+                declares_log_fn: utils::find_module_fn(context.module, "log").is_some(),
+                ..Default::default()
+            });
+        }
+
+        Ok(())
+    }
+
+    fn visit_use(&mut self, context: &UseContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        module_state.imports.visit_use(context.item_use);
+        module_state.access_checks.visit_use(context.item_use);
+
+        // Check the use tree for the functions we keep track of
+        for &path in TRANSFER_FUNCTIONS {
+            if let Some(name) = utils::use_tree_to_name(&context.item_use.tree, path) {
+                module_state.transfer_fn_names.push(name);
+            }
+        }
+
+        for &path in LOW_LEVEL_CALL_FUNCTIONS {
+            if let Some(name) = utils::use_tree_to_name(&context.item_use.tree, path) {
+                module_state.low_level_call_fn_names.push(name);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_fn(&mut self, context: &FnContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        let fn_name = context.item_fn.fn_signature.name.as_str().to_string();
+        let signature_span = context.item_fn.fn_signature.span();
+
+        module_state.access_checks.visit_fn(context.item_impl, context.item_fn);
+
+        // Functions of `impl <abi> for Contract` and the `#[fallback]` function are externally callable
+        let abi_name = context.item_impl.and_then(|item_impl| {
+            let (path_type, _) = item_impl.trait_opt.as_ref()?;
+            (item_impl.ty.span().as_str() == "Contract").then(|| utils::path_type_to_string(path_type))
+        });
+
+        let is_fallback = context.item_impl.is_none() && utils::check_attribute_decls(context.fn_attributes, "fallback", &[]);
+
+        let entry_point = if abi_name.is_some() || is_fallback {
+            let storage = ["read", "write"].into_iter()
+                .filter(|&x| utils::check_attribute_decls(context.fn_attributes, "storage", &[x]))
+                .map(|x| x.to_string())
+                .collect();
+
+            Some(EntryPoint {
+                file: context.path.into(),
+                line: project.span_to_line(context.path, &signature_span)?,
+                abi: abi_name,
+                name: fn_name.clone(),
+                payable: utils::check_attribute_decls(context.fn_attributes, "payable", &[]),
+                storage,
+                ..Default::default()
+            })
+        } else {
+            None
+        };
+
+        // Create the function state
+        module_state.fn_states.push(FnState {
+            name: fn_name,
+            signature_span,
+            entry_point,
+            effects: Effects::default(),
+            abi_variables: vec![],
+            called_fn_names: vec![],
+        });
+
+        Ok(())
+    }
+
+    fn visit_statement(&mut self, context: &StatementContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Only check variable bindings of abi casts, e.g. `let x = abi(MyAbi, contract_id);`
+        let Statement::Let(statement_let) = context.statement else { return Ok(()) };
+        let Expr::AbiCast { .. } = &statement_let.expr else { return Ok(()) };
+
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        // Get the function state
+        let signature_span = context.item_fn.fn_signature.span();
+        let Some(fn_state) = module_state.fn_states.iter_mut().rev().find(|x| x.signature_span == signature_span) else { return Ok(()) };
+
+        for ident in utils::fold_pattern_idents(&statement_let.pattern) {
+            fn_state.abi_variables.push(ident.as_str().to_string());
+        }
+
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, context: &ExprContext, _scope: Rc<RefCell<AstScope>>, _project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get_mut(context.path) else { return Ok(()) };
+
+        let Some(item_fn) = context.item_fn.as_ref() else { return Ok(()) };
+
+        // Check for access control checks
+        module_state.access_checks.visit_expr(item_fn, context.expr);
+
+        let is_log_fn_call = module_state.is_log_fn_call(context.expr);
+
+        // Get the function state
+        let signature_span = item_fn.fn_signature.span();
+        let Some(fn_state) = module_state.fn_states.iter_mut().rev().find(|x| x.signature_span == signature_span) else { return Ok(()) };
+
+        match context.expr {
+            Expr::FuncApp { args, .. } => {
+                // Check for asset transfers
+                if is_fn_call(context.expr, TRANSFER_FUNCTIONS, &module_state.transfer_fn_names) {
+                    push_unique(&mut fn_state.effects.asset_transfers, compact_text(context.expr.span().as_str()));
+                }
+                // Check for low level calls to other contracts
+                else if is_fn_call(context.expr, LOW_LEVEL_CALL_FUNCTIONS, &module_state.low_level_call_fn_names) {
+                    push_unique(&mut fn_state.effects.external_calls, compact_text(context.expr.span().as_str()));
+                }
+                // Check for logged events, which are named after the type of their struct if possible
+                else if is_log_fn_call {
+                    let Some(arg) = utils::fold_punctuated(&args.inner).first().copied() else { return Ok(()) };
+
+                    let event = match arg {
+                        Expr::Struct { path, .. } => utils::path_expr_to_string(path),
+                        _ => compact_text(arg.span().as_str()),
+                    };
+
+                    push_unique(&mut fn_state.effects.events, event);
+                }
+                // Store the names of other called functions, since they can have effects on behalf of the function
+                else if let Some(fn_name) = utils::get_fn_call_name(context.expr) {
+                    push_unique(&mut fn_state.called_fn_names, fn_name);
+                }
+            }
+
+            Expr::MethodCall { target, path_seg, .. } => {
+                // Check for storage writes
+                let storage_idents = utils::fold_expr_idents(context.expr);
+
+                if storage_idents.len() >= 3 && storage_idents[0].as_str() == "storage" && utils::is_storage_write_fn(path_seg.name.as_str()) {
+                    push_unique(&mut fn_state.effects.storage_writes, storage_idents[1].as_str().to_string());
+                }
+
+                // Check for calls to other contracts, either through an abi cast or a variable bound to one
+                let is_external_call = match target.as_ref() {
+                    Expr::AbiCast { .. } => true,
+                    Expr::Path(_) => fn_state.abi_variables.iter().any(|x| x == target.span().as_str()),
+                    _ => false,
+                };
+
+                if is_external_call {
+                    push_unique(
+                        &mut fn_state.effects.external_calls,
+                        format!("{}.{}", compact_text(target.span().as_str()), path_seg.name.as_str()),
+                    );
+                }
+            }
+
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn leave_module(&mut self, context: &ModuleContext, _scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
+        // Get the module state
+        let Some(module_state) = self.module_states.get(context.path) else { return Ok(()) };
+
+        // Summarize the entry points, including the effects of the functions they call
+        for (fn_state, effects) in module_state.fn_states.iter().zip(module_state.resolve_effects()) {
+            let Some(entry_point) = fn_state.entry_point.as_ref() else { continue };
+
+            let mut entry_point = entry_point.clone();
+
+            // Collect the access checks of the function and of the functions it calls, including the functions of other modules
+            let mut visited = vec![];
+
+            for access_fn_state in module_state.access_checks.reachable_fn_states(&fn_state.signature_span) {
+                for span in access_fn_state.access_check_spans.iter() {
+                    push_unique(&mut entry_point.access_checks, compact_text(span.as_str()));
+                }
+
+                for call in access_fn_state.function_calls.iter() {
+                    let access_checks = module_fn_access_checks(project, context.path, &module_state.imports, call, &mut visited);
+                    extend_unique(&mut entry_point.access_checks, &access_checks);
+                }
+            }
+
+            extend_unique(&mut entry_point.storage_writes, &effects.storage_writes);
+            extend_unique(&mut entry_point.external_calls, &effects.external_calls);
+            extend_unique(&mut entry_point.asset_transfers, &effects.asset_transfers);
+            extend_unique(&mut entry_point.events, &effects.events);
+
+            self.entry_points.borrow_mut().push(entry_point);
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats the supplied `entry_points` in the supplied `display_format`, which is either JSON, a Markdown table or plain text.
+pub fn format_entry_points(entry_points: &[EntryPoint], display_format: DisplayFormat) -> String {
+    let mut entry_points = entry_points.to_vec();
+    entry_points.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+
+    let join_or_none = |values: &[String]| if values.is_empty() { "none".to_string() } else { values.join(", ") };
+
+    match display_format {
+        DisplayFormat::Json => serde_json::to_string_pretty(&entry_points).unwrap(),
+
+        DisplayFormat::Markdown => {
+            let mut result = String::new();
+
+            result.push_str("| Function | File | Attributes | Access Checks | Storage Writes | External Calls | Asset Transfers | Events |\n");
+            result.push_str("|----------|------|------------|---------------|----------------|----------------|-----------------|--------|\n");
+
+            let cell = |values: &[String]| if values.is_empty() { "-".to_string() } else { values.iter().map(|x| format!("`{}`", x.replace('|', "\\|"))).collect::<Vec<_>>().join("<br>") };
+
+            for entry_point in entry_points.iter() {
+                let line = entry_point.line.map(|x| format!(":{x}")).unwrap_or_default();
+
+                result.push_str(&format!(
+                    "| `{}` | `{}{line}` | {} | {} | {} | {} | {} | {} |\n",
+                    entry_point.location(),
+                    entry_point.file.to_string_lossy(),
+                    cell(&entry_point.attributes()),
+                    cell(&entry_point.access_checks),
+                    cell(&entry_point.storage_writes),
+                    cell(&entry_point.external_calls),
+                    cell(&entry_point.asset_transfers),
+                    cell(&entry_point.events),
+                ));
+            }
+
+            result
+        }
+
+        _ => {
+            let mut result = String::new();
+
+            for entry_point in entry_points.iter() {
+                let line = entry_point.line.map(|x| format!(":{x}")).unwrap_or_default();
+                result.push_str(&format!("{}{line}: {}\n", entry_point.file.to_string_lossy(), entry_point.location()));
+                result.push_str(&format!("    attributes: {}\n", join_or_none(&entry_point.attributes())));
+                result.push_str(&format!("    access checks: {}\n", join_or_none(&entry_point.access_checks)));
+                result.push_str(&format!("    storage writes: {}\n", join_or_none(&entry_point.storage_writes)));
+                result.push_str(&format!("    external calls: {}\n", join_or_none(&entry_point.external_calls)));
+                result.push_str(&format!("    asset transfers: {}\n", join_or_none(&entry_point.asset_transfers)));
+                result.push_str(&format!("    events: {}\n", join_or_none(&entry_point.events)));
+            }
+
+            result.push_str(&format!("{} {}", entry_points.len(), if entry_points.len() == 1 { "entry point" } else { "entry points" }));
+            result
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SurfaceVisitor;
+    use crate::{project::Project, Options};
+    use std::{cell::RefCell, path::PathBuf, rc::Rc};
+
+    #[test]
+    fn test_surface() {
+        let options = Options {
+            directory: Some(PathBuf::from("test/surface/")),
+            ..Default::default()
+        };
+
+        let entry_points = Rc::new(RefCell::new(vec![]));
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.set_visitor("surface", Box::new(SurfaceVisitor::new(entry_points.clone())));
+        project.analyze_modules().unwrap();
+
+        let mut entry_points = entry_points.borrow().clone();
+        entry_points.sort_by_key(|x| x.line);
+
+        let names = entry_points.iter().map(|x| x.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["deposit", "withdraw", "set_owner", "ping_oracle", "balance", "pause"]);
+
+        // deposit
+        assert!(entry_points[0].payable);
+        assert_eq!(entry_points[0].storage, vec!["read", "write"]);
+        assert!(entry_points[0].access_checks.is_empty());
+        assert_eq!(entry_points[0].storage_writes, vec!["balance"]);
+        assert_eq!(entry_points[0].events, vec!["DepositEvent"]);
+
+        // withdraw, which is checked through a helper function
+        assert!(!entry_points[1].payable);
+        assert_eq!(entry_points[1].access_checks.len(), 1);
+        assert_eq!(entry_points[1].storage_writes, vec!["balance"]);
+        assert_eq!(entry_points[1].asset_transfers, vec!["transfer(to, AssetId::base(), amount)"]);
+
+        // set_owner
+        assert_eq!(entry_points[2].access_checks.len(), 1);
+        assert_eq!(entry_points[2].storage_writes, vec!["owner"]);
+        assert_eq!(entry_points[2].events, vec!["OwnerChangedEvent"]);
+
+        // ping_oracle
+        assert!(entry_points[3].storage.is_empty());
+        assert_eq!(entry_points[3].external_calls, vec!["oracle.ping"]);
+
+        // balance
+        assert_eq!(entry_points[4].storage, vec!["read"]);
+        assert!(entry_points[4].storage_writes.is_empty());
+        assert!(entry_points[4].external_calls.is_empty());
+
+        // pause, which is checked through a helper function of another module
+        assert_eq!(entry_points[5].access_checks, vec![r#"require(msg_sender().unwrap() == owner, "Not owner")"#]);
+        assert_eq!(entry_points[5].storage_writes, vec!["paused"]);
+    }
+}
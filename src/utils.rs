@@ -1,5 +1,7 @@
+use crate::forc::{normalize_path_lexically, Package, PackageGraph};
 use sway_ast::{assignable::ElementAccess, attribute::{Annotated, Attribute}, ty::{TyArrayDescriptor, TyTupleDescriptor}, *};
 use sway_types::{BaseIdent, Span, Spanned};
+use std::{collections::HashMap, path::{Path, PathBuf}};
 
 pub fn fold_punctuated<T, P>(punctuated: &Punctuated<T, P>) -> Vec<&T> {
     let mut result = vec![];
@@ -623,6 +625,104 @@ pub fn map_pattern_and_ty<F: FnMut(&Pattern, &Ty)>(pattern: &Pattern, ty: &Ty, f
     }
 }
 
+/// Calls `f` with the span of each name referenced by the supplied `expr`, including the names of the types in its generic arguments.
+pub fn map_expr_usage<F: FnMut(&Span)>(expr: &Expr, f: &mut F) {
+    map_expr(expr, &mut |expr| {
+        if let Expr::Path(path_expr) = expr {
+            map_path_expr_usage(path_expr, f);
+        }
+    });
+}
+
+/// Calls `f` with the span of each name referenced by the supplied `pattern`.
+pub fn map_pattern_usage<F: FnMut(&Span)>(pattern: &Pattern, f: &mut F) {
+    map_pattern(pattern, &mut |pattern| {
+        if let Pattern::Constructor { path, .. } | Pattern::Struct { path, .. } = pattern {
+            map_path_expr_usage(path, f);
+        }
+    });
+}
+
+/// Calls `f` with the span of each name referenced by the supplied `path`.
+pub fn map_path_expr_usage<F: FnMut(&Span)>(path: &PathExpr, f: &mut F) {
+    let mut map_segment_usage = |segment: &PathExprSegment| {
+        f(&segment.name.span());
+
+        if let Some((_, generics)) = segment.generics_opt.as_ref() {
+            for ty in &generics.parameters.inner {
+                map_ty_usage(ty, f);
+            }
+        }
+    };
+
+    map_segment_usage(&path.prefix);
+
+    for (_, suffix) in path.suffix.iter() {
+        map_segment_usage(suffix);
+    }
+}
+
+/// Calls `f` with the span of each name referenced by the supplied `path`.
+pub fn map_path_type_usage<F: FnMut(&Span)>(path: &PathType, f: &mut F) {
+    f(&path.prefix.name.span());
+
+    if let Some(generics) = path.prefix.generics_opt.as_ref() {
+        for ty in &generics.1.parameters.inner {
+            map_ty_usage(ty, f);
+        }
+    }
+
+    if let Some(root) = path.root_opt.as_ref() {
+        if let Some(root) = root.0.as_ref() {
+            map_ty_usage(root.inner.ty.as_ref(), f);
+            map_path_type_usage(root.inner.as_trait.1.as_ref(), f);
+        }
+    }
+}
+
+/// Calls `f` with the span of each name referenced by the supplied `ty`.
+pub fn map_ty_usage<F: FnMut(&Span)>(ty: &Ty, f: &mut F) {
+    match ty {
+        Ty::Path(path) => {
+            map_path_type_usage(path, f);
+        }
+
+        Ty::Tuple(tuple) => {
+            if let TyTupleDescriptor::Cons { head, tail, .. } = &tuple.inner {
+                map_ty_usage(head.as_ref(), f);
+
+                for ty in tail {
+                    map_ty_usage(ty, f);
+                }
+            }
+        }
+
+        Ty::Array(array) => {
+            map_ty_usage(&array.inner.ty, f);
+            map_expr_usage(array.inner.length.as_ref(), f);
+        }
+
+        Ty::StringSlice(_) => {}
+        Ty::StringArray { .. } => {}
+        Ty::Infer { .. } => {}
+
+        Ty::Ptr { ty, .. } |
+        Ty::Slice { ty, .. } => {
+            map_ty_usage(ty.inner.as_ref(), f);
+        }
+
+        Ty::Ref { ty, .. } => {
+            map_ty_usage(ty.as_ref(), f);
+        }
+
+        Ty::Never { .. } => {}
+
+        Ty::Expr(expr) => {
+            map_expr_usage(expr, f);
+        }
+    }
+}
+
 pub fn check_attribute_decls(
     attribute_decls: &[AttributeDecl],
     attribute_name: &str,
@@ -1144,6 +1244,15 @@ fn is_storage_vec_write_fn(s: &str) -> bool {
     matches!(s, "push" | "pop" | "remove" | "swap_remove" | "set" | "insert" | "clear" | "swap" | "reverse" | "fill" | "resize")
 }
 
+/// Checks if the storage method named `s` writes to storage.
+pub fn is_storage_write_fn(s: &str) -> bool {
+    is_storage_bytes_write_fn(s)
+        || is_storage_key_write_fn(s)
+        || is_storage_map_write_fn(s)
+        || is_storage_string_write_fn(s)
+        || is_storage_vec_write_fn(s)
+}
+
 pub fn storage_write_statement_to_storage_variable_ident(statement: &Statement) -> Option<BaseIdent> {
     let Statement::Expr { expr, .. } = statement else { return None };
     let Expr::MethodCall { .. } = expr else { return None };
@@ -1212,6 +1321,82 @@ pub fn statement_to_storage_write_idents(statement: &Statement) -> Option<(BaseI
     Some((storage_idents[1].clone(), variable_idents[0].clone()))
 }
 
+/// Gets the identifiers following `storage` of the storage key passed by value in the supplied `expr`, e.g. `storage.x`
+/// or `storage::ns.x` without a method call, which helper functions can read or write on behalf of the caller.
+pub fn get_storage_key_idents(expr: &Expr) -> Option<Vec<BaseIdent>> {
+    let Expr::FieldProjection { .. } = expr else { return None };
+    let idents = fold_expr_idents(expr);
+
+    if idents.len() < 2 || idents[0].as_str() != "storage" {
+        return None;
+    }
+
+    Some(idents[1..].to_vec())
+}
+
+/// Tracks the variables bound to storage keys, e.g. `let x = storage.y.get(z);`, so writes through them can be attributed to their storage field.
+#[derive(Default)]
+pub struct StorageBindingState {
+    bindings: HashMap<Span, Vec<(BaseIdent, Vec<BaseIdent>)>>,
+}
+
+impl StorageBindingState {
+    /// Records the storage binding declared by the supplied `statement` in the innermost of the supplied `blocks`.
+    pub fn visit_statement(&mut self, blocks: &[Span], statement: &Statement) {
+        let Statement::Let(StatementLet {
+            pattern: Pattern::AmbiguousSingleIdent(variable_name),
+            expr,
+            ..
+        }) = statement else { return };
+
+        let Some(block_span) = blocks.last() else { return };
+        let idents = fold_expr_idents(expr);
+
+        if idents.len() < 3 || idents[0].as_str() != "storage" {
+            return;
+        }
+
+        let ("get" | "read") = idents.last().unwrap().as_str() else { return };
+
+        self.bindings.entry(block_span.clone()).or_default().push((variable_name.clone(), idents[1..].to_vec()));
+    }
+
+    /// Gets the identifiers following `storage` of the storage field written by the supplied method call `expr`, either directly,
+    /// e.g. `storage.x.write(y)` or `storage.x.get(k).unwrap().write(y)`, or through a variable bound in one of the supplied `blocks`.
+    pub fn get_storage_write_idents(&self, blocks: &[Span], expr: &Expr) -> Option<Vec<BaseIdent>> {
+        let Expr::MethodCall { .. } = expr else { return None };
+        let idents = fold_expr_idents(expr);
+
+        if idents.len() < 2 {
+            return None;
+        }
+
+        // Check the expression for direct storage access
+        if idents[0].as_str() == "storage" {
+            if idents.len() < 3 || !idents[2..].iter().any(|x| is_storage_write_fn(x.as_str())) {
+                return None;
+            }
+
+            return Some(idents[1..].to_vec());
+        }
+
+        // Check the expression for indirect storage access
+        if !idents[1..].iter().any(|x| is_storage_write_fn(x.as_str())) {
+            return None;
+        }
+
+        for block_span in blocks.iter().rev() {
+            let Some(bindings) = self.bindings.get(block_span) else { continue };
+
+            if let Some((_, storage_idents)) = bindings.iter().rev().find(|(x, _)| x.as_str() == idents[0].as_str()) {
+                return Some(storage_idents.clone());
+            }
+        }
+
+        None
+    }
+}
+
 pub fn block_has_revert(block: &Braces<CodeBlockContents>) -> bool {
     // Check if `if_expr.then_block` contains a revert
     let mut has_revert = false;
@@ -1280,7 +1465,25 @@ pub fn collect_storage_fields(module: &Module) -> Vec<&StorageField> {
         ..
     }) = module.items.iter().find(|x| matches!(x.value, ItemKind::Storage(_))) else { return vec![] };
 
-    fold_punctuated(&storage.entries.inner).iter().map(|x| x.value.field.as_ref().unwrap()).collect()
+    fn collect_entry_fields<'a>(entry: &'a StorageEntry, fields: &mut Vec<&'a StorageField>) {
+        if let Some(field) = entry.field.as_ref() {
+            fields.push(field);
+        }
+
+        if let Some(entries) = entry.namespace.as_ref() {
+            for entry in fold_punctuated(&entries.inner) {
+                collect_entry_fields(entry.value.as_ref(), fields);
+            }
+        }
+    }
+
+    let mut fields = vec![];
+
+    for entry in fold_punctuated(&storage.entries.inner) {
+        collect_entry_fields(&entry.value, &mut fields);
+    }
+
+    fields
 }
 
 pub fn is_boolean_literal_or_negation(expr: &Expr) -> bool {
@@ -1770,3 +1973,599 @@ pub fn create_ident_ty(name: &str) -> Ty {
         suffix: vec![],
     })
 }
+
+/// Attempts to get the root directory of the Forc package containing the source file at the supplied `path`.
+pub fn get_package_root(path: &Path) -> Option<&Path> {
+    path.ancestors().skip(1).find(|x| x.join("Forc.toml").is_file())
+}
+
+/// Checks if the supplied `module` is a contract.
+#[inline]
+pub fn is_contract_module(module: &Module) -> bool {
+    matches!(module.kind, ModuleKind::Contract { .. })
+}
+
+/// Checks if the supplied `module` is a predicate.
+#[inline]
+pub fn is_predicate_module(module: &Module) -> bool {
+    matches!(module.kind, ModuleKind::Predicate { .. })
+}
+
+/// Checks if the supplied `module` is a script.
+#[inline]
+pub fn is_script_module(module: &Module) -> bool {
+    matches!(module.kind, ModuleKind::Script { .. })
+}
+
+/// The functions of `std::inputs` which read an input by its index.
+pub const INDEXED_INPUT_FNS: &[&str] = &[
+    "input_type",
+    "input_amount",
+    "input_asset_id",
+    "input_coin_owner",
+    "input_predicate",
+    "input_predicate_data",
+    "input_message_sender",
+    "input_message_recipient",
+    "input_message_nonce",
+    "input_message_data",
+];
+
+/// The functions of `std::outputs` which read an output by its index.
+pub const INDEXED_OUTPUT_FNS: &[&str] = &[
+    "output_type",
+    "output_amount",
+    "output_asset_id",
+    "output_asset_to",
+];
+
+/// Gets the name of the function called by the supplied `expr`, without its path prefix.
+pub fn get_fn_call_name(expr: &Expr) -> Option<String> {
+    let Expr::FuncApp { func, .. } = expr else { return None };
+    let Expr::Path(path) = func.as_ref() else { return None };
+    fold_path_idents(path).last().map(|x| x.as_str().to_string())
+}
+
+/// Checks if the supplied `expr` is the boolean literal `true`, ignoring parentheses.
+pub fn is_true_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Parens(expr) => is_true_literal(expr.inner.as_ref()),
+        Expr::Literal(x) => x.span().as_str() == "true",
+        _ => false,
+    }
+}
+
+/// Checks if the supplied `expr` is a comparison.
+pub fn is_comparison_expr(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Equal { .. }
+        | Expr::NotEqual { .. }
+        | Expr::LessThan { .. }
+        | Expr::GreaterThan { .. }
+        | Expr::LessThanEq { .. }
+        | Expr::GreaterThanEq { .. }
+    )
+}
+
+/// Calls `f` with every expression of the supplied `block`, including the expressions of nested blocks.
+pub fn map_block_exprs<F: FnMut(&Expr)>(block: &CodeBlockContents, f: &mut F) {
+    fn map_nested_expr<F: FnMut(&Expr)>(expr: &Expr, f: &mut F) {
+        map_expr(expr, &mut |expr| {
+            f(expr);
+
+            match expr {
+                Expr::Block(block) => map_block_exprs(&block.inner, f),
+
+                Expr::If(if_expr) => {
+                    let mut if_expr = if_expr;
+
+                    loop {
+                        map_block_exprs(&if_expr.then_block.inner, f);
+
+                        match if_expr.else_opt.as_ref() {
+                            Some((_, sway_ast::expr::LoopControlFlow::Continue(else_if_expr))) => if_expr = else_if_expr.as_ref(),
+                            Some((_, sway_ast::expr::LoopControlFlow::Break(else_block))) => {
+                                map_block_exprs(&else_block.inner, f);
+                                break;
+                            }
+                            None => break,
+                        }
+                    }
+                }
+
+                Expr::Match { branches, .. } => {
+                    for branch in branches.inner.iter() {
+                        match &branch.kind {
+                            MatchBranchKind::Block { block, .. } => map_block_exprs(&block.inner, f),
+                            MatchBranchKind::Expr { expr, .. } => map_nested_expr(expr, f),
+                        }
+                    }
+                }
+
+                Expr::While { block, .. } => map_block_exprs(&block.inner, f),
+
+                Expr::For { iterator, block, .. } => {
+                    map_nested_expr(iterator.as_ref(), f);
+                    map_block_exprs(&block.inner, f);
+                }
+
+                _ => {}
+            }
+        });
+    }
+
+    for statement in block.statements.iter() {
+        match statement {
+            Statement::Let(stmt_let) => map_nested_expr(&stmt_let.expr, f),
+            Statement::Expr { expr, .. } => map_nested_expr(expr, f),
+            Statement::Item(_) | Statement::Error(_, _) => {}
+        }
+    }
+
+    if let Some(expr) = block.final_expr_opt.as_ref() {
+        map_nested_expr(expr.as_ref(), f);
+    }
+}
+
+/// Checks if the supplied `block` calls the function with the supplied `name`, with or without a path prefix.
+pub fn block_calls_fn(block: &CodeBlockContents, name: &str) -> bool {
+    let mut result = false;
+
+    map_block_exprs(block, &mut |expr| {
+        if get_fn_call_name(expr).map(|x| x == name).unwrap_or(false) {
+            result = true;
+        }
+    });
+
+    result
+}
+
+/// Checks if the supplied `module` is an SRC-14 proxy contract, either by implementing the `SRC14` abi
+/// or by declaring a `#[fallback]` function that delegates to `run_external`.
+pub fn is_src14_proxy_module(module: &Module) -> bool {
+    if !is_contract_module(module) {
+        return false;
+    }
+
+    module.items.iter().any(|item| match &item.value {
+        ItemKind::Impl(item_impl) => {
+            let Some((path_type, _)) = item_impl.trait_opt.as_ref() else { return false };
+            let segment = path_type.suffix.last().map(|(_, x)| x).unwrap_or(&path_type.prefix);
+            segment.name.as_str() == "SRC14"
+        }
+
+        ItemKind::Fn(item_fn) => {
+            check_attribute_decls(item.attributes.as_slice(), "fallback", &[])
+                && block_calls_fn(&item_fn.body.inner, "run_external")
+        }
+
+        _ => false,
+    })
+}
+
+/// Checks if the contract `module` at the supplied `path` is a potential implementation (target) of an SRC-14 proxy contract.
+///
+/// A contract is considered an implementation when it is not a proxy itself and its Forc package is linked to the package of a proxy contract
+/// by a dependency in either direction, e.g. a `[contract-dependencies]` entry of the proxy.
+pub fn is_proxy_implementation_module(path: &Path, module: &Module, modules: &HashMap<PathBuf, Module>, packages: &PackageGraph) -> bool {
+    if !is_contract_module(module) || is_src14_proxy_module(module) {
+        return false;
+    }
+
+    let Some(package) = packages.package_for_path(path) else { return false };
+
+    modules.iter().any(|(proxy_path, proxy_module)| {
+        if !is_src14_proxy_module(proxy_module) {
+            return false;
+        }
+
+        let Some(proxy_package) = packages.package_for_path(proxy_path) else { return false };
+
+        if proxy_package.root == package.root {
+            return false;
+        }
+
+        packages.get_dependencies(proxy_package).iter().any(|x| x.root == package.root)
+            || packages.get_dependencies(package).iter().any(|x| x.root == proxy_package.root)
+    })
+}
+
+/// Checks if the supplied `expr` restricts access to the caller, either by a `require` or `if`-`revert` that
+/// depends on `msg_sender()`, or by a call to one of the access control functions from `sway_libs`.
+pub fn is_access_restriction_expr(expr: &Expr, msg_sender_names: &[String]) -> bool {
+    if let Expr::FuncApp { func, .. } = expr {
+        if let Expr::Path(path) = func.as_ref() {
+            if let Some(name) = fold_path_idents(path).last() {
+                if matches!(name.as_str(), "only_owner" | "only_admin" | "only_proxy_owner") {
+                    return true;
+                }
+            }
+        }
+    }
+
+    let condition = if let Some(require_args) = get_require_args(expr) {
+        let Some(condition) = require_args.first() else { return false };
+        *condition
+    } else if let Some(IfCondition::Expr(condition)) = get_if_revert_condition(expr) {
+        condition.as_ref()
+    } else {
+        return false;
+    };
+
+    let mut result = false;
+
+    map_expr(condition, &mut |expr| {
+        let Expr::FuncApp { func, .. } = expr else { return };
+        let name = func.span().as_str().to_string();
+
+        if name == "std::auth::msg_sender" || msg_sender_names.contains(&name) {
+            result = true;
+        }
+    });
+
+    result
+}
+
+/// The access restriction state of a function, used by [`AccessCheckState`].
+pub struct AccessCheckFnState {
+    pub name: String,
+    pub impl_span: Option<Span>,
+    pub has_access_check: bool,
+    pub has_requirement: bool,
+    /// The spans of the expressions which restrict access, e.g. `require(msg_sender().unwrap() == owner, "Not owner")`.
+    pub access_check_spans: Vec<Span>,
+    pub function_calls: Vec<String>,
+}
+
+/// Tracks which functions of a module restrict access to their callers, keyed by the spans of their signatures.
+pub struct AccessCheckState {
+    pub msg_sender_names: Vec<String>,
+    pub fn_states: HashMap<Span, AccessCheckFnState>,
+}
+
+impl Default for AccessCheckState {
+    fn default() -> Self {
+        Self {
+            // Since `std::auth::msg_sender` is part of the prelude, include it here
+            msg_sender_names: vec!["msg_sender".into()],
+            fn_states: Default::default(),
+        }
+    }
+}
+
+impl AccessCheckState {
+    /// Records the name `std::auth::msg_sender` is imported as by the supplied `item_use`, if any.
+    pub fn visit_use(&mut self, item_use: &ItemUse) {
+        if let Some(name) = use_tree_to_name(&item_use.tree, "std::auth::msg_sender") {
+            self.msg_sender_names.push(name);
+        }
+    }
+
+    /// Creates the state of the supplied `item_fn`, which is declared in the supplied `item_impl` if any.
+    pub fn visit_fn(&mut self, item_impl: Option<&ItemImpl>, item_fn: &ItemFn) {
+        self.fn_states.entry(item_fn.fn_signature.span()).or_insert_with(|| AccessCheckFnState {
+            name: item_fn.fn_signature.name.as_str().to_string(),
+            impl_span: item_impl.map(|x| x.span()),
+            has_access_check: false,
+            has_requirement: false,
+            access_check_spans: vec![],
+            function_calls: vec![],
+        });
+    }
+
+    /// Records the access restriction, requirement or function call of the supplied `expr` inside of the supplied `item_fn`.
+    pub fn visit_expr(&mut self, item_fn: &ItemFn, expr: &Expr) {
+        let is_access_restriction = is_access_restriction_expr(expr, self.msg_sender_names.as_slice());
+        let Some(fn_state) = self.fn_states.get_mut(&item_fn.fn_signature.span()) else { return };
+
+        if is_access_restriction {
+            fn_state.has_access_check = true;
+            fn_state.access_check_spans.push(expr.span());
+        }
+
+        if get_require_args(expr).is_some() || get_if_revert_condition(expr).is_some() {
+            fn_state.has_requirement = true;
+        }
+
+        if let Expr::FuncApp { func, .. } = expr {
+            if let Expr::Path(_) = func.as_ref() {
+                let func = func.span().as_str().to_string();
+
+                if !fn_state.function_calls.contains(&func) {
+                    fn_state.function_calls.push(func);
+                }
+            }
+        }
+    }
+
+    /// Gets the signature spans of the functions the supplied function `call` may resolve to from the supplied `caller`.
+    ///
+    /// Calls through `Self::` resolve to the functions of the caller's impl, and other calls resolve to free functions.
+    fn resolve_fn_call(&self, caller: &AccessCheckFnState, call: &str) -> Vec<&Span> {
+        let (name, impl_span) = match call.strip_prefix("Self::") {
+            Some(name) => (name, caller.impl_span.as_ref()),
+            None => (call, None),
+        };
+
+        self.fn_states.iter()
+            .filter(|(_, x)| x.name == name && x.impl_span.as_ref() == impl_span)
+            .map(|(span, _)| span)
+            .collect()
+    }
+
+    /// Checks if the function with the supplied `signature_span` restricts access, either directly or through one of the functions it calls.
+    pub fn fn_has_access_check(&self, signature_span: &Span) -> bool {
+        self.fn_reaches(signature_span, |x| x.has_access_check)
+    }
+
+    /// Checks if the function with the supplied `signature_span` contains a requirement, either directly or through one of the functions it calls.
+    pub fn fn_has_requirement(&self, signature_span: &Span) -> bool {
+        self.fn_reaches(signature_span, |x| x.has_requirement)
+    }
+
+    /// Checks if the supplied `predicate` holds for the function with the supplied `signature_span` or any of the functions it calls.
+    fn fn_reaches(&self, signature_span: &Span, predicate: impl Fn(&AccessCheckFnState) -> bool) -> bool {
+        self.reachable_fn_states(signature_span).into_iter().any(predicate)
+    }
+
+    /// Gets the states of the function with the supplied `signature_span` and of the functions of the module it calls, directly or indirectly.
+    pub fn reachable_fn_states(&self, signature_span: &Span) -> Vec<&AccessCheckFnState> {
+        let mut result = vec![];
+        let mut visited = vec![];
+        let mut queue = vec![signature_span];
+
+        while let Some(span) = queue.pop() {
+            if visited.contains(&span) {
+                continue;
+            }
+
+            visited.push(span);
+
+            let Some(fn_state) = self.fn_states.get(span) else { continue };
+
+            for call in fn_state.function_calls.iter() {
+                queue.extend(self.resolve_fn_call(fn_state, call));
+            }
+
+            result.push(fn_state);
+        }
+
+        result
+    }
+}
+
+/// Tracks the names imported into a module by its `use` items, so paths can be resolved to the items they refer to.
+#[derive(Default)]
+pub struct ImportState {
+    /// The full paths of the imported names, keyed by the names they are imported as.
+    pub imports: HashMap<String, Vec<String>>,
+    /// The paths of the modules whose items are imported with globs.
+    pub glob_imports: Vec<Vec<String>>,
+}
+
+impl ImportState {
+    /// Records the names imported by the supplied `item_use`.
+    pub fn visit_use(&mut self, item_use: &ItemUse) {
+        self.import_use_tree(&[], &item_use.tree);
+    }
+
+    fn import_use_tree(&mut self, prefix: &[String], use_tree: &UseTree) {
+        match use_tree {
+            UseTree::Group { imports } => {
+                for use_tree in &imports.inner {
+                    self.import_use_tree(prefix, use_tree);
+                }
+            }
+
+            UseTree::Name { name } if name.as_str() == "self" => {
+                if let Some(last) = prefix.last() {
+                    self.imports.insert(last.clone(), prefix.to_vec());
+                }
+            }
+
+            UseTree::Name { name } => {
+                let mut path = prefix.to_vec();
+                path.push(name.as_str().to_string());
+                self.imports.insert(name.as_str().to_string(), path);
+            }
+
+            UseTree::Rename { name, alias, .. } => {
+                let mut path = prefix.to_vec();
+                path.push(name.as_str().to_string());
+                self.imports.insert(alias.as_str().to_string(), path);
+            }
+
+            UseTree::Glob { .. } => {
+                self.glob_imports.push(prefix.to_vec());
+            }
+
+            UseTree::Path { prefix: inner_prefix, suffix, .. } => {
+                let mut prefix = prefix.to_vec();
+                prefix.push(inner_prefix.as_str().to_string());
+                self.import_use_tree(&prefix, suffix.as_ref());
+            }
+
+            UseTree::Error { .. } => {}
+        }
+    }
+
+    /// Gets the full path of the supplied `path` segments, expanding the first segment if it is an imported name.
+    pub fn resolve_path(&self, path: &[String]) -> Vec<String> {
+        let Some((first, rest)) = path.split_first() else { return vec![] };
+
+        match self.imports.get(first) {
+            Some(import) => import.iter().chain(rest).cloned().collect(),
+            None => path.to_vec(),
+        }
+    }
+}
+
+/// Checks if the supplied full `path` refers to an item of the `std` or `core` libraries.
+pub fn is_std_path(path: &[String]) -> bool {
+    matches!(path.first().map(|x| x.as_str()), Some("std" | "core"))
+}
+
+/// Gets the key of the parsed module with the supplied module `path` segments, e.g. `[a, b]` for `a::b`, as seen from the module at `from_path`.
+///
+/// The first segment can name a local dependency of the package. Other paths are resolved from the `src` directory of the package,
+/// and then from the directory of the submodules of the module at `from_path`.
+pub fn resolve_module_path(from_path: &Path, path: &[String], modules: &HashMap<PathBuf, Module>, packages: &PackageGraph) -> Option<PathBuf> {
+    fn module_file(package: &Package, path: &[String]) -> PathBuf {
+        if path.is_empty() {
+            return package.entry_path.clone();
+        }
+
+        let mut result = package.src_path();
+        result.extend(path);
+        result.set_extension("sw");
+        result
+    }
+
+    let find_module = |file: PathBuf| modules.keys().find(|x| normalize_path_lexically(x) == file).cloned();
+    let package = packages.package_for_path(from_path)?;
+
+    // Check for paths into a local dependency of the package
+    if let Some((first, rest)) = path.split_first() {
+        let dependency = package.dependencies.iter()
+            .filter_map(|x| packages.package_by_root(x))
+            .find(|x| x.name == *first);
+
+        if let Some(dependency) = dependency {
+            return find_module(module_file(dependency, rest));
+        }
+    }
+
+    // Check for paths from the root of the package
+    if let Some(result) = find_module(module_file(package, path)) {
+        return Some(result);
+    }
+
+    // Check for paths to the submodules of the module
+    if path.is_empty() {
+        return None;
+    }
+
+    let from_path = normalize_path_lexically(from_path);
+
+    let mut file = if from_path == package.entry_path { package.src_path() } else { from_path.with_extension("") };
+    file.extend(path);
+    file.set_extension("sw");
+
+    find_module(file)
+}
+
+/// Finds the free function named `name` in the supplied `module`, along with its attributes.
+pub fn find_module_fn<'a>(module: &'a Module, name: &str) -> Option<(&'a [AttributeDecl], &'a ItemFn)> {
+    module.items.iter().find_map(|Annotated { attributes, value }| match value {
+        ItemKind::Fn(item_fn) if item_fn.fn_signature.name.as_str() == name => Some((attributes.as_slice(), item_fn)),
+        _ => None,
+    })
+}
+
+/// Resolves the free function called through the supplied `path` segments from the module at `from_path`, following the `imports` of the module.
+///
+/// Returns the key of the module declaring the function along with its attributes and declaration, or `None` if it is not declared in a module of the project.
+pub fn resolve_module_fn<'a>(
+    from_path: &Path,
+    path: &[String],
+    imports: &ImportState,
+    modules: &'a HashMap<PathBuf, Module>,
+    packages: &PackageGraph,
+) -> Option<(PathBuf, &'a [AttributeDecl], &'a ItemFn)> {
+    let path = imports.resolve_path(path);
+    let (name, module_path) = path.split_last()?;
+
+    // Unqualified names are declared in the module itself or in one of the modules imported with globs
+    let module_paths = if module_path.is_empty() {
+        let mut result = vec![from_path.to_path_buf()];
+        result.extend(imports.glob_imports.iter().filter_map(|x| resolve_module_path(from_path, x, modules, packages)));
+        result
+    } else {
+        resolve_module_path(from_path, module_path, modules, packages).into_iter().collect()
+    };
+
+    module_paths.into_iter().find_map(|module_path| {
+        let (attributes, item_fn) = find_module_fn(modules.get(&module_path)?, name)?;
+        Some((module_path, attributes, item_fn))
+    })
+}
+
+/// The paths of the `std` functions which get the timestamp of a block.
+pub const TIMESTAMP_FN_PATHS: &[&str] = &["std::block::timestamp", "std::block::timestamp_of_block"];
+
+/// The paths of the `std` functions which get the balance of an asset held by a contract.
+pub const BALANCE_FN_PATHS: &[&str] = &["std::context::this_balance", "std::context::balance_of"];
+
+/// The paths of the `std` functions which transfer assets, along with the index of their amount argument.
+pub const TRANSFER_FN_AMOUNT_ARGS: &[(&str, usize)] = &[
+    ("std::asset::transfer", 2),
+    ("std::asset::transfer_to_address", 2),
+    ("std::asset::force_transfer_to_contract", 2),
+];
+
+/// Checks if any of the `_`-separated words of the supplied `name` is one of the supplied `words`, e.g. `pool_balance` contains `balance` but `balanced` does not.
+pub fn name_contains_word(name: &str, words: &[&str]) -> bool {
+    name.split('_').any(|x| words.iter().any(|word| x.eq_ignore_ascii_case(word)))
+}
+
+/// Checks if the storage field with the supplied `name` holds asset balances, e.g. `balance` or `user_balances`.
+pub fn is_balance_storage_field_name(name: &str) -> bool {
+    name_contains_word(name, &["balance", "balances"])
+}
+
+/// Recognizes calls to a set of functions, either by their full paths or by the names they are imported as.
+#[derive(Clone, Default)]
+pub struct FnCallMatcher {
+    names: Vec<(String, String)>,
+}
+
+impl FnCallMatcher {
+    /// Creates a matcher for the functions with the supplied full `paths`.
+    pub fn new<S: AsRef<str>>(paths: &[S]) -> Self {
+        Self {
+            names: paths.iter().map(|x| (x.as_ref().to_string(), x.as_ref().to_string())).collect(),
+        }
+    }
+
+    /// Adds the supplied `name` which the function with the supplied `path` can be called by, e.g. when it is part of the prelude.
+    pub fn add_name(&mut self, name: &str, path: &str) {
+        self.names.push((name.to_string(), path.to_string()));
+    }
+
+    /// Records the names the matched functions are imported as by the supplied `item_use`.
+    pub fn visit_use(&mut self, item_use: &ItemUse) {
+        let paths = self.names.iter().filter(|(name, path)| name == path).map(|(_, path)| path.clone()).collect::<Vec<_>>();
+
+        for path in paths {
+            if let Some(name) = use_tree_to_name(&item_use.tree, &path) {
+                self.names.push((name, path));
+            }
+        }
+    }
+
+    /// Gets the full path of the matched function called by the supplied `expr`, if any.
+    pub fn called_path(&self, expr: &Expr) -> Option<&str> {
+        let Expr::FuncApp { func, .. } = expr else { return None };
+        let Expr::Path(_) = func.as_ref() else { return None };
+
+        let func = func.span();
+
+        self.names.iter().find(|(name, _)| name == func.as_str()).map(|(_, path)| path.as_str())
+    }
+
+    /// Checks if the supplied `expr` calls one of the matched functions.
+    #[inline]
+    pub fn is_call(&self, expr: &Expr) -> bool {
+        self.called_path(expr).is_some()
+    }
+}
+
+/// Gets the amount argument of the supplied `expr` if it calls one of the transfer functions of `TRANSFER_FN_AMOUNT_ARGS` recognized by the supplied `transfer_fns`.
+pub fn get_transfer_amount_arg<'a>(expr: &'a Expr, transfer_fns: &FnCallMatcher) -> Option<&'a Expr> {
+    let path = transfer_fns.called_path(expr)?;
+    let (_, index) = TRANSFER_FN_AMOUNT_ARGS.iter().find(|(x, _)| *x == path)?;
+    let Expr::FuncApp { args, .. } = expr else { return None };
+
+    fold_punctuated(&args.inner).get(*index).copied()
+}
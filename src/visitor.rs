@@ -335,6 +335,7 @@ pub struct StorageFieldContext<'a> {
     pub item: &'a ItemKind,
     pub storage_attributes: &'a [AttributeDecl],
     pub item_storage: &'a ItemStorage,
+    pub namespace: Vec<BaseIdent>,
     pub field_attributes: &'a [AttributeDecl],
     pub field: &'a StorageField,
 }
@@ -379,6 +380,10 @@ pub struct TraitTypeContext<'a> {
 
 #[allow(unused_variables)]
 pub trait AstVisitor {
+    /// Checks if the findings of the visitor in a module can depend on the contents of other modules, e.g. by inspecting
+    /// `Project::modules` or keeping state between modules. The analysis cache re-runs these visitors when a linked file changes.
+    fn is_cross_module(&self) -> bool { false }
+
     fn visit_module(&mut self, context: &ModuleContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> { Ok(()) }
     fn leave_module(&mut self, context: &ModuleContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> { Ok(()) }
 
@@ -475,7 +480,7 @@ pub trait AstVisitor {
 
 #[derive(Default)]
 pub struct AstVisitorRecursive<'a> {
-    pub visitors: Vec<Box<dyn AstVisitor>>,
+    pub visitors: Vec<(&'static str, Box<dyn AstVisitor>)>,
     pub visit_module_hooks: Vec<Box<dyn FnMut(&ModuleContext, Rc<RefCell<AstScope>>, &mut Project) -> Result<(), Error> + 'a>>,
     pub leave_module_hooks: Vec<Box<dyn FnMut(&ModuleContext, Rc<RefCell<AstScope>>, &mut Project) -> Result<(), Error> + 'a>>,
     pub visit_module_item_hooks: Vec<Box<dyn FnMut(&ItemContext, Rc<RefCell<AstScope>>, &mut Project) -> Result<(), Error> + 'a>>,
@@ -540,9 +545,50 @@ pub struct AstVisitorRecursive<'a> {
     pub leave_trait_type_hooks: Vec<Box<dyn FnMut(&TraitTypeContext, Rc<RefCell<AstScope>>, &mut Project) -> Result<(), Error> + 'a>>,
 }
 
+impl AstVisitorRecursive<'_> {
+    /// Visits a storage entry, recursing into any nested storage namespaces.
+    fn visit_storage_entry(
+        &mut self,
+        context: &StorageContext,
+        attributes: &[AttributeDecl],
+        entry: &StorageEntry,
+        namespace: &[BaseIdent],
+        scope: Rc<RefCell<AstScope>>,
+        project: &mut Project,
+    ) -> Result<(), Error> {
+        if let Some(field) = entry.field.as_ref() {
+            let context = StorageFieldContext {
+                path: context.path,
+                module: context.module,
+                item: context.item,
+                storage_attributes: context.attributes,
+                item_storage: context.item_storage,
+                namespace: namespace.to_vec(),
+                field_attributes: attributes,
+                field,
+            };
+
+            self.visit_storage_field(&context, scope.clone(), project)?;
+            self.leave_storage_field(&context, scope.clone(), project)?;
+        }
+
+        if let Some(entries) = entry.namespace.as_ref() {
+            let mut namespace = namespace.to_vec();
+            namespace.push(entry.name.clone());
+
+            for entry in &entries.inner {
+                self.visit_storage_entry(context, entry.attributes.as_slice(), entry.value.as_ref(), namespace.as_slice(), scope.clone(), project)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl AstVisitor for AstVisitorRecursive<'_> {
     fn visit_module(&mut self, context: &ModuleContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_module(context, scope.clone(), project)?;
         }
 
@@ -572,7 +618,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_module(&mut self, context: &ModuleContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_module(context, scope.clone(), project)?;
         }
 
@@ -584,7 +631,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn visit_module_item(&mut self, context: &ItemContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_module_item(context, scope.clone(), project)?;
         }
 
@@ -766,7 +814,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_module_item(&mut self, context: &ItemContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_module_item(context, scope.clone(), project)?;
         }
 
@@ -778,7 +827,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn visit_submodule(&mut self, context: &SubmoduleContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_submodule(context, scope.clone(), project)?;
         }
 
@@ -790,7 +840,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_submodule(&mut self, context: &SubmoduleContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_submodule(context, scope.clone(), project)?;
         }
         
@@ -804,7 +855,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     fn visit_use(&mut self, context: &UseContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
         // scope.borrow_mut().add_use(context.item_use);
         
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_use(context, scope.clone(), project)?;
         }
         
@@ -816,7 +868,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_use(&mut self, context: &UseContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_use(context, scope.clone(), project)?;
         }
         
@@ -836,7 +889,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
         //     scope.borrow_mut().add_generic_params(project, generics, context.item_struct.where_clause_opt.as_ref());
         // }
         
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_struct(context, scope.clone(), project)?;
         }
         
@@ -863,7 +917,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_struct(&mut self, context: &StructContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_struct(context, scope.clone(), project)?;
         }
         
@@ -875,7 +930,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn visit_struct_field(&mut self, context: &StructFieldContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_struct_field(context, scope.clone(), project)?;
         }
 
@@ -887,7 +943,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_struct_field(&mut self, context: &StructFieldContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_struct_field(context, scope.clone(), project)?;
         }
 
@@ -907,7 +964,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
         //     scope.borrow_mut().add_generic_params(project, generics, context.item_enum.where_clause_opt.as_ref());
         // }
         
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_enum(context, scope.clone(), project)?;
         }
         
@@ -934,7 +992,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_enum(&mut self, context: &EnumContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_enum(context, scope.clone(), project)?;
         }
         
@@ -946,7 +1005,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn visit_enum_field(&mut self, context: &EnumFieldContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_enum_field(context, scope.clone(), project)?;
         }
         
@@ -958,7 +1018,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_enum_field(&mut self, context: &EnumFieldContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_enum_field(context, scope.clone(), project)?;
         }
         
@@ -1007,7 +1068,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
         //     }
         // }
 
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_fn(context, scope.clone(), project)?;
         }
 
@@ -1036,7 +1098,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_fn(&mut self, context: &FnContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_fn(context, scope.clone(), project)?;
         }
         
@@ -1048,7 +1111,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn visit_statement(&mut self, context: &StatementContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_statement(context, scope.clone(), project)?;
         }
         
@@ -1118,7 +1182,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_statement(&mut self, context: &StatementContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_statement(context, scope.clone(), project)?;
         }
         
@@ -1146,7 +1211,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
         //     },
         // );
 
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_statement_let(context, scope.clone(), project)?;
         }
         
@@ -1174,7 +1240,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_statement_let(&mut self, context: &StatementLetContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_statement_let(context, scope.clone(), project)?;
         }
         
@@ -1186,7 +1253,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn visit_expr(&mut self, context: &ExprContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_expr(context, scope.clone(), project)?;
         }
 
@@ -2405,7 +2473,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_expr(&mut self, context: &ExprContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_expr(context, scope.clone(), project)?;
         }
         
@@ -2417,7 +2486,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn visit_block(&mut self, context: &BlockContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_block(context, scope.clone(), project)?;
         }
 
@@ -2467,7 +2537,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_block(&mut self, context: &BlockContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_block(context, scope.clone(), project)?;
         }
         
@@ -2479,7 +2550,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn visit_asm_block(&mut self, context: &AsmBlockContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_asm_block(context, scope.clone(), project)?;
         }
 
@@ -2531,7 +2603,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_asm_block(&mut self, context: &AsmBlockContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_asm_block(context, scope.clone(), project)?;
         }
         
@@ -2543,7 +2616,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn visit_asm_instruction(&mut self, context: &AsmInstructionContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_asm_instruction(context, scope.clone(), project)?;
         }
         
@@ -2555,7 +2629,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_asm_instruction(&mut self, context: &AsmInstructionContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_asm_instruction(context, scope.clone(), project)?;
         }
         
@@ -2567,7 +2642,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn visit_asm_final_expr(&mut self, context: &AsmFinalExprContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_asm_final_expr(context, scope.clone(), project)?;
         }
         
@@ -2579,7 +2655,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_asm_final_expr(&mut self, context: &AsmFinalExprContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_asm_final_expr(context, scope.clone(), project)?;
         }
         
@@ -2591,7 +2668,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn visit_if_expr(&mut self, context: &IfExprContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_if_expr(context, scope.clone(), project)?;
         }
 
@@ -2723,7 +2801,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_if_expr(&mut self, context: &IfExprContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_if_expr(context, scope.clone(), project)?;
         }
         
@@ -2735,7 +2814,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn visit_match_expr(&mut self, context: &MatchExprContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_match_expr(context, scope.clone(), project)?;
         }
 
@@ -2783,7 +2863,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_match_expr(&mut self, context: &MatchExprContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_match_expr(context, scope.clone(), project)?;
         }
         
@@ -2795,7 +2876,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn visit_match_branch(&mut self, context: &MatchBranchContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_match_branch(context, scope.clone(), project)?;
         }
 
@@ -2850,7 +2932,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_match_branch(&mut self, context: &MatchBranchContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_match_branch(context, scope.clone(), project)?;
         }
         
@@ -2862,7 +2945,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn visit_while_expr(&mut self, context: &WhileExprContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_while_expr(context, scope.clone(), project)?;
         }
 
@@ -2907,7 +2991,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_while_expr(&mut self, context: &WhileExprContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_while_expr(context, scope.clone(), project)?;
         }
         
@@ -2919,7 +3004,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn visit_for_expr(&mut self, context: &ForExprContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_for_expr(context, scope.clone(), project)?;
         }
 
@@ -2964,7 +3050,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_for_expr(&mut self, context: &ForExprContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_for_expr(context, scope.clone(), project)?;
         }
         
@@ -2984,7 +3071,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
         //     scope.borrow_mut().add_generic_params(project, generics, context.item_trait.where_clause_opt.as_ref());
         // }
         
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_trait(context, scope.clone(), project)?;
         }
 
@@ -2996,7 +3084,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_trait(&mut self, context: &TraitContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_trait(context, scope.clone(), project)?;
         }
         
@@ -3016,7 +3105,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
         //     scope.borrow_mut().add_generic_params(project, generics, context.item_impl.where_clause_opt.as_ref());
         // }
         
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_impl(context, scope.clone(), project)?;
         }
 
@@ -3079,7 +3169,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_impl(&mut self, context: &ImplContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_impl(context, scope.clone(), project)?;
         }
 
@@ -3093,7 +3184,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     fn visit_abi(&mut self, context: &AbiContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
         // scope.borrow_mut().add_abi(project, context.item_abi);
 
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_abi(context, scope.clone(), project)?;
         }
         
@@ -3105,7 +3197,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_abi(&mut self, context: &AbiContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_abi(context, scope.clone(), project)?;
         }
         
@@ -3128,7 +3221,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
         //     &ty,
         // );
 
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_const(context, scope.clone(), project)?;
         }
         
@@ -3158,7 +3252,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_const(&mut self, context: &ConstContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_const(context, scope.clone(), project)?;
         }
         
@@ -3170,7 +3265,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn visit_storage(&mut self, context: &StorageContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_storage(context, scope.clone(), project)?;
         }
 
@@ -3178,26 +3274,16 @@ impl AstVisitor for AstVisitorRecursive<'_> {
             hook(context, scope.clone(), project)?;
         }
 
-        for field in &context.item_storage.entries.inner {
-            let context = StorageFieldContext {
-                path: context.path,
-                module: context.module,
-                item: context.item,
-                storage_attributes: context.attributes,
-                item_storage: context.item_storage,
-                field_attributes: field.attributes.as_slice(),
-                field: &field.value.field.as_ref().unwrap(),
-            };
-
-            self.visit_storage_field(&context, scope.clone(), project)?;
-            self.leave_storage_field(&context, scope.clone(), project)?;
+        for entry in &context.item_storage.entries.inner {
+            self.visit_storage_entry(context, entry.attributes.as_slice(), &entry.value, &[], scope.clone(), project)?;
         }
         
         Ok(())
     }
 
     fn leave_storage(&mut self, context: &StorageContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_storage(context, scope.clone(), project)?;
         }
         
@@ -3216,7 +3302,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
         //     &context.field.ty,
         // );
 
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_storage_field(context, scope.clone(), project)?;
         }
 
@@ -3244,7 +3331,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_storage_field(&mut self, context: &StorageFieldContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_storage_field(context, scope.clone(), project)?;
         }
         
@@ -3256,7 +3344,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
     
     fn visit_configurable(&mut self, context: &ConfigurableContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_configurable(context, scope.clone(), project)?;
         }
 
@@ -3283,7 +3372,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_configurable(&mut self, context: &ConfigurableContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_configurable(context, scope.clone(), project)?;
         }
         
@@ -3302,7 +3392,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
         //     &context.field.ty,
         // );
 
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_configurable_field(context, scope.clone(), project)?;
         }
 
@@ -3330,7 +3421,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_configurable_field(&mut self, context: &ConfigurableFieldContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_configurable_field(context, scope.clone(), project)?;
         }
         
@@ -3344,7 +3436,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     fn visit_type_alias(&mut self, context: &TypeAliasContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
         // scope.borrow_mut().add_type_alias(project, context.item_type_alias);
 
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_type_alias(context, scope.clone(), project)?;
         }
         
@@ -3356,7 +3449,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_type_alias(&mut self, context: &TypeAliasContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_type_alias(context, scope.clone(), project)?;
         }
         
@@ -3368,7 +3462,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn visit_trait_type(&mut self, context: &TraitTypeContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.visit_trait_type(context, scope.clone(), project)?;
         }
         
@@ -3380,7 +3475,8 @@ impl AstVisitor for AstVisitorRecursive<'_> {
     }
 
     fn leave_trait_type(&mut self, context: &TraitTypeContext, scope: Rc<RefCell<AstScope>>, project: &mut Project) -> Result<(), Error> {
-        for visitor in self.visitors.iter_mut() {
+        for (detector_name, visitor) in self.visitors.iter_mut() {
+            project.report.borrow_mut().set_detector(*detector_name);
             visitor.leave_trait_type(context, scope.clone(), project)?;
         }
         
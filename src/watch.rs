@@ -0,0 +1,200 @@
+use crate::{diff::ReportDiff, error::Error, project::Project, Options};
+use notify::{RecursiveMode, Watcher};
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc::{channel, RecvTimeoutError},
+    time::Duration,
+};
+
+/// The time to wait for further file system events before re-analyzing, since editors often write a file in several steps.
+const DEBOUNCE_DURATION: Duration = Duration::from_millis(200);
+
+/// Gets the paths that are watched for changes, along with their canonical paths.
+fn get_watch_targets(options: &Options) -> Result<Vec<(PathBuf, PathBuf)>, Error> {
+    let mut result = vec![];
+
+    for path in options.directory.iter().chain(options.files.iter()) {
+        let canonical_path = path.canonicalize().map_err(|_| Error::FileNotFound(path.clone()))?;
+        result.push((path.clone(), canonical_path));
+    }
+
+    Ok(result)
+}
+
+/// Gets the directories to watch for the supplied `targets`, along with whether to watch them recursively.
+///
+/// File targets are watched through their parent directory, since editors often save a file by renaming a new file over it,
+/// which would end a watch on the file itself. Events for other files in that directory are filtered out by `to_project_path`.
+fn get_watch_dirs(targets: &[(PathBuf, PathBuf)]) -> Vec<(PathBuf, RecursiveMode)> {
+    let mut result: Vec<(PathBuf, RecursiveMode)> = vec![];
+
+    for (_, canonical_path) in targets {
+        let (path, recursive_mode) = if canonical_path.is_file() {
+            let Some(parent) = canonical_path.parent() else { continue };
+            (parent.to_path_buf(), RecursiveMode::NonRecursive)
+        } else {
+            (canonical_path.clone(), RecursiveMode::Recursive)
+        };
+
+        // Skip directories which are already watched, preferring a recursive watch over a non-recursive one
+        if let Some(existing) = result.iter_mut().find(|(x, _)| *x == path) {
+            if recursive_mode == RecursiveMode::Recursive {
+                existing.1 = RecursiveMode::Recursive;
+            }
+
+            continue;
+        }
+
+        result.push((path, recursive_mode));
+    }
+
+    result
+}
+
+/// Converts the supplied absolute `path` of a file system event into the path the project knows the file by.
+fn to_project_path(targets: &[(PathBuf, PathBuf)], path: &Path) -> Option<PathBuf> {
+    for (target_path, canonical_path) in targets {
+        if path == canonical_path {
+            return Some(target_path.clone());
+        }
+
+        if let Ok(relative_path) = path.strip_prefix(canonical_path) {
+            return Some(target_path.join(relative_path));
+        }
+    }
+
+    None
+}
+
+/// Monitors the targets of the supplied `options` for changes, re-analyzing the `project` whenever a Sway source file changes.
+pub fn watch(options: &Options, mut project: Project) -> Result<(), Error> {
+    let targets = get_watch_targets(options)?;
+
+    let (sender, receiver) = channel();
+    let mut watcher = notify::recommended_watcher(sender).map_err(|e| Error::Wrapped(Box::new(e)))?;
+
+    for (path, recursive_mode) in get_watch_dirs(&targets) {
+        watcher.watch(&path, recursive_mode).map_err(|e| Error::Wrapped(Box::new(e)))?;
+    }
+
+    eprintln!("Watching for changes...");
+
+    while let Ok(event) = receiver.recv() {
+        let mut events = vec![event];
+
+        // Collect the events that follow shortly after
+        loop {
+            match receiver.recv_timeout(DEBOUNCE_DURATION) {
+                Ok(event) => events.push(event),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+        }
+
+        // Get the project paths of the touched Sway source files
+        let mut paths = events.into_iter()
+            .filter_map(|event| event.ok())
+            .flat_map(|event| event.paths)
+            .filter(|path| path.extension().map(|x| x == "sw").unwrap_or(false))
+            .filter_map(|path| to_project_path(&targets, &path))
+            .collect::<Vec<_>>();
+
+        paths.sort();
+        paths.dedup();
+
+        if paths.is_empty() {
+            continue;
+        }
+
+        let old_report = project.json_report();
+
+        // Re-parse the touched files, removing the ones that no longer exist
+        for path in paths.iter() {
+            if path.is_file() {
+                // Keep the previous parse of a file that fails to parse, since it is likely in the middle of being edited
+                if let Err(e) = project.parse_file(path) {
+                    eprintln!("{e}");
+                }
+            } else {
+                project.remove_file(path);
+            }
+        }
+
+        project.reset_analysis();
+
+        if let Err(e) = project.analyze_modules() {
+            eprintln!("{e}");
+            continue;
+        }
+
+        // Filter the entries based on the include or exclude options
+        let entries = crate::filter_entries(&project.report.borrow(), options);
+        project.report.borrow_mut().set_filtered_entries(entries);
+
+        // Display the updated report with markers for the added and removed findings
+        let diff = ReportDiff::new(
+            &old_report,
+            &project.json_report(),
+            options.display_format.unwrap_or_default(),
+            options.sorting.unwrap_or_default(),
+        );
+
+        println!("{diff}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reanalysis() {
+        let options = Options {
+            directory: Some(PathBuf::from("test/arbitrary_asset_transfer/")),
+            detectors: vec!["arbitrary_asset_transfer".to_string()],
+            ..Default::default()
+        };
+
+        let mut project = Project::try_from(&options).unwrap();
+        project.analyze_modules().unwrap();
+
+        let entry_count = project.report.borrow().entry_count("test/arbitrary_asset_transfer/src/main.sw");
+
+        // Map an event path back to the path the project knows the file by
+        let targets = get_watch_targets(&options).unwrap();
+        let event_path = PathBuf::from("test/arbitrary_asset_transfer/src/main.sw").canonicalize().unwrap();
+        let path = to_project_path(&targets, &event_path).unwrap();
+
+        assert_eq!(path, PathBuf::from("test/arbitrary_asset_transfer/src/main.sw"));
+
+        // Re-parsing and re-analyzing should produce the same findings
+        project.parse_file(&path).unwrap();
+        project.reset_analysis();
+        project.analyze_modules().unwrap();
+
+        assert_eq!(project.report.borrow().entry_count(&path), entry_count);
+    }
+
+    #[test]
+    fn test_file_targets() {
+        let options = Options {
+            files: vec![PathBuf::from("test/arbitrary_asset_transfer/src/main.sw")],
+            ..Default::default()
+        };
+
+        // File targets should be watched through their parent directory
+        let targets = get_watch_targets(&options).unwrap();
+        let src_path = PathBuf::from("test/arbitrary_asset_transfer/src").canonicalize().unwrap();
+
+        assert_eq!(get_watch_dirs(&targets), vec![(src_path.clone(), RecursiveMode::NonRecursive)]);
+
+        // Events for other files in the parent directory should be ignored
+        assert_eq!(to_project_path(&targets, &src_path.join("other.sw")), None);
+        assert_eq!(
+            to_project_path(&targets, &src_path.join("main.sw")),
+            Some(PathBuf::from("test/arbitrary_asset_transfer/src/main.sw")),
+        );
+    }
+}
@@ -0,0 +1,75 @@
+contract;
+
+mod ops;
+mod oracle;
+
+use ops::{require_unpaused, unsafe_op};
+use std::block::timestamp;
+
+abi Vault {
+    #[storage(read, write)]
+    fn deposit(amount: u64);
+
+    #[storage(read)]
+    fn preview(amount: u64) -> u64;
+
+    #[storage(read)]
+    fn updated_at() -> u64;
+}
+
+storage {
+    paused: bool = false,
+    balance: u64 = 0,
+}
+
+#[storage(read)]
+fn require_not_paused() {
+    require(!storage.paused.read(), "paused");
+}
+
+impl Vault for Contract {
+    #[storage(read, write)]
+    fn deposit(amount: u64) {
+        require_not_paused();
+        storage.balance.write(unsafe_op(amount));
+    }
+
+    /// Report entry should be created:
+    // L40: The `Contract::preview` function does not check the `storage.paused` field. Every entry point must respect the pause switch.
+    #[storage(read)]
+    fn preview(amount: u64) -> u64 {
+        // Report entry should be created:
+        // L43: The `Contract::preview` function calls `ops::unsafe_op` without being declared with `#[storage(write)]`: `unsafe_op(amount)`.
+        unsafe_op(amount)
+    }
+
+    #[storage(read)]
+    fn updated_at() -> u64 {
+        require(!storage.paused.read(), "paused");
+        timestamp()
+    }
+}
+
+abi Admin {
+    #[storage(read, write)]
+    fn unpause();
+
+    #[storage(read, write)]
+    fn withdraw(amount: u64);
+}
+
+impl Admin for Contract {
+    /// Report entry should be created, since writing the field does not check it:
+    // L65: The `Contract::unpause` function does not check the `storage.paused` field. Every entry point must respect the pause switch.
+    #[storage(read, write)]
+    fn unpause() {
+        storage.paused.write(false);
+    }
+
+    // Report entry should not be created, since the field is checked by the helper in the other module
+    #[storage(read, write)]
+    fn withdraw(amount: u64) {
+        require_unpaused(storage.paused);
+        storage.balance.write(storage.balance.read() - amount);
+    }
+}
@@ -0,0 +1,10 @@
+library;
+
+pub fn unsafe_op(value: u64) -> u64 {
+    value * 2
+}
+
+#[storage(read)]
+pub fn require_unpaused(paused: StorageKey<bool>) {
+    require(!paused.read(), "paused");
+}
@@ -0,0 +1,9 @@
+library;
+
+use std::block::timestamp;
+
+pub fn is_stale(updated_at: u64) -> bool {
+    // Report entry should be created:
+    // L8: The `is_stale` function calls the forbidden `std::block::timestamp` function: `timestamp()`.
+    timestamp() - updated_at > 3600
+}
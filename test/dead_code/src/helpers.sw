@@ -0,0 +1,15 @@
+library;
+
+/// Report entry should be created:
+// L5: The `square` function is never used.
+fn square(x: u64) -> u64 {
+    x * x
+}
+
+pub fn double(x: u64) -> u64 {
+    add(x, x)
+}
+
+fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
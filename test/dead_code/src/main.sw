@@ -0,0 +1,79 @@
+contract;
+
+mod helpers;
+
+use helpers::double;
+
+abi Counter {
+    fn increment(amount: u64) -> u64;
+}
+
+/// Report entry should be created:
+// L13: The `Unused` struct is never used.
+struct Unused {
+    value: u64,
+}
+
+struct Amount {
+    value: u64,
+}
+
+/// Report entry should be created:
+// L23: The `UnusedError` enum is never used.
+enum UnusedError {
+    Overflow: (),
+}
+
+enum CounterError {
+    Zero: (),
+}
+
+/// Report entry should be created:
+// L33: The `UNUSED_LIMIT` constant is never used.
+const UNUSED_LIMIT: u64 = 10;
+
+const LIMIT: u64 = 100;
+
+trait Describe {
+    fn describe(self) -> u64;
+}
+
+/// Report entry should be created:
+// L43: The `Describe` implementation for `Amount` is never used.
+impl Describe for Amount {
+    fn describe(self) -> u64 {
+        self.value
+    }
+}
+
+/// Report entry should be created:
+// L51: The `unused_helper` function is never used.
+fn unused_helper() -> u64 {
+    1
+}
+
+fn checked(amount: u64) -> u64 {
+    require(amount != 0, CounterError::Zero);
+
+    if amount > LIMIT {
+        return LIMIT;
+
+        // Report entry should be created:
+        // L63: The `checked` function contains unreachable code after `return LIMIT`: `log(amount);`.
+        log(amount);
+    }
+
+    Amount { value: amount }.value
+}
+
+impl Counter for Contract {
+    fn increment(amount: u64) -> u64 {
+        double(checked(amount))
+    }
+}
+
+/// Report entry should be created, since the `add` function used by the `helpers` module is a different function:
+// L77: The `add` function is never used.
+fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
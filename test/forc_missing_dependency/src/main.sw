@@ -0,0 +1,14 @@
+/*
+library;
+*/
+contract;
+
+abi Counter {
+    fn get() -> u64;
+}
+
+impl Counter for Contract {
+    fn get() -> u64 {
+        0
+    }
+}
@@ -0,0 +1,5 @@
+library;
+
+pub fn double(value: u64) -> u64 {
+    value * 2
+}
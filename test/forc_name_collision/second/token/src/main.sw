@@ -0,0 +1,13 @@
+contract;
+
+use shared::double;
+
+abi Token {
+    fn doubled(value: u64) -> u64;
+}
+
+impl Token for Contract {
+    fn doubled(value: u64) -> u64 {
+        double(value)
+    }
+}
@@ -3,12 +3,15 @@ contract;
 abi TestInlineAssemblyUsage {
     fn test_inline_assembly_usage_1(a: u64, b: u64, c: u64) -> (u64, u64);
     fn test_inline_assembly_usage_2(a: u64, b: u64, c: u64) -> (u64, u64);
+    fn test_inline_assembly_usage_3(index: u64) -> u8;
+    fn test_inline_assembly_usage_4(ptr: raw_ptr, value: u64);
+    fn test_inline_assembly_usage_5(target: raw_ptr, amount: u64, asset_id: raw_ptr, gas: u64);
+    fn test_inline_assembly_usage_6(recipient: raw_ptr, amount: u64, asset_id: raw_ptr);
+    fn test_inline_assembly_usage_7(amount: u64, sub_id: raw_ptr, destination: u64);
 }
 
 fn dummy_inline_assembly(a: u64, b: u64, c: u64) -> (u64, u64) {
     let empty_tuple = (0u64, 0u64);
-    // Report entry should be created:
-    // L12: The `dummy_inline_assembly` function contains inline assembly usage.
     asm(output: empty_tuple, r1: a, r2: b, r3: c, r4, r5) {
         add  r4 r1 r2; // add a & b and put the result in r4
         add  r5 r2 r3; // add b & c and put the result in r5
@@ -21,8 +24,6 @@ fn dummy_inline_assembly(a: u64, b: u64, c: u64) -> (u64, u64) {
 impl TestInlineAssemblyUsage for Contract {
     fn test_inline_assembly_usage_1(a: u64, b: u64, c: u64) -> (u64, u64) {
         let empty_tuple = (0u64, 0u64);
-        // Report entry should be created:
-        // L26: The `Contract::test_inline_assembly_usage_1` function contains inline assembly usage.
         asm(output: empty_tuple, r1: a, r2: b, r3: c, r4, r5) {
             add  r4 r1 r2; // add a & b and put the result in r4
             add  r5 r2 r3; // add b & c and put the result in r5
@@ -35,4 +36,69 @@ impl TestInlineAssemblyUsage for Contract {
     fn test_inline_assembly_usage_2(a: u64, b: u64, c: u64) -> (u64, u64) {
         dummy_inline_assembly(a, b, c)
     }
+
+    fn test_inline_assembly_usage_3(index: u64) -> u8 {
+        asm(r1: index, r2) {
+            gtf  r2 r1 i1;
+            r2: u8
+        }
+    }
+
+    fn test_inline_assembly_usage_4(ptr: raw_ptr, value: u64) {
+        asm(r1: ptr, r2: value) {
+            // Report entry should be created:
+            // L51: The `Contract::test_inline_assembly_usage_4` function contains inline assembly which writes to caller-provided memory, the stack, the heap or storage with the `SW` instruction: `sw   r1 r2 i0`.
+            sw   r1 r2 i0;
+        }
+    }
+
+    fn test_inline_assembly_usage_5(target: raw_ptr, amount: u64, asset_id: raw_ptr, gas: u64) {
+        asm(r1: target, r2: amount, r3: asset_id, r4: gas) {
+            // Report entry should be created:
+            // L59: The `Contract::test_inline_assembly_usage_5` function contains inline assembly which alters the control flow with the `CALL` instruction: `call r1 r2 r3 r4`.
+            call r1 r2 r3 r4;
+        }
+    }
+
+    fn test_inline_assembly_usage_6(recipient: raw_ptr, amount: u64, asset_id: raw_ptr) {
+        asm(r1: recipient, r2: amount, r3: asset_id) {
+            // Report entry should be created:
+            // L67: The `Contract::test_inline_assembly_usage_6` function contains inline assembly which moves assets with the `TR` instruction: `tr   r1 r2 r3`.
+            tr   r1 r2 r3;
+        }
+    }
+
+    fn test_inline_assembly_usage_7(amount: u64, sub_id: raw_ptr, destination: u64) {
+        asm(r1: amount, r2: sub_id, r3: destination) {
+            // Report entry should be created:
+            // L75: The `Contract::test_inline_assembly_usage_7` function contains inline assembly which moves assets with the `MINT` instruction: `mint r1 r2`.
+            mint r1 r2;
+            // Report entry should be created:
+            // L78: The `Contract::test_inline_assembly_usage_7` function contains inline assembly which alters the control flow with the `JMP` instruction: `jmp  r3`.
+            jmp  r3;
+        }
+    }
+}
+
+abi TestInlineAssemblyUsageHashing {
+    fn test_inline_assembly_usage_8(digest: raw_ptr, data: raw_ptr, length: u64);
+    fn test_inline_assembly_usage_9(flags: u64);
+}
+
+impl TestInlineAssemblyUsageHashing for Contract {
+    fn test_inline_assembly_usage_8(digest: raw_ptr, data: raw_ptr, length: u64) {
+        asm(r1: digest, r2: data, r3: length) {
+            // Report entry should be created:
+            // L92: The `Contract::test_inline_assembly_usage_8` function contains inline assembly which writes to caller-provided memory, the stack, the heap or storage with the `S256` instruction: `s256 r1 r2 r3`.
+            s256 r1 r2 r3;
+        }
+    }
+
+    fn test_inline_assembly_usage_9(flags: u64) {
+        asm(r1: flags) {
+            // Report entry should be created:
+            // L100: The `Contract::test_inline_assembly_usage_9` function contains inline assembly which has unclassified side effects with the `FLAG` instruction: `flag r1`.
+            flag r1;
+        }
+    }
 }
@@ -0,0 +1,6 @@
+library;
+
+pub struct Transfer {
+    pub sender: Identity,
+    pub amount: u64,
+}
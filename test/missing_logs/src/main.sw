@@ -4,6 +4,22 @@ use std::constants::ZERO_B256;
 
 use std::logging::log;
 use std::logging::log as imported_log;
+use std::asset::{mint_to, transfer};
+
+mod events;
+
+struct ValueChanged {
+    value: u64,
+}
+
+struct Deposit {
+    amount: u64,
+}
+
+struct Transfer {
+    recipient: Identity,
+    amount: u64,
+}
 
 abi TestMissingLogs {
     #[storage(write)]
@@ -22,18 +38,30 @@ abi TestMissingLogs {
     fn test_missing_logs_7(x: b256);
     #[storage(read, write)]
     fn test_missing_logs_8(x: b256);
+    #[storage(write)]
+    fn test_missing_logs_9(x: u64);
+}
+
+abi TestEventAnalysis {
+    fn mint_tokens(recipient: Identity, amount: u64);
+    #[storage(write)]
+    fn set_owner(new_owner: Identity);
+    fn transfer_tokens(recipient: Identity, amount: u64);
+    #[payable]
+    fn deposit(amount: u64);
 }
 
 storage {
     value: u64 = 0,
     admin: b256 = ZERO_B256,
+    owner: Identity = Identity::Address(Address::zero()),
 }
 
 impl TestMissingLogs for Contract {
     #[storage(write)]
     fn test_missing_logs_1(x: u64) {
         // Report entry should be created:
-        // L37: The `Contract::test_missing_logs_1` function writes to `storage.value` without being logged.
+        // L65: The `Contract::test_missing_logs_1` function writes to `storage.value` without being logged.
         storage.value.write(x);
     }
 
@@ -61,7 +89,7 @@ impl TestMissingLogs for Contract {
     #[storage(write)]
     fn test_missing_logs_5(x: b256) {
         // Report entry should be created:
-        // L65: The `Contract::test_missing_logs_5` function writes to `storage.admin` without being logged.
+        // L93: The `Contract::test_missing_logs_5` function writes to `storage.admin` without being logged.
         storage.admin.write(x);
     }
 
@@ -85,4 +113,74 @@ impl TestMissingLogs for Contract {
         storage.admin.write(x);
         imported_log(storage.admin.read());
     }
+
+    #[storage(write)]
+    fn test_missing_logs_9(x: u64) {
+        // Report entry should not be created
+        storage.value.write(x);
+        log(ValueChanged { value: x });
+    }
+}
+
+impl TestEventAnalysis for Contract {
+    // Report entry should be created:
+    // L128: The `Contract::mint_tokens` function mints assets without emitting any logs.
+    fn mint_tokens(recipient: Identity, amount: u64) {
+        mint_to(recipient, b256::zero(), amount);
+    }
+
+    // Report entry should be created:
+    // L135: The `Contract::set_owner` function changes ownership without emitting any logs.
+    #[storage(write)]
+    fn set_owner(new_owner: Identity) {
+        // Report entry should be created:
+        // L138: The `Contract::set_owner` function writes to `storage.owner` without being logged.
+        storage.owner.write(new_owner);
+    }
+
+    fn transfer_tokens(recipient: Identity, amount: u64) {
+        transfer(recipient, AssetId::default(), amount);
+
+        // Report entry should be created:
+        // L146: The `Contract::transfer_tokens` function logs the `Transfer` event type, which is declared in multiple modules: `test/missing_logs/src/events.sw`, `test/missing_logs/src/main.sw`. Consider giving each event type a unique name so it can be distinguished by indexers.
+        log(Transfer { recipient, amount });
+    }
+
+    #[payable]
+    fn deposit(amount: u64) {
+        // Report entry should be created:
+        // L153: The `Contract::deposit` function emits a log before a later revert path: `log(Deposit { amount })`. Consider emitting logs after all requirements have been checked.
+        log(Deposit { amount });
+        require(amount > 0, "Zero deposit");
+    }
+}
+
+abi TestRevertPaths {
+    fn withdraw(amount: u64);
+}
+
+impl TestRevertPaths for Contract {
+    fn withdraw(amount: u64) {
+        // Report entry should not be created
+        if amount > 0 {
+            log(Deposit { amount });
+        } else {
+            revert(0);
+        }
+    }
+}
+
+abi TestLocalHelpers {
+    fn burn_points(amount: u64) -> u64;
+}
+
+fn burn(points: u64, amount: u64) -> u64 {
+    points - amount
+}
+
+impl TestLocalHelpers for Contract {
+    fn burn_points(amount: u64) -> u64 {
+        // Report entry should not be created
+        burn(100, amount)
+    }
 }
@@ -0,0 +1,130 @@
+contract;
+
+use std::{block::timestamp, context::this_balance};
+
+abi TestMissingSlippageProtection {
+    #[storage(read)]
+    fn swap_unprotected(amount_in: u64, asset_out: AssetId);
+
+    #[storage(read)]
+    fn swap_without_deadline(amount_in: u64, asset_out: AssetId, min_amount_out: u64);
+
+    #[storage(read)]
+    fn swap_protected(amount_in: u64, asset_out: AssetId, min_amount_out: u64, deadline: u64);
+
+    fn swap_balances_without_minimum(amount_in: u64, asset_in: AssetId, asset_out: AssetId, deadline: u64);
+
+    fn withdraw(amount: u64, asset: AssetId);
+}
+
+storage {
+    reserve_in: u64 = 0,
+    reserve_out: u64 = 0,
+}
+
+impl TestMissingSlippageProtection for Contract {
+    #[storage(read)]
+    fn swap_unprotected(amount_in: u64, asset_out: AssetId) {
+        let amount_out = amount_in * storage.reserve_out.read() / storage.reserve_in.read();
+
+        // Report entry should be created:
+        // L32: The `Contract::swap_unprotected` function performs a transfer whose amount depends on a storage-read price or pool balance without checking a user-supplied minimum output amount or deadline: `transfer(msg_sender().unwrap(), asset_out, amount_out)`. Consider adding slippage and deadline protection.
+        transfer(msg_sender().unwrap(), asset_out, amount_out);
+    }
+
+    #[storage(read)]
+    fn swap_without_deadline(amount_in: u64, asset_out: AssetId, min_amount_out: u64) {
+        let amount_out = amount_in * storage.reserve_out.read() / storage.reserve_in.read();
+        require(amount_out >= min_amount_out, "Insufficient output amount");
+
+        // Report entry should be created:
+        // L42: The `Contract::swap_without_deadline` function performs a transfer whose amount depends on a storage-read price or pool balance without checking a user-supplied deadline: `transfer(msg_sender().unwrap(), asset_out, amount_out)`. Consider adding slippage and deadline protection.
+        transfer(msg_sender().unwrap(), asset_out, amount_out);
+    }
+
+    #[storage(read)]
+    fn swap_protected(amount_in: u64, asset_out: AssetId, min_amount_out: u64, deadline: u64) {
+        require(timestamp() <= deadline, "Expired");
+
+        let amount_out = amount_in * storage.reserve_out.read() / storage.reserve_in.read();
+        require(amount_out >= min_amount_out, "Insufficient output amount");
+
+        // Report entry should not be created
+        transfer(msg_sender().unwrap(), asset_out, amount_out);
+    }
+
+    fn swap_balances_without_minimum(amount_in: u64, asset_in: AssetId, asset_out: AssetId, deadline: u64) {
+        if timestamp() > deadline {
+            revert(0);
+        }
+
+        let amount_out = amount_in * this_balance(asset_out) / this_balance(asset_in);
+
+        // Report entry should be created:
+        // L65: The `Contract::swap_balances_without_minimum` function performs a transfer whose amount depends on a storage-read price or pool balance without checking a user-supplied minimum output amount: `transfer(msg_sender().unwrap(), asset_out, amount_out)`. Consider adding slippage and deadline protection.
+        transfer(msg_sender().unwrap(), asset_out, amount_out);
+    }
+
+    fn withdraw(amount: u64, asset: AssetId) {
+        // Report entry should not be created
+        transfer(msg_sender().unwrap(), asset, amount);
+    }
+}
+
+abi TestMisleadingParameterNames {
+    #[storage(read)]
+    fn swap_until_minute(amount_in: u64, asset_out: AssetId, minute: u64, deadline: u64);
+}
+
+impl TestMisleadingParameterNames for Contract {
+    #[storage(read)]
+    fn swap_until_minute(amount_in: u64, asset_out: AssetId, minute: u64, deadline: u64) {
+        require(timestamp() <= deadline, "Expired");
+
+        let amount_out = amount_in * storage.reserve_out.read() / storage.reserve_in.read();
+        require(amount_out >= minute, "Not a minimum output amount");
+
+        // Report entry should be created:
+        // L89: The `Contract::swap_until_minute` function performs a transfer whose amount depends on a storage-read price or pool balance without checking a user-supplied minimum output amount: `transfer(msg_sender().unwrap(), asset_out, amount_out)`. Consider adding slippage and deadline protection.
+        transfer(msg_sender().unwrap(), asset_out, amount_out);
+    }
+}
+
+abi TestSwapHelpers {
+    #[storage(read)]
+    fn swap_through_helper(amount_in: u64, asset_out: AssetId);
+
+    #[storage(read)]
+    fn swap_through_protected_helper(amount_in: u64, asset_out: AssetId, min_amount_out: u64, deadline: u64);
+}
+
+#[storage(read)]
+fn _swap(amount_in: u64, asset_out: AssetId) {
+    let amount_out = amount_in * storage.reserve_out.read() / storage.reserve_in.read();
+
+    // Report entry should be created:
+    // L107: The `Contract::swap_through_helper` function performs a transfer whose amount depends on a storage-read price or pool balance without checking a user-supplied minimum output amount or deadline: `transfer(msg_sender().unwrap(), asset_out, amount_out)`. Consider adding slippage and deadline protection.
+    transfer(msg_sender().unwrap(), asset_out, amount_out);
+}
+
+#[storage(read)]
+fn _swap_protected(amount_in: u64, asset_out: AssetId, min_amount_out: u64) {
+    let amount_out = amount_in * storage.reserve_out.read() / storage.reserve_in.read();
+    require(amount_out >= min_amount_out, "Insufficient output amount");
+
+    // Report entry should not be created
+    transfer(msg_sender().unwrap(), asset_out, amount_out);
+}
+
+impl TestSwapHelpers for Contract {
+    #[storage(read)]
+    fn swap_through_helper(amount_in: u64, asset_out: AssetId) {
+        _swap(amount_in, asset_out);
+    }
+
+    #[storage(read)]
+    fn swap_through_protected_helper(amount_in: u64, asset_out: AssetId, min_amount_out: u64, deadline: u64) {
+        require(timestamp() <= deadline, "Expired");
+        _swap_protected(amount_in, asset_out, min_amount_out);
+    }
+}
@@ -32,6 +32,7 @@ abi TestNonZeroIdentityValidation {
     fn test_identity_validated_17(input: Identity);
     fn test_identity_validated_18(input: Identity);
     fn test_identity_validated_19(input: Identity);
+    fn test_identity_validated_20(input: Identity);
 }
 
 impl TestNonZeroIdentityValidation for Contract {
@@ -64,7 +65,7 @@ impl TestNonZeroIdentityValidation for Contract {
     }
 
     // Report entry should be created:
-    // L68: The `Contract::test_address_validated_5` function does not check its `input` parameter for a zero value.
+    // L69: The `Contract::test_address_validated_5` function does not check its `input` parameter for a zero value.
     fn test_address_validated_5(input: Address) {
         log(input);
     }
@@ -98,7 +99,7 @@ impl TestNonZeroIdentityValidation for Contract {
     }
 
     // Report entry should be created:
-    // L102: The `Contract::test_contract_id_validated_5` function does not check its `input` parameter for a zero value.
+    // L103: The `Contract::test_contract_id_validated_5` function does not check its `input` parameter for a zero value.
     fn test_contract_id_validated_5(input: ContractId) {
         log(input);
     }
@@ -326,8 +327,26 @@ impl TestNonZeroIdentityValidation for Contract {
     }
 
     // Report entry should be created:
-    // L330: The `Contract::test_identity_validated_19` function does not check its `input` parameter for a zero value.
+    // L331: The `Contract::test_identity_validated_19` function does not check its `input` parameter for a zero value.
     fn test_identity_validated_19(input: Identity) {
         log(input);
     }
+
+    // Report entry should be created with low confidence, since the helper function may check the parameter:
+    // L337: The `Contract::test_identity_validated_20` function does not check its `input` parameter for a zero value.
+    fn test_identity_validated_20(input: Identity) {
+        check_identity(input);
+        log(input);
+    }
+}
+
+// Report entry should not be created
+fn check_identity(input: Identity) {
+    if match input {
+        Identity::Address(x) => x == Address::from(ZERO_B256),
+        Identity::ContractId(x) => x == ContractId::from(ZERO_B256),
+    } {
+        revert(0);
+    }
+    log(input);
 }
@@ -0,0 +1,7 @@
+predicate;
+
+/// Report entry should be created:
+// L5: The predicate always returns `true` without checking any condition, which allows anyone to spend its coins.
+fn main() -> bool {
+    true
+}
@@ -0,0 +1,27 @@
+predicate;
+
+use std::{inputs::input_count, outputs::{output_amount, output_asset_to}};
+
+configurable {
+    RECEIVER: Address = Address::zero(),
+    ASK_AMOUNT: u64 = 100,
+}
+
+fn main() -> bool {
+    let amount_paid = if input_count() == 1 {
+        output_amount(0) == Some(ASK_AMOUNT)
+    } else {
+        // Report entry should be created:
+        // L16: The `main` function returns `true` in the `else` branch of `if input_count() == 1`, which skips the validation performed in the other branch.
+        true
+    };
+
+    let receiver_paid = match output_asset_to(0) {
+        Some(to) => to == RECEIVER,
+        // Report entry should be created:
+        // L23: The `main` function returns `true` in the `None` branch of `match output_asset_to(0)`, which skips the validation performed in the other branches.
+        None => true,
+    };
+
+    amount_paid && receiver_paid
+}
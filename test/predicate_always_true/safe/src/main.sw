@@ -0,0 +1,27 @@
+predicate;
+
+use std::{inputs::input_count, outputs::{output_amount, output_asset_to}};
+
+configurable {
+    RECEIVER: Address = Address::zero(),
+    ASK_AMOUNT: u64 = 100,
+}
+
+fn main() -> bool {
+    require(input_count() == 1, "Invalid input count");
+
+    let amount_paid = if output_amount(0) == Some(ASK_AMOUNT) {
+        true
+    } else {
+        false
+    };
+
+    let receiver_paid = match output_asset_to(0) {
+        Some(to) => to == RECEIVER,
+        None => false,
+    };
+
+    require(amount_paid && receiver_paid, "Invalid outputs");
+
+    true
+}
@@ -0,0 +1,22 @@
+predicate;
+
+use std::{inputs::{input_coin_owner, input_count}, outputs::{output_amount, output_asset_id, output_asset_to, output_count}};
+
+configurable {
+    MAKER: Address = Address::zero(),
+    ASK_AMOUNT: u64 = 100,
+    ASK_ASSET: AssetId = AssetId::zero(),
+}
+
+fn main() -> bool {
+    if input_count() == 1 && input_coin_owner(0) == Some(MAKER) {
+        return true;
+    }
+
+    let outputs = output_count();
+
+    outputs == 2
+        && output_asset_to(0) == Some(MAKER)
+        && output_asset_id(0) == Some(ASK_ASSET)
+        && output_amount(0) == Some(ASK_AMOUNT)
+}
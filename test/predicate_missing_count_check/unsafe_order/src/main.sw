@@ -0,0 +1,21 @@
+predicate;
+
+use std::{inputs::input_coin_owner, outputs::{output_amount, output_asset_id, output_asset_to}};
+
+configurable {
+    MAKER: Address = Address::zero(),
+    ASK_AMOUNT: u64 = 100,
+    ASK_ASSET: AssetId = AssetId::zero(),
+}
+
+fn main() -> bool {
+    // Report entry should be created:
+    // L14: The predicate reads transaction inputs by index without checking the number of inputs: `input_coin_owner(0)`.
+    if input_coin_owner(0) == Some(MAKER) {
+        return true;
+    }
+
+    // Report entry should be created:
+    // L20: The predicate reads transaction outputs by index without checking the number of outputs: `output_asset_to(0)`.
+    output_asset_to(0) == Some(MAKER) && output_asset_id(0) == Some(ASK_ASSET) && output_amount(0) == Some(ASK_AMOUNT)
+}
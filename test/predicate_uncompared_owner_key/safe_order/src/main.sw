@@ -0,0 +1,15 @@
+predicate;
+
+use std::inputs::input_coin_owner;
+
+configurable {
+    OWNER: Address = Address::zero(),
+}
+
+fn is_owner(owner: Address) -> bool {
+    input_coin_owner(0) == Some(owner)
+}
+
+fn main() -> bool {
+    is_owner(OWNER)
+}
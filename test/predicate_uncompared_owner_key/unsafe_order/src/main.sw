@@ -0,0 +1,18 @@
+predicate;
+
+use std::{inputs::input_coin_owner, outputs::{output_amount, output_asset_to}};
+
+configurable {
+    // Report entry should be created:
+    // L8: The predicate declares the `OWNER` configurable as an owner key, but never compares it against a transaction input or recovered signer.
+    OWNER: Address = Address::zero(),
+
+    MAKER: Address = Address::zero(),
+    ASK_AMOUNT: u64 = 100,
+}
+
+fn main() -> bool {
+    let maker = MAKER;
+
+    output_asset_to(0) == Some(maker) && output_amount(0) == Some(ASK_AMOUNT)
+}
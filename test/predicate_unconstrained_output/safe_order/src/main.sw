@@ -0,0 +1,20 @@
+predicate;
+
+use std::outputs::{output_amount, output_asset_id, output_asset_to};
+
+configurable {
+    ASK_AMOUNT: u64 = 100,
+    ASK_ASSET: AssetId = AssetId::zero(),
+    RECEIVER: Address = Address::zero(),
+}
+
+fn main() -> bool {
+    let amount = output_amount(0).unwrap();
+
+    let to_receiver = match output_asset_to(0) {
+        Some(to) => to == RECEIVER,
+        None => false,
+    };
+
+    to_receiver && output_asset_id(0) == Some(ASK_ASSET) && amount >= ASK_AMOUNT
+}
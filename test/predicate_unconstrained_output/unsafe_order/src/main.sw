@@ -0,0 +1,14 @@
+predicate;
+
+use std::outputs::{output_amount, output_asset_id};
+
+configurable {
+    ASK_AMOUNT: u64 = 100,
+}
+
+/// Report entry should be created:
+// L11: The predicate inspects its transaction outputs without constraining their asset id, recipient.
+fn main() -> bool {
+    let asset_id = output_asset_id(0);
+    output_amount(0).unwrap() >= ASK_AMOUNT && asset_id.is_some()
+}
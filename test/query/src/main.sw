@@ -0,0 +1,32 @@
+contract;
+
+use std::asset::transfer;
+use std::asset::transfer as send;
+
+abi Query {
+    #[storage(read, write)]
+    fn withdraw(to: Identity, asset_id: AssetId, amount: u64);
+}
+
+storage {
+    balance: u64 = 0,
+    withdrawn: u64 = 0,
+}
+
+impl Query for Contract {
+    #[storage(read, write)]
+    fn withdraw(to: Identity, asset_id: AssetId, amount: u64) {
+        storage.balance.write(storage.balance.read() - amount);
+        storage.withdrawn.write(amount);
+
+        transfer(to, asset_id, amount);
+
+        send(
+            to,
+            asset_id,
+            amount,
+        );
+
+        std::asset::transfer(to, asset_id, 1);
+    }
+}
@@ -0,0 +1,41 @@
+script;
+
+use std::{inputs::{input_asset_id, input_count, input_coin_owner, input_type, Input}, outputs::{output_amount, output_asset_to}};
+
+fn find_input_owner(asset_id: AssetId) -> Option<Address> {
+    let mut i = 0;
+
+    while i < input_count().as_u64() {
+        // Index is not hard-coded
+        if input_asset_id(i) == Some(asset_id) {
+            return input_coin_owner(i);
+        }
+
+        i += 1;
+    }
+
+    None
+}
+
+fn main(asset_id: AssetId, recipient: Address) {
+    // Report entry should be created:
+    // L23: The `main` function assumes a fixed transaction input ordering: `input_coin_owner(0)`.
+    let sender = input_coin_owner(0);
+
+    // Report entry should not be created, since the recipient of the output is checked
+    require(output_asset_to(1) == Some(recipient), "Invalid recipient");
+
+    require(find_input_owner(asset_id) == sender, "Invalid sender");
+}
+
+fn checked_input_owner() -> Option<Address> {
+    // Report entry should not be created, since the type of the input is checked
+    require(input_type(1) == Some(Input::Coin), "Invalid input");
+    input_coin_owner(1)
+}
+
+fn unchecked_output_amount() -> u64 {
+    // Report entry should be created:
+    // L40: The `unchecked_output_amount` function assumes a fixed transaction output ordering: `output_amount(2)`.
+    output_amount(2).unwrap_or(0)
+}
@@ -0,0 +1,48 @@
+script;
+
+abi Router {
+    fn swap(amount_in: u64, min_amount_out: u64) -> u64;
+    fn quote(amount_in: u64) -> u64;
+}
+
+fn is_valid_amount(amount: u64) -> bool {
+    if amount == 0 {
+        // Helper functions are allowed to return early
+        return false;
+    }
+
+    true
+}
+
+fn main(router_id: ContractId, amount_in: u64, min_amount_out: u64) -> u64 {
+    let router = abi(Router, router_id.into());
+
+    if !is_valid_amount(amount_in) {
+        // Report entry should not be created, since the condition does not test
+        // the result of an external call or a balance
+        return 0;
+    }
+
+    let amount_out = router.swap(amount_in, min_amount_out);
+
+    if amount_out < min_amount_out {
+        log(amount_out);
+
+        // Report entry should be created:
+        // L33: The `main` function returns early without reverting when `amount_out < min_amount_out` holds.
+        return amount_out;
+    }
+
+    if router.quote(amount_in) > amount_out {
+        // Reverting on failed preconditions is fine
+        revert(0);
+    }
+
+    if std::context::balance_of(router_id, AssetId::base()) == 0 {
+        // Report entry should be created:
+        // L44: The `main` function returns early without reverting when `std::context::balance_of(router_id, AssetId::base()) == 0` holds.
+        return amount_out;
+    }
+
+    amount_out
+}
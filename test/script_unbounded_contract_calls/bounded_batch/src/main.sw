@@ -0,0 +1,15 @@
+script;
+
+abi Pool {
+    fn sync();
+}
+
+const MAX_POOLS: u64 = 8;
+
+fn main(pool_ids: Vec<ContractId>) {
+    require(pool_ids.len() <= MAX_POOLS, "Too many pools");
+
+    for pool_id in pool_ids.iter() {
+        abi(Pool, pool_id.into()).sync();
+    }
+}
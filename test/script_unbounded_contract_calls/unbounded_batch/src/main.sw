@@ -0,0 +1,19 @@
+script;
+
+abi Pool {
+    fn sync();
+}
+
+fn main(pool_ids: Vec<ContractId>) {
+    let mut i = 0;
+
+    while i < pool_ids.len() {
+        let pool_id = pool_ids.get(i).unwrap();
+
+        // Report entry should be created:
+        // L15: The `main` function calls every contract in the unbounded `pool_ids` argument: `abi(Pool, pool_id.into())`.
+        abi(Pool, pool_id.into()).sync();
+
+        i += 1;
+    }
+}
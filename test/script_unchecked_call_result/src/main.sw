@@ -0,0 +1,29 @@
+script;
+
+abi Vault {
+    fn deposit(recipient: Identity) -> bool;
+    fn withdraw(amount: u64) -> u64;
+    fn sync();
+}
+
+fn main(vault_id: ContractId, recipient: Identity, amount: u64) {
+    let vault = abi(Vault, vault_id.into());
+
+    // Report entry should be created:
+    // L14: The `main` function discards the result of an external call: `vault.deposit(recipient)`.
+    vault.deposit(recipient);
+
+    // Report entry should be created:
+    // L18: The `main` function discards the result of an external call: `abi(Vault, vault_id.into()).withdraw(amount)`.
+    let _ = abi(Vault, vault_id.into()).withdraw(amount);
+
+    // Return value is checked
+    require(vault.deposit(recipient), "Deposit failed");
+
+    // Return value is used
+    let withdrawn = vault.withdraw(amount);
+    require(withdrawn == amount, "Withdraw failed");
+
+    // No return value to check
+    vault.sync();
+}
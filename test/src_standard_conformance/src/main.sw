@@ -0,0 +1,123 @@
+contract;
+
+use standards::{
+    src20::{SetDecimalsEvent, SetNameEvent, SetSymbolEvent, SRC20, TotalSupplyEvent},
+    src3::SRC3,
+    src5::{SRC5, State},
+    src7::{Metadata, SRC7},
+};
+use sway_libs::asset::metadata::_set_metadata;
+use std::{asset::{burn, mint_to}, string::String};
+
+configurable {
+    DECIMALS: u8 = 9u8,
+}
+
+abi TokenAdmin {
+    #[storage(write)]
+    fn set_name(asset: AssetId, name: String);
+
+    #[storage(write)]
+    fn set_symbol(asset: AssetId, symbol: String);
+
+    fn set_decimals(asset: AssetId);
+
+    #[storage(write)]
+    fn set_metadata(asset: AssetId, key: String, metadata: Metadata);
+}
+
+storage {
+    total_supply: u64 = 0,
+    name: Option<String> = None,
+    symbol: Option<String> = None,
+    metadata: StorageMetadata = StorageMetadata {},
+}
+
+// Report entry should be created:
+// L38: The `SRC20` implementation for `Contract` is missing the required `fn total_assets() -> u64` function.
+impl SRC20 for Contract {
+    #[storage(read)]
+    fn total_supply(asset: AssetId) -> Option<u64> {
+        Some(storage.total_supply.read())
+    }
+
+    #[storage(read)]
+    fn name(asset: AssetId) -> Option<String> {
+        storage.name.read()
+    }
+
+    #[storage(read)]
+    fn symbol(asset: AssetId) -> Option<String> {
+        storage.symbol.read()
+    }
+
+    #[storage(read)]
+    fn decimals(asset: AssetId) -> Option<u8> {
+        Some(DECIMALS)
+    }
+}
+
+impl SRC3 for Contract {
+    // Report entry should be created:
+    // L67: The `Contract::mint` function does not match the `SRC3` standard: expected `fn mint(Identity, Option<SubId>, u64)`, found `fn mint(Identity, u64)`.
+    //
+    // Report entry should be created:
+    // L67: The `Contract::mint` function changes the total supply of an asset without logging a `TotalSupplyEvent`, which is required by the `SRC20` standard.
+    #[storage(read, write)]
+    fn mint(recipient: Identity, amount: u64) {
+        storage.total_supply.write(storage.total_supply.read() + amount);
+        mint_to(recipient, b256::zero(), amount);
+    }
+
+    // Report entry should be created:
+    // L75: The `Contract::burn` function is not marked `#[payable]`, which is required by the `SRC3` standard.
+    #[storage(read, write)]
+    fn burn(sub_id: SubId, amount: u64) {
+        let supply = storage.total_supply.read() - amount;
+        storage.total_supply.write(supply);
+        burn(sub_id, amount);
+        log(TotalSupplyEvent::new(AssetId::default(), supply, msg_sender().unwrap()));
+    }
+}
+
+impl SRC7 for Contract {
+    #[storage(read)]
+    fn metadata(asset: AssetId, key: String) -> Option<Metadata> {
+        storage.metadata.get(asset, key)
+    }
+}
+
+impl SRC5 for Contract {
+    fn owner() -> State {
+        State::Uninitialized
+    }
+}
+
+impl TokenAdmin for Contract {
+    // Report entry should be created:
+    // L100: The `Contract::set_name` function does not log a `SetNameEvent`, which is required by the `SRC20` standard.
+    #[storage(write)]
+    fn set_name(asset: AssetId, name: String) {
+        storage.name.write(Some(name));
+        log(String::from_ascii_str("SetNameEvent"));
+    }
+
+    // Report entry should not be created
+    #[storage(write)]
+    fn set_symbol(asset: AssetId, symbol: String) {
+        storage.symbol.write(Some(symbol));
+        log(SetSymbolEvent::new(asset, Some(symbol), msg_sender().unwrap()));
+    }
+
+    fn set_decimals(asset: AssetId) {
+        // Report entry should be created:
+        // L114: The `Contract::set_decimals` function logs a `SetDecimalsEvent` with 6 decimals, but the `decimals` function returns 9.
+        log(SetDecimalsEvent { asset, decimals: 6u8, sender: msg_sender().unwrap() });
+    }
+
+    // Report entry should not be created
+    #[storage(write)]
+    fn set_metadata(asset: AssetId, key: String, metadata: Metadata) {
+        _set_metadata(storage.metadata, asset, key, metadata);
+    }
+}
@@ -0,0 +1,17 @@
+library;
+
+const COUNTER_KEY: b256 = 0x0000000000000000000000000000000000000000000000000000000000000001;
+
+#[storage(write)]
+pub fn write_counter(value: u64) {
+    std::storage::storage_api::write(COUNTER_KEY, 0, value);
+}
+
+pub struct Counter {}
+
+impl Counter {
+    #[storage(read)]
+    pub fn current() -> u64 {
+        std::storage::storage_api::read::<u64>(COUNTER_KEY, 0).unwrap_or(0)
+    }
+}
@@ -0,0 +1,132 @@
+contract;
+
+mod counter;
+mod metrics;
+
+use counter::{Counter, write_counter};
+
+storage {
+    balance: u64 = 0,
+    total: u64 = 0,
+}
+
+abi Vault {
+    #[storage(read, write)]
+    fn deposit(amount: u64);
+
+    #[storage(read, write)]
+    fn balance() -> u64;
+
+    #[storage(read)]
+    fn version() -> u64;
+
+    #[storage(read, write)]
+    fn total() -> u64;
+}
+
+#[storage(read, write)]
+fn add_balance(amount: u64) {
+    storage.balance.write(storage.balance.read() + amount);
+    storage.total.write(storage.total.read() + amount);
+}
+
+#[storage(read)]
+fn get_total() -> u64 {
+    storage.total.read()
+}
+
+impl Vault for Contract {
+    // Storage is written through the `add_balance` function
+    #[storage(read, write)]
+    fn deposit(amount: u64) {
+        add_balance(amount);
+    }
+
+    /// Report entry should be created:
+    // L48: The `Contract::balance` function is declared with #[storage(read, write)], but never writes to storage.
+    #[storage(read, write)]
+    fn balance() -> u64 {
+        storage.balance.read()
+    }
+
+    /// Report entry should be created:
+    // L55: The `Contract::version` function is declared with #[storage(read)], but never accesses storage.
+    #[storage(read)]
+    fn version() -> u64 {
+        1
+    }
+
+    /// Report entry should be created:
+    // L62: The `Contract::total` function is declared with #[storage(read)], but the `Vault` abi declares it with #[storage(read, write)].
+    #[storage(read)]
+    fn total() -> u64 {
+        get_total()
+    }
+}
+
+abi Registry {
+    #[storage(read, write)]
+    fn register(value: u64);
+
+    #[storage(write)]
+    fn reset();
+
+    #[storage(write)]
+    fn count(value: u64);
+}
+
+#[storage(write)]
+fn set_key(key: StorageKey<u64>, value: u64) {
+    key.write(value);
+}
+
+impl Registry for Contract {
+    // Report entry should not be created, since the storage key is written by the helper
+    #[storage(read, write)]
+    fn register(value: u64) {
+        set_key(storage.total, value);
+    }
+
+    // Report entry should not be created, since the storage slot is written directly
+    #[storage(write)]
+    fn reset() {
+        std::storage::storage_api::write(b256::zero(), 0, 0);
+    }
+
+    // Report entry should not be created, since storage is written by the helper in the other module
+    #[storage(write)]
+    fn count(value: u64) {
+        write_counter(value);
+    }
+}
+
+abi Ledger {
+    #[storage(read)]
+    fn current() -> u64;
+
+    #[storage(write)]
+    fn clear();
+}
+
+struct Totals {}
+
+impl Totals {
+    #[storage(write)]
+    fn clear() {
+        storage.total.write(0);
+    }
+}
+
+impl Ledger for Contract {
+    // Report entry should not be created, since the storage accesses of the associated function in the other module are unknown
+    #[storage(read)]
+    fn current() -> u64 {
+        Counter::current()
+    }
+
+    // Report entry should not be created, since storage is written by the associated function
+    #[storage(write)]
+    fn clear() {
+        Totals::clear();
+    }
+}
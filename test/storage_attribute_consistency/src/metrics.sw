@@ -0,0 +1,6 @@
+library;
+
+// Shares its name with the function imported from the `counter` module, but does not access storage
+pub fn write_counter(value: u64) -> u64 {
+    value
+}
@@ -0,0 +1,90 @@
+contract;
+
+use std::storage::storage_vec::*;
+
+abi Registry {
+    #[storage(read, write)]
+    fn register(id: u64, owner: Identity);
+
+    #[storage(read)]
+    fn owner_of(id: u64) -> Option<Identity>;
+
+    #[storage(write)]
+    fn touch();
+
+    #[storage(read, write)]
+    fn push_item(item: u64);
+
+    #[storage(read, write)]
+    fn bump();
+
+    #[storage(write)]
+    fn reset_nonce(owner: Identity);
+
+    #[storage(read)]
+    fn current_count() -> u64;
+}
+
+storage {
+    owners: StorageMap<u64, Identity> = StorageMap {},
+    count: u64 = 0,
+    last_touched: u64 = 0,
+    admin: Identity = Identity::Address(Address::zero()),
+    items: StorageVec<u64> = StorageVec {},
+    bumps: u64 = 0,
+    nonces: StorageMap<Identity, u64> = StorageMap {},
+    stats {
+        total: u64 = 0,
+    },
+}
+
+#[storage(read, write)]
+fn increment(counter: StorageKey<u64>) {
+    counter.write(counter.read() + 1);
+}
+
+#[storage(read)]
+fn current(counter: StorageKey<u64>) -> u64 {
+    counter.read()
+}
+
+impl Registry for Contract {
+    #[storage(read, write)]
+    fn register(id: u64, owner: Identity) {
+        storage.owners.insert(id, owner);
+        storage.count.write(storage.count.read() + 1);
+        storage::stats.total.write(storage::stats.total.read() + 1);
+    }
+
+    #[storage(read)]
+    fn owner_of(id: u64) -> Option<Identity> {
+        storage.owners.get(id).try_read()
+    }
+
+    #[storage(write)]
+    fn touch() {
+        storage.last_touched.write(42);
+    }
+
+    #[storage(read, write)]
+    fn push_item(item: u64) {
+        storage.items.push(item);
+        storage.items.get(0).unwrap().write(item);
+    }
+
+    #[storage(read, write)]
+    fn bump() {
+        increment(storage.bumps);
+    }
+
+    #[storage(write)]
+    fn reset_nonce(owner: Identity) {
+        let nonce = storage.nonces.get(owner);
+        nonce.write(0);
+    }
+
+    #[storage(read)]
+    fn current_count() -> u64 {
+        current(storage.count)
+    }
+}
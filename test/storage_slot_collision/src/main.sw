@@ -0,0 +1,73 @@
+contract;
+
+mod slots;
+
+use std::constants::ZERO_B256;
+use std::hash::*;
+use std::storage::storage_api::{read, write};
+
+const OWNER_SLOT: b256 = 0xb53e80568e0d57b30044935f8eeb7dd0ee8986144e5215e30fa0d12677c1b1a5;
+const BALANCE_SLOT: b256 = 0x9dd476cdb7c87c4488f9d37589bbb6551ee9adc6d47448105945cf3ded663985;
+const UNUSED_SLOT: b256 = 0x0000000000000000000000000000000000000000000000000000000000000001;
+
+abi TestStorageSlotCollision {
+    #[storage(write)]
+    fn test_storage_slot_collision_1();
+    #[storage(read)]
+    fn test_storage_slot_collision_2() -> b256;
+    #[storage(read)]
+    fn test_storage_slot_collision_3() -> u64;
+    #[storage(read, write)]
+    fn test_storage_slot_collision_4() -> u64;
+}
+
+storage {
+    // Report entry should not be created
+    balance: u64 = 0,
+
+    // Report entry should not be created
+    owner in OWNER_SLOT: b256 = ZERO_B256,
+
+    // Report entry should be created:
+    // L33: The `admin` storage field can alias the same storage slot as the `owner` storage field.
+    admin in OWNER_SLOT: b256 = ZERO_B256,
+
+    // Report entry should be created:
+    // L37: The `shadow_balance` storage field can alias the same storage slot as the `balance` storage field.
+    shadow_balance in 0x9dd476cdb7c87c4488f9d37589bbb6551ee9adc6d47448105945cf3ded663985: u64 = 0,
+
+    my_ns {
+        // Report entry should not be created
+        balance: u64 = 0,
+    },
+}
+
+impl TestStorageSlotCollision for Contract {
+    #[storage(write)]
+    fn test_storage_slot_collision_1() {
+        // Report entry should be created:
+        // L50: The `Contract::test_storage_slot_collision_1` function uses a manual storage slot that overlaps with the slot of the `balance`, `shadow_balance` storage fields: `write(BALANCE_SLOT, 0, 1)`
+        write(BALANCE_SLOT, 0, 1);
+    }
+
+    #[storage(read)]
+    fn test_storage_slot_collision_2() -> b256 {
+        // Report entry should be created:
+        // L57: The `Contract::test_storage_slot_collision_2` function uses a manual storage slot that overlaps with the slot of the `owner`, `admin` storage fields: `StorageKey::<b256>::new(OWNER_SLOT, 0, OWNER_SLOT)`
+        StorageKey::<b256>::new(OWNER_SLOT, 0, OWNER_SLOT).read()
+    }
+
+    #[storage(read)]
+    fn test_storage_slot_collision_3() -> u64 {
+        // Report entry should be created:
+        // L64: The `Contract::test_storage_slot_collision_3` function uses a manual storage slot that overlaps with the slot of the `balance`, `shadow_balance` storage fields: `read::<u64>(sha256("storage.balance"), 0)`
+        read::<u64>(sha256("storage.balance"), 0).unwrap_or(0)
+    }
+
+    #[storage(read, write)]
+    fn test_storage_slot_collision_4() -> u64 {
+        // Report entry should not be created
+        write(UNUSED_SLOT, 0, 1);
+        storage::my_ns.balance.read()
+    }
+}
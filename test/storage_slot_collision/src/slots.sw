@@ -0,0 +1,11 @@
+library;
+
+use std::hash::*;
+use std::storage::storage_api::write;
+
+#[storage(write)]
+pub fn reset_balance() {
+    // Report entry should be created:
+    // L10: The `reset_balance` function uses a manual storage slot that overlaps with the slot of the `balance` (in `test/storage_slot_collision/src/main.sw`), `shadow_balance` (in `test/storage_slot_collision/src/main.sw`) storage fields: `write(sha256("storage.balance"), 0, 0)`
+    write(sha256("storage.balance"), 0, 0);
+}
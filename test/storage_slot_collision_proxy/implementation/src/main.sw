@@ -0,0 +1,24 @@
+contract;
+
+abi Counter {
+    #[storage(read, write)]
+    fn increment() -> u64;
+}
+
+storage {
+    // Report entry should be created:
+    // L11: The `owner` storage field can alias the same storage slot as the `owner` storage field in `test/storage_slot_collision_proxy/proxy/src/main.sw`.
+    owner: Option<Identity> = None,
+
+    // Report entry should not be created
+    count: u64 = 0,
+}
+
+impl Counter for Contract {
+    #[storage(read, write)]
+    fn increment() -> u64 {
+        require(storage.owner.read().is_some(), "Not initialized");
+        storage.count.write(storage.count.read() + 1);
+        storage.count.read()
+    }
+}
@@ -0,0 +1,20 @@
+contract;
+
+use std::execution::run_external;
+
+storage {
+    SRC14 {
+        target in 0x7bb458adc1d118713319a5baa00a2d049dd64d2916477d2688d76970c898cd55: Option<ContractId> = None,
+    },
+
+    // Report entry should be created:
+    // L12: The `owner` storage field can alias the same storage slot as the `owner` storage field in `test/storage_slot_collision_proxy/implementation/src/main.sw`.
+    owner: Option<Identity> = None,
+}
+
+#[fallback]
+#[storage(read)]
+fn fallback() {
+    require(storage.owner.read().is_some(), "Not initialized");
+    run_external(storage::SRC14.target.read().unwrap())
+}
@@ -0,0 +1,9 @@
+library;
+
+pub fn require_caller(owner: Identity) {
+    check_sender(owner);
+}
+
+fn check_sender(owner: Identity) {
+    require(msg_sender().unwrap() == owner, "Not owner");
+}
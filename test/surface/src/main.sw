@@ -0,0 +1,89 @@
+contract;
+
+mod auth;
+
+use auth::require_caller;
+use std::{asset::transfer, auth::msg_sender, context::msg_amount};
+
+abi Vault {
+    #[payable]
+    #[storage(read, write)]
+    fn deposit();
+
+    #[storage(read, write)]
+    fn withdraw(to: Identity, amount: u64);
+
+    #[storage(read, write)]
+    fn set_owner(owner: Identity);
+
+    fn ping_oracle(oracle_id: ContractId);
+
+    #[storage(read)]
+    fn balance() -> u64;
+
+    #[storage(read, write)]
+    fn pause();
+}
+
+abi Oracle {
+    fn ping();
+}
+
+struct DepositEvent {
+    sender: Identity,
+    amount: u64,
+}
+
+struct OwnerChangedEvent {
+    owner: Identity,
+}
+
+storage {
+    owner: Identity = Identity::Address(Address::zero()),
+    balance: u64 = 0,
+    paused: bool = false,
+}
+
+#[storage(read)]
+fn only_vault_owner() {
+    require(msg_sender().unwrap() == storage.owner.read(), "Not owner");
+}
+
+impl Vault for Contract {
+    #[payable]
+    #[storage(read, write)]
+    fn deposit() {
+        storage.balance.write(storage.balance.read() + msg_amount());
+        log(DepositEvent { sender: msg_sender().unwrap(), amount: msg_amount() });
+    }
+
+    #[storage(read, write)]
+    fn withdraw(to: Identity, amount: u64) {
+        only_vault_owner();
+        storage.balance.write(storage.balance.read() - amount);
+        transfer(to, AssetId::base(), amount);
+    }
+
+    #[storage(read, write)]
+    fn set_owner(owner: Identity) {
+        only_vault_owner();
+        storage.owner.write(owner);
+        log(OwnerChangedEvent { owner });
+    }
+
+    fn ping_oracle(oracle_id: ContractId) {
+        let oracle = abi(Oracle, oracle_id.into());
+        oracle.ping();
+    }
+
+    #[storage(read)]
+    fn balance() -> u64 {
+        storage.balance.read()
+    }
+
+    #[storage(read, write)]
+    fn pause() {
+        require_caller(storage.owner.read());
+        storage.paused.write(true);
+    }
+}
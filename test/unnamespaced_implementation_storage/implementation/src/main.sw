@@ -0,0 +1,33 @@
+contract;
+
+abi Counter {
+    #[storage(read, write)]
+    fn increment();
+}
+
+storage {
+    // Report entry should be created:
+    // L11: The `count` storage field is declared without a namespace in a proxy implementation contract, which can collide with the storage of the proxy contract. Consider declaring it inside a storage namespace.
+    count: u64 = 0,
+
+    // Report entry should be created:
+    // L15: The `owner` storage field is declared without a namespace in a proxy implementation contract, which can collide with the storage of the proxy contract. Consider declaring it inside a storage namespace.
+    owner: Option<Identity> = None,
+
+    // Report entry should not be created
+    total in 0x8a1a0a3b5fd1a8d1c3c4ad3f3c6a4f2a8b9e2d7c1f0b3a4e5d6c7b8a9f0e1d2c: u64 = 0,
+
+    counter {
+        // Report entry should not be created
+        count: u64 = 0,
+    },
+}
+
+impl Counter for Contract {
+    #[storage(read, write)]
+    fn increment() {
+        storage.count.write(storage.count.read() + 1);
+        storage::counter.count.write(storage::counter.count.read() + 1);
+        storage.total.write(storage.total.read() + 1);
+    }
+}
@@ -12,6 +12,9 @@ abi TestUnprotectedInitialization {
 
     #[storage(read, write)]
     fn safe_init3(value: u64);
+
+    #[storage(read, write)]
+    fn safe_init4(value: u64);
 }
 
 storage {
@@ -24,10 +27,22 @@ fn ensure_secure() {
     require(!storage.initialized.read(), "Already initialized");
 }
 
+#[storage(read)]
+fn ensure_secure_depth(depth: u64) {
+    if depth > 0 {
+        ensure_secure_next(depth - 1);
+    }
+}
+
+#[storage(read)]
+fn ensure_secure_next(depth: u64) {
+    ensure_secure_depth(depth);
+    require(!storage.initialized.read(), "Already initialized");
+}
 
 impl TestUnprotectedInitialization for Contract {
     // Report entry should be created:
-    // L23: The `Contract::unsafe_init` function is an unprotected initializer function. Consider adding a requirement to prevent it from being called multiple times.
+    // L47: The `Contract::unsafe_init` function is an unprotected initializer function. Consider adding a requirement to prevent it from being called multiple times.
     #[storage(write)]
     fn unsafe_init(value: u64) {
         storage.value.write(value);
@@ -55,4 +70,11 @@ impl TestUnprotectedInitialization for Contract {
         ensure_secure();
         storage.value.write(value);
     }
+
+    // Report entry should not be created
+    #[storage(read, write)]
+    fn safe_init4(value: u64) {
+        ensure_secure_depth(1);
+        storage.value.write(value);
+    }
 }
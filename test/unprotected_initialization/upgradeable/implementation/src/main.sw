@@ -0,0 +1,40 @@
+contract;
+
+abi Implementation {
+    #[storage(read, write)]
+    fn unsafe_initialize(owner: Identity);
+
+    #[storage(read, write)]
+    fn safe_initialize(owner: Identity);
+}
+
+configurable {
+    DEPLOYER: Identity = Identity::Address(Address::zero()),
+}
+
+storage {
+    implementation {
+        initialized: bool = false,
+        owner: Option<Identity> = None,
+    },
+}
+
+impl Implementation for Contract {
+    // Report entry should be created:
+    // L26: The `Contract::unsafe_initialize` function is an initializer function in a proxy implementation contract that can be called on the implementation directly. Consider checking against `msg_sender()` in order to limit access.
+    #[storage(read, write)]
+    fn unsafe_initialize(owner: Identity) {
+        require(!storage::implementation.initialized.read(), "Already initialized");
+        storage::implementation.initialized.write(true);
+        storage::implementation.owner.write(Some(owner));
+    }
+
+    // Report entry should not be created
+    #[storage(read, write)]
+    fn safe_initialize(owner: Identity) {
+        require(msg_sender().unwrap() == DEPLOYER, "Not deployer");
+        require(!storage::implementation.initialized.read(), "Already initialized");
+        storage::implementation.initialized.write(true);
+        storage::implementation.owner.write(Some(owner));
+    }
+}
@@ -0,0 +1,15 @@
+contract;
+
+use std::execution::run_external;
+
+storage {
+    SRC14 {
+        target in 0x7bb458adc1d118713319a5baa00a2d049dd64d2916477d2688d76970c898cd55: Option<ContractId> = None,
+    },
+}
+
+#[fallback]
+#[storage(read)]
+fn fallback() {
+    run_external(storage::SRC14.target.read().unwrap())
+}
@@ -0,0 +1,18 @@
+contract;
+
+use std::execution::run_external;
+
+storage {
+    SRC14 {
+        target in 0x7bb458adc1d118713319a5baa00a2d049dd64d2916477d2688d76970c898cd55: Option<ContractId> = None,
+        allowed_caller in 0x0d3c2d6d4b8e2fd3eab4d1f6c2a9bd4ff2af7a6a0ebf0d4b3e0c4fd8d6f1a1c2: Option<Identity> = None,
+    },
+}
+
+// Report entry should not be created
+#[fallback]
+#[storage(read)]
+fn fallback() {
+    require(storage::SRC14.allowed_caller.read() == Some(msg_sender().unwrap()), "Not allowed");
+    run_external(storage::SRC14.target.read().unwrap())
+}
@@ -0,0 +1,17 @@
+contract;
+
+use std::execution::run_external;
+
+storage {
+    SRC14 {
+        target in 0x7bb458adc1d118713319a5baa00a2d049dd64d2916477d2688d76970c898cd55: Option<ContractId> = None,
+    },
+}
+
+// Report entry should be created:
+// L15: The `fallback` function delegates calls to the proxy target without access restriction. Consider checking against `msg_sender()` in order to limit which callers can reach the implementation contract.
+#[fallback]
+#[storage(read)]
+fn fallback() {
+    run_external(storage::SRC14.target.read().unwrap())
+}
@@ -0,0 +1,43 @@
+contract;
+
+use std::execution::run_external;
+
+abi SRC14 {
+    #[storage(read, write)]
+    fn set_proxy_target(new_target: ContractId);
+
+    #[storage(read)]
+    fn proxy_target() -> Option<ContractId>;
+}
+
+storage {
+    SRC14 {
+        target in 0x7bb458adc1d118713319a5baa00a2d049dd64d2916477d2688d76970c898cd55: Option<ContractId> = None,
+        owner in 0xbb79927b15d9259ea316f2ecb2297d6cc8851888a98278c0a2e03e1a091ea754: Option<Identity> = None,
+    },
+}
+
+#[storage(read)]
+fn only_current_owner() {
+    require(storage::SRC14.owner.read() == Some(msg_sender().unwrap()), "Not owner");
+}
+
+impl SRC14 for Contract {
+    // Report entry should not be created
+    #[storage(read, write)]
+    fn set_proxy_target(new_target: ContractId) {
+        only_current_owner();
+        storage::SRC14.target.write(Some(new_target));
+    }
+
+    #[storage(read)]
+    fn proxy_target() -> Option<ContractId> {
+        storage::SRC14.target.read()
+    }
+}
+
+#[fallback]
+#[storage(read)]
+fn fallback() {
+    run_external(storage::SRC14.target.read().unwrap())
+}
@@ -0,0 +1,37 @@
+contract;
+
+use std::execution::run_external;
+
+abi SRC14 {
+    #[storage(read, write)]
+    fn set_proxy_target(new_target: ContractId);
+
+    #[storage(read)]
+    fn proxy_target() -> Option<ContractId>;
+}
+
+storage {
+    SRC14 {
+        target in 0x7bb458adc1d118713319a5baa00a2d049dd64d2916477d2688d76970c898cd55: Option<ContractId> = None,
+    },
+}
+
+impl SRC14 for Contract {
+    // Report entry should be created:
+    // L23: The `Contract::set_proxy_target` function can change the proxy target without access restriction. Consider checking against `msg_sender()` in order to limit access.
+    #[storage(read, write)]
+    fn set_proxy_target(new_target: ContractId) {
+        storage::SRC14.target.write(Some(new_target));
+    }
+
+    #[storage(read)]
+    fn proxy_target() -> Option<ContractId> {
+        storage::SRC14.target.read()
+    }
+}
+
+#[fallback]
+#[storage(read)]
+fn fallback() {
+    run_external(storage::SRC14.target.read().unwrap())
+}
@@ -0,0 +1,107 @@
+contract;
+
+use std::{bytes::Bytes, inputs::{input_message_data, input_message_nonce, input_message_sender}, message::send_message};
+
+configurable {
+    BRIDGE: b256 = b256::zero(),
+    MAX_WITHDRAWAL: u64 = 1000,
+}
+
+storage {
+    processed_nonces: StorageMap<b256, bool> = StorageMap {},
+    balances: StorageMap<b256, u64> = StorageMap {},
+}
+
+abi Bridge {
+    #[storage(read, write)]
+    fn process_message(input_index: u64);
+
+    #[storage(read, write)]
+    fn process_message_checked(input_index: u64);
+
+    fn withdraw(recipient: b256, amount: u64);
+
+    fn withdraw_checked(amount: u64);
+}
+
+impl Bridge for Contract {
+    #[storage(read, write)]
+    fn process_message(input_index: u64) {
+        // Report entries should be created:
+        // L33: The `Contract::process_message` function processes a message input without validating its sender: `input_message_data(input_index, 0)`.
+        // L33: The `Contract::process_message` function processes a message input without checking its nonce: `input_message_data(input_index, 0)`.
+        let data = input_message_data(input_index, 0);
+        let recipient = b256::from_be_bytes(data.clone().split_at(32).0);
+        storage.balances.insert(recipient, storage.balances.get(recipient).try_read().unwrap_or(0) + 1);
+    }
+
+    #[storage(read, write)]
+    fn process_message_checked(input_index: u64) {
+        let sender = input_message_sender(input_index);
+        require(sender == BRIDGE, "Untrusted sender");
+
+        let nonce = input_message_nonce(input_index);
+        require(storage.processed_nonces.get(nonce).try_read().is_none(), "Message already processed");
+        storage.processed_nonces.insert(nonce, true);
+
+        let data = input_message_data(input_index, 0);
+        let recipient = b256::from_be_bytes(data.clone().split_at(32).0);
+        storage.balances.insert(recipient, storage.balances.get(recipient).try_read().unwrap_or(0) + 1);
+    }
+
+    fn withdraw(recipient: b256, amount: u64) {
+        // Report entries should be created:
+        // L56: The `Contract::withdraw` function sends a message whose recipient is controlled by the unvalidated `recipient` argument: `send_message(recipient, Bytes::new(), amount)`.
+        // L56: The `Contract::withdraw` function sends a message whose amount is controlled by the unvalidated `amount` argument: `send_message(recipient, Bytes::new(), amount)`.
+        send_message(recipient, Bytes::new(), amount);
+    }
+
+    fn withdraw_checked(amount: u64) {
+        require(amount <= MAX_WITHDRAWAL, "Withdrawal too large");
+
+        let recipient = msg_sender().unwrap().as_address().unwrap().bits();
+        send_message(recipient, Bytes::new(), amount);
+    }
+}
+
+abi ReplayableBridge {
+    #[storage(read, write)]
+    fn process_message_replayable(input_index: u64);
+
+    #[storage(read, write)]
+    fn process_message_bound_check(input_index: u64);
+}
+
+impl ReplayableBridge for Contract {
+    #[storage(read, write)]
+    fn process_message_replayable(input_index: u64) {
+        // Report entry should be created:
+        // L80: The `Contract::process_message_replayable` function processes a message input without checking its nonce: `input_message_sender(input_index)`.
+        let sender = input_message_sender(input_index);
+        require(sender == BRIDGE, "Untrusted sender");
+
+        // Recording the nonce without checking it first does not prevent replays
+        let nonce = input_message_nonce(input_index);
+        storage.processed_nonces.insert(nonce, true);
+
+        let data = input_message_data(input_index, 0);
+        let recipient = b256::from_be_bytes(data.clone().split_at(32).0);
+        storage.balances.insert(recipient, storage.balances.get(recipient).try_read().unwrap_or(0) + 1);
+    }
+
+    #[storage(read, write)]
+    fn process_message_bound_check(input_index: u64) {
+        let sender = input_message_sender(input_index);
+        require(sender == BRIDGE, "Untrusted sender");
+
+        // Report entry should not be created
+        let nonce = input_message_nonce(input_index);
+        let processed = storage.processed_nonces.get(nonce).try_read();
+        require(processed.is_none(), "Message already processed");
+        storage.processed_nonces.insert(nonce, true);
+
+        let data = input_message_data(input_index, 0);
+        let recipient = b256::from_be_bytes(data.clone().split_at(32).0);
+        storage.balances.insert(recipient, storage.balances.get(recipient).try_read().unwrap_or(0) + 1);
+    }
+}
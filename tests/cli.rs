@@ -0,0 +1,96 @@
+use std::process::Command;
+
+/// Runs the `sway-analyzer` binary with the supplied `args` and returns its stdout.
+fn run(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_sway-analyzer"))
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_code_quality_stdout() {
+    let stdout = run(&[
+        "--directory", "test/arbitrary_asset_transfer",
+        "--detectors", "arbitrary_asset_transfer",
+        "--display-format", "codequality",
+    ]);
+
+    let issues: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert!(!issues.as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_junit_xml_stdout() {
+    let stdout = run(&[
+        "--directory", "test/arbitrary_asset_transfer",
+        "--detectors", "arbitrary_asset_transfer",
+        "--display-format", "junitxml",
+    ]);
+
+    let stdout = stdout.trim_start();
+    assert!(stdout.starts_with("<?xml") || stdout.starts_with("<testsuites"));
+}
+
+#[test]
+fn test_json_stdout() {
+    let stdout = run(&[
+        "--directory", "test/arbitrary_asset_transfer",
+        "--detectors", "arbitrary_asset_transfer",
+        "--display-format", "json",
+    ]);
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert!(report.is_object());
+}
+
+#[test]
+fn test_query_json_stdout() {
+    let stdout = run(&[
+        "--directory", "test/query",
+        "--display-format", "json",
+        "query", "storage.$f.write($v)",
+    ]);
+
+    let matches: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert!(matches.is_array());
+}
+
+#[test]
+fn test_surface_json_stdout() {
+    let stdout = run(&[
+        "--directory", "test/surface",
+        "--display-format", "json",
+        "surface",
+    ]);
+
+    let entry_points: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert!(!entry_points.as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_storage_layout_json_stdout() {
+    let stdout = run(&[
+        "--directory", "test/storage_layout",
+        "--display-format", "json",
+        "storage-layout",
+    ]);
+
+    let fields: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert!(!fields.as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_storage_layout_csv_stdout() {
+    let stdout = run(&[
+        "--directory", "test/storage_layout",
+        "--display-format", "csv",
+        "storage-layout",
+    ]);
+
+    assert!(stdout.starts_with("file,line,field,type,initial_value,readers,writers,write_only,never_written\n"));
+}